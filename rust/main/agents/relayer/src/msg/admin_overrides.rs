@@ -0,0 +1,573 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Read, Write},
+    sync::RwLock,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::{Context, Result};
+use hyperlane_base::db::{HyperlaneDb, HyperlaneRocksDB};
+use hyperlane_core::{Decode, Encode, HyperlaneMessage, HyperlaneProtocolError, QueueOperation};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::settings::matching_list::MatchingList;
+
+// Stored under each origin's `HyperlaneRocksDB`, so this key MUST not be reused
+// for anything else.
+const ADMIN_OVERRIDES: &str = "relayer_admin_overrides_";
+// IDs of revoked overrides, so a database that missed the revocation cannot
+// bring them back when the stored sets are merged
+const ADMIN_OVERRIDE_REVOCATIONS: &str = "relayer_admin_override_revocations_";
+// Marks a message as having had its retries reset by a retry override
+const ADMIN_RETRY_APPLIED: &str = "relayer_admin_retry_applied_";
+
+/// How long a retry override keeps applying to rehydrated messages
+pub const RETRY_OVERRIDE_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+/// The most retry overrides kept at once. The oldest are evicted first, since
+/// every active retry override costs a lookup per rehydrated message.
+pub const MAX_RETRY_OVERRIDES: usize = 64;
+
+/// What a persisted override does to the messages it matches
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OverrideAction {
+    /// Matching operations are held in the paused queue
+    Pause,
+    /// Matching messages that were already indexed are never handed to a submitter
+    Drop,
+    /// Matching messages that were already indexed are rehydrated once without
+    /// their persisted retry count
+    Retry,
+}
+
+/// An operator intervention that is re-applied whenever messages are rehydrated
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AdminOverride {
+    pub id: String,
+    pub action: OverrideAction,
+    /// The matching list the override applies to, as it was submitted
+    pub pattern: Value,
+    /// Unix timestamp in seconds of when the override was created
+    pub created_at: u64,
+    /// The highest nonce indexed for each origin domain when the override was
+    /// created. Drop and retry overrides only apply to those messages, so they
+    /// never affect messages sent afterwards.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub up_to_nonces: BTreeMap<u32, u32>,
+    #[serde(skip)]
+    matching_list: MatchingList,
+}
+
+impl AdminOverride {
+    fn new(
+        action: OverrideAction,
+        pattern: Value,
+        up_to_nonces: BTreeMap<u32, u32>,
+    ) -> Result<Self> {
+        let matching_list =
+            serde_json::from_value(pattern.clone()).context("Invalid matching list")?;
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            action,
+            pattern,
+            created_at: unix_now(),
+            up_to_nonces,
+            matching_list,
+        })
+    }
+
+    /// Whether the override is scoped to include `message`
+    fn covers(&self, message: &HyperlaneMessage) -> bool {
+        match self.action {
+            OverrideAction::Pause => true,
+            OverrideAction::Drop | OverrideAction::Retry => self
+                .up_to_nonces
+                .get(&message.origin)
+                .is_some_and(|nonce| message.nonce <= *nonce),
+        }
+    }
+
+    /// Whether the override no longer applies at `now`. Only retry overrides
+    /// expire, after `RETRY_OVERRIDE_TTL`.
+    fn is_expired(&self, now: u64) -> bool {
+        self.action == OverrideAction::Retry
+            && now.saturating_sub(self.created_at) >= RETRY_OVERRIDE_TTL.as_secs()
+    }
+
+    /// The parsed `pattern`
+    pub fn matching_list(&self) -> &MatchingList {
+        &self.matching_list
+    }
+}
+
+/// Stores a value as JSON, since the matching lists are arbitrary JSON anyway
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+struct JsonEncoded<T>(T);
+
+impl<T: Serialize> Encode for JsonEncoded<T> {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let serialized = serde_json::to_vec(self)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Failed to serialize"))?;
+        writer.write(&serialized)
+    }
+}
+
+impl<T: DeserializeOwned> Decode for JsonEncoded<T> {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        serde_json::from_reader(reader).map_err(|err| {
+            HyperlaneProtocolError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to deserialize. Error: {}", err),
+            ))
+        })
+    }
+}
+
+/// The set of admin overrides, persisted to every origin database so they
+/// survive restarts and apply regardless of which origin a message is from.
+#[derive(Debug, Default)]
+pub struct AdminOverrides {
+    dbs: Vec<HyperlaneRocksDB>,
+    overrides: RwLock<Vec<AdminOverride>>,
+    revocations: RwLock<BTreeSet<String>>,
+}
+
+impl AdminOverrides {
+    /// Loads the overrides stored in any of `dbs` that were not revoked in any
+    /// of them, and writes the merged set back so that newly added origins pick
+    /// them up too.
+    pub fn load(dbs: Vec<HyperlaneRocksDB>) -> Result<Self> {
+        let mut revocations = BTreeSet::new();
+        for db in &dbs {
+            let stored = db
+                .retrieve_decodable::<JsonEncoded<BTreeSet<String>>>(
+                    ADMIN_OVERRIDE_REVOCATIONS,
+                    b"",
+                )?
+                .unwrap_or_default();
+            revocations.extend(stored.0);
+        }
+
+        let now = unix_now();
+        let mut overrides: Vec<AdminOverride> = vec![];
+        for db in &dbs {
+            let stored = db
+                .retrieve_decodable::<JsonEncoded<Vec<AdminOverride>>>(ADMIN_OVERRIDES, b"")?
+                .unwrap_or_default();
+            for mut stored in stored.0 {
+                if revocations.contains(&stored.id)
+                    || stored.is_expired(now)
+                    || overrides.iter().any(|o| o.id == stored.id)
+                {
+                    continue;
+                }
+                match serde_json::from_value(stored.pattern.clone()) {
+                    Ok(matching_list) => {
+                        stored.matching_list = matching_list;
+                        overrides.push(stored);
+                    }
+                    Err(err) => {
+                        warn!(
+                            id = stored.id,
+                            ?err,
+                            "Ignoring admin override with invalid pattern"
+                        )
+                    }
+                }
+            }
+        }
+        overrides.sort_by_key(|o| o.created_at);
+        info!(count = overrides.len(), "Loaded admin overrides");
+
+        let this = Self {
+            dbs,
+            overrides: RwLock::new(vec![]),
+            revocations: RwLock::new(revocations),
+        };
+        this.persist(&overrides)?;
+        this.persist_revocations(&this.revocations.read().expect("revocations lock poisoned"))?;
+        *this.overrides.write().expect("overrides lock poisoned") = overrides;
+        Ok(this)
+    }
+
+    /// All active overrides, oldest first
+    pub fn list(&self) -> Vec<AdminOverride> {
+        let now = unix_now();
+        self.overrides
+            .read()
+            .expect("overrides lock poisoned")
+            .iter()
+            .filter(|o| !o.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Stores a new override for the messages matching `pattern`
+    pub fn add(&self, action: OverrideAction, pattern: Value) -> Result<AdminOverride> {
+        let up_to_nonces = match action {
+            OverrideAction::Pause => BTreeMap::new(),
+            OverrideAction::Drop | OverrideAction::Retry => self.highest_nonces()?,
+        };
+        let new_override = AdminOverride::new(action, pattern, up_to_nonces)?;
+        let mut overrides = self.overrides.write().expect("overrides lock poisoned");
+        let now = unix_now();
+        overrides.retain(|o| !o.is_expired(now));
+        if action == OverrideAction::Retry {
+            let retries = overrides
+                .iter()
+                .filter(|o| o.action == OverrideAction::Retry)
+                .count();
+            let mut evicted = (retries + 1).saturating_sub(MAX_RETRY_OVERRIDES);
+            overrides.retain(|o| {
+                let evict = evicted > 0 && o.action == OverrideAction::Retry;
+                if evict {
+                    evicted -= 1;
+                }
+                !evict
+            });
+        }
+        overrides.push(new_override.clone());
+        self.persist(&overrides)?;
+        Ok(new_override)
+    }
+
+    /// Removes the override with the given id, returning it if it existed
+    pub fn revoke(&self, id: &str) -> Result<Option<AdminOverride>> {
+        let mut overrides = self.overrides.write().expect("overrides lock poisoned");
+        let Some(index) = overrides.iter().position(|o| o.id == id) else {
+            return Ok(None);
+        };
+        let revoked = overrides.remove(index);
+        self.record_revocations(std::slice::from_ref(&revoked))?;
+        self.persist(&overrides)?;
+        Ok(Some(revoked))
+    }
+
+    /// Removes all overrides with the given action and exactly this pattern
+    pub fn revoke_pattern(
+        &self,
+        action: OverrideAction,
+        pattern: &Value,
+    ) -> Result<Vec<AdminOverride>> {
+        let mut overrides = self.overrides.write().expect("overrides lock poisoned");
+        let (revoked, kept): (Vec<_>, Vec<_>) = overrides
+            .drain(..)
+            .partition(|o| o.action == action && &o.pattern == pattern);
+        *overrides = kept;
+        if !revoked.is_empty() {
+            self.record_revocations(&revoked)?;
+            self.persist(&overrides)?;
+        }
+        Ok(revoked)
+    }
+
+    /// Returns the first override with `action` that matches the message
+    pub fn find_for_message(
+        &self,
+        action: OverrideAction,
+        message: &HyperlaneMessage,
+    ) -> Option<AdminOverride> {
+        self.find_all_for_message(action, message)
+            .into_iter()
+            .next()
+    }
+
+    /// Returns the first retry override matching the message that has not
+    /// been applied to it yet
+    pub fn find_unapplied_retry(
+        &self,
+        message: &HyperlaneMessage,
+        origin_db: &HyperlaneRocksDB,
+    ) -> Result<Option<AdminOverride>> {
+        for retry in self.find_all_for_message(OverrideAction::Retry, message) {
+            let applied = origin_db
+                .retrieve_decodable::<bool>(ADMIN_RETRY_APPLIED, retry_key(&retry, message))?
+                .unwrap_or_default();
+            if !applied {
+                return Ok(Some(retry));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Records that `retry` reset the retries of `message`, so it never does again
+    pub fn mark_retry_applied(
+        &self,
+        retry: &AdminOverride,
+        message: &HyperlaneMessage,
+        origin_db: &HyperlaneRocksDB,
+    ) -> Result<()> {
+        origin_db.store_encodable(ADMIN_RETRY_APPLIED, retry_key(retry, message), &true)?;
+        Ok(())
+    }
+
+    /// Returns the first override with `action` that matches the operation
+    pub fn find_for_op(
+        &self,
+        action: OverrideAction,
+        op: &QueueOperation,
+    ) -> Option<AdminOverride> {
+        self.find(action, |list| list.op_matches(op))
+    }
+
+    fn find_all_for_message(
+        &self,
+        action: OverrideAction,
+        message: &HyperlaneMessage,
+    ) -> Vec<AdminOverride> {
+        let now = unix_now();
+        self.overrides
+            .read()
+            .expect("overrides lock poisoned")
+            .iter()
+            .filter(|o| {
+                o.action == action
+                    && !o.is_expired(now)
+                    && o.covers(message)
+                    && o.matching_list.msg_matches(message, false)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn find(
+        &self,
+        action: OverrideAction,
+        matches: impl Fn(&MatchingList) -> bool,
+    ) -> Option<AdminOverride> {
+        self.overrides
+            .read()
+            .expect("overrides lock poisoned")
+            .iter()
+            .find(|o| o.action == action && matches(&o.matching_list))
+            .cloned()
+    }
+
+    /// The highest indexed message nonce of every origin
+    fn highest_nonces(&self) -> Result<BTreeMap<u32, u32>> {
+        let mut nonces = BTreeMap::new();
+        for db in &self.dbs {
+            if let Some(nonce) = db.retrieve_highest_seen_message_nonce()? {
+                nonces.insert(db.domain().id(), nonce);
+            }
+        }
+        Ok(nonces)
+    }
+
+    fn persist(&self, overrides: &[AdminOverride]) -> Result<()> {
+        let list = JsonEncoded(overrides);
+        for db in &self.dbs {
+            db.store_encodable(ADMIN_OVERRIDES, b"", &list)?;
+        }
+        Ok(())
+    }
+
+    /// Tombstones the revoked overrides before they are removed from the
+    /// stored set, so a crash in between cannot resurrect them either
+    fn record_revocations(&self, revoked: &[AdminOverride]) -> Result<()> {
+        let mut revocations = self.revocations.write().expect("revocations lock poisoned");
+        revocations.extend(revoked.iter().map(|o| o.id.clone()));
+        self.persist_revocations(&revocations)
+    }
+
+    fn persist_revocations(&self, revocations: &BTreeSet<String>) -> Result<()> {
+        let list = JsonEncoded(revocations);
+        for db in &self.dbs {
+            db.store_encodable(ADMIN_OVERRIDE_REVOCATIONS, b"", &list)?;
+        }
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn retry_key(retry: &AdminOverride, message: &HyperlaneMessage) -> Vec<u8> {
+    [retry.id.as_bytes(), message.id().as_bytes()].concat()
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::HyperlaneDomain;
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_overrides_survive_reload() {
+        test_utils::run_test_db(|db| async move {
+            let origin_1 =
+                HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("origin1"), db.clone());
+            let origin_2 = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("origin2"), db);
+
+            // Test domains have id 0
+            let indexed = HyperlaneMessage {
+                destination: 42,
+                ..Default::default()
+            };
+            origin_1.store_message(&indexed, 1).unwrap();
+
+            let overrides = AdminOverrides::load(vec![origin_1.clone()]).unwrap();
+            let pause = overrides
+                .add(OverrideAction::Pause, json!([{ "destinationdomain": 42 }]))
+                .unwrap();
+            let drop = overrides
+                .add(OverrideAction::Drop, json!([{ "origindomain": 0 }]))
+                .unwrap();
+            assert!(overrides
+                .add(OverrideAction::Drop, json!({ "not": "a list" }))
+                .is_err());
+
+            // A newly configured origin picks up the existing overrides
+            let reloaded = AdminOverrides::load(vec![origin_1, origin_2.clone()]).unwrap();
+            let ids: Vec<_> = reloaded.list().into_iter().map(|o| o.id).collect();
+            assert_eq!(ids, vec![pause.id.clone(), drop.id.clone()]);
+
+            assert_eq!(
+                reloaded
+                    .find_for_message(OverrideAction::Drop, &indexed)
+                    .unwrap()
+                    .id,
+                drop.id
+            );
+            assert!(reloaded
+                .find_for_message(OverrideAction::Retry, &indexed)
+                .is_none());
+            // Messages sent after the drop are not dropped
+            let later = HyperlaneMessage {
+                nonce: 1,
+                ..indexed.clone()
+            };
+            assert!(reloaded
+                .find_for_message(OverrideAction::Drop, &later)
+                .is_none());
+
+            assert!(reloaded.revoke(&drop.id).unwrap().is_some());
+            assert!(reloaded.revoke(&drop.id).unwrap().is_none());
+            let revoked = reloaded
+                .revoke_pattern(OverrideAction::Pause, &json!([{ "destinationdomain": 42 }]))
+                .unwrap();
+            assert_eq!(revoked.len(), 1);
+
+            let reloaded = AdminOverrides::load(vec![origin_2]).unwrap();
+            assert!(reloaded.list().is_empty());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_revoked_overrides_are_not_resurrected() {
+        test_utils::run_test_db(|db| async move {
+            let origin_1 =
+                HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("origin1"), db.clone());
+            let origin_2 = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("origin2"), db);
+
+            let overrides = AdminOverrides::load(vec![origin_1.clone(), origin_2.clone()]).unwrap();
+            let pause = overrides
+                .add(OverrideAction::Pause, json!([{ "destinationdomain": 42 }]))
+                .unwrap();
+
+            // Revoked while the second origin was not configured, so its copy is stale
+            let overrides = AdminOverrides::load(vec![origin_1.clone()]).unwrap();
+            assert!(overrides.revoke(&pause.id).unwrap().is_some());
+
+            let reloaded = AdminOverrides::load(vec![origin_1, origin_2.clone()]).unwrap();
+            assert!(reloaded.list().is_empty());
+            // The tombstone is merged into every origin
+            let reloaded = AdminOverrides::load(vec![origin_2]).unwrap();
+            assert!(reloaded.list().is_empty());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_retry_overrides_expire_and_are_capped() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("origin"), db);
+            let message = HyperlaneMessage::default();
+            origin.store_message(&message, 1).unwrap();
+
+            let overrides = AdminOverrides::load(vec![origin.clone()]).unwrap();
+            let pause = overrides
+                .add(OverrideAction::Pause, json!([{ "destinationdomain": 42 }]))
+                .unwrap();
+            let retries: Vec<_> = (0..MAX_RETRY_OVERRIDES + 1)
+                .map(|_| {
+                    overrides
+                        .add(OverrideAction::Retry, json!([{ "origindomain": 0 }]))
+                        .unwrap()
+                })
+                .collect();
+
+            // The oldest retry is evicted, the pause is kept
+            let ids: Vec<_> = overrides.list().into_iter().map(|o| o.id).collect();
+            assert_eq!(ids.len(), MAX_RETRY_OVERRIDES + 1);
+            assert_eq!(ids[0], pause.id);
+            assert!(!ids.contains(&retries[0].id));
+
+            // Retries stop applying once they expire, pauses never do
+            for o in overrides
+                .overrides
+                .write()
+                .expect("overrides lock poisoned")
+                .iter_mut()
+            {
+                o.created_at -= RETRY_OVERRIDE_TTL.as_secs();
+            }
+            assert!(overrides
+                .find_unapplied_retry(&message, &origin)
+                .unwrap()
+                .is_none());
+            let ids: Vec<_> = overrides.list().into_iter().map(|o| o.id).collect();
+            assert_eq!(ids, vec![pause.id]);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_retry_applies_once() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("origin"), db);
+            let message = HyperlaneMessage::default();
+            origin.store_message(&message, 1).unwrap();
+
+            let overrides = AdminOverrides::load(vec![origin.clone()]).unwrap();
+            let retry = overrides
+                .add(OverrideAction::Retry, json!([{ "origindomain": 0 }]))
+                .unwrap();
+            assert_eq!(
+                overrides
+                    .find_unapplied_retry(&message, &origin)
+                    .unwrap()
+                    .unwrap()
+                    .id,
+                retry.id
+            );
+
+            overrides
+                .mark_retry_applied(&retry, &message, &origin)
+                .unwrap();
+            let reloaded = AdminOverrides::load(vec![origin.clone()]).unwrap();
+            assert!(reloaded
+                .find_unapplied_retry(&message, &origin)
+                .unwrap()
+                .is_none());
+        })
+        .await;
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    sync::Mutex,
+    time::Instant,
+};
+
+use hyperlane_core::QueueOperation;
+
+use crate::settings::AppContextSchedulingConf;
+
+use super::op_queue::OperationPriorityQueue;
+
+/// Virtual time an app context of weight 1 is charged per popped operation
+const VIRTUAL_TIME_PER_OP: u64 = 1_000_000;
+
+/// Pops operations from an `OpQueue` using start-time fair queuing keyed by
+/// `app_context()`, so one busy app context cannot starve the others sharing
+/// a destination. Only operations that are due are scheduled, and within an
+/// app context operations keep their queue order.
+#[derive(Debug)]
+pub struct AppContextScheduler {
+    confs: HashMap<String, AppContextSchedulingConf>,
+    /// Queues whose operations count towards each app context's `max_in_flight`
+    in_flight_queues: Vec<OperationPriorityQueue>,
+    state: Mutex<SchedulerState>,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    /// Start tag of the last popped operation
+    virtual_time: u64,
+    /// Finish tag of the last popped operation of each app context
+    finish_tags: HashMap<String, u64>,
+}
+
+impl AppContextScheduler {
+    pub fn new(
+        confs: HashMap<String, AppContextSchedulingConf>,
+        in_flight_queues: Vec<OperationPriorityQueue>,
+    ) -> Self {
+        Self {
+            confs,
+            in_flight_queues,
+            state: Default::default(),
+        }
+    }
+
+    /// Counts the operations of each app context in the in-flight queues
+    pub async fn in_flight(&self) -> HashMap<String, usize> {
+        let mut in_flight = HashMap::new();
+        for queue in &self.in_flight_queues {
+            for Reverse(op) in queue.lock().await.iter() {
+                *in_flight.entry(app_context_key(op)).or_default() += 1;
+            }
+        }
+        in_flight
+    }
+
+    /// Pops up to `limit` operations that are due at `now` from `queue`, skipping
+    /// app contexts that already have `max_in_flight` operations in flight.
+    /// Operations that are not due yet stay in the queue.
+    pub fn pop(
+        &self,
+        queue: &mut BinaryHeap<Reverse<QueueOperation>>,
+        limit: usize,
+        in_flight: &HashMap<String, usize>,
+        now: Instant,
+    ) -> Vec<QueueOperation> {
+        // Due operations sort before the others, so only those are taken off the queue
+        let mut backlogs: HashMap<String, VecDeque<QueueOperation>> = HashMap::new();
+        while queue.peek().is_some_and(|Reverse(op)| is_due(op, now)) {
+            let Some(Reverse(op)) = queue.pop() else {
+                break;
+            };
+            backlogs
+                .entry(app_context_key(&op))
+                .or_default()
+                .push_back(op);
+        }
+
+        let mut state = self.state.lock().expect("scheduler lock poisoned");
+        let mut popped = vec![];
+        let mut popped_per_context: HashMap<String, usize> = HashMap::new();
+        while popped.len() < limit {
+            let next = backlogs
+                .iter_mut()
+                .filter(|(app_context, backlog)| {
+                    !backlog.is_empty()
+                        && !self.at_max_in_flight(
+                            app_context,
+                            in_flight.get(*app_context).copied().unwrap_or_default()
+                                + popped_per_context
+                                    .get(*app_context)
+                                    .copied()
+                                    .unwrap_or_default(),
+                        )
+                })
+                .map(|(app_context, backlog)| {
+                    let start_tag = state
+                        .finish_tags
+                        .get(app_context)
+                        .copied()
+                        .unwrap_or_default()
+                        .max(state.virtual_time);
+                    (start_tag, app_context, backlog)
+                })
+                .min_by(|(a_tag, _, a_backlog), (b_tag, _, b_backlog)| {
+                    a_tag
+                        .cmp(b_tag)
+                        .then_with(|| a_backlog.front().cmp(&b_backlog.front()))
+                });
+            let Some((start_tag, app_context, backlog)) = next else {
+                break;
+            };
+            let Some(op) = backlog.pop_front() else {
+                break;
+            };
+
+            // Only the popped operation is charged to its app context
+            let weight = u64::from(self.conf(app_context).weight.max(1));
+            state.virtual_time = start_tag;
+            state.finish_tags.insert(
+                app_context.clone(),
+                start_tag + VIRTUAL_TIME_PER_OP / weight,
+            );
+            *popped_per_context.entry(app_context.clone()).or_default() += 1;
+            popped.push(op);
+        }
+
+        queue.extend(backlogs.into_values().flatten().map(Reverse));
+        popped
+    }
+
+    fn conf(&self, app_context: &str) -> AppContextSchedulingConf {
+        self.confs.get(app_context).copied().unwrap_or_default()
+    }
+
+    fn at_max_in_flight(&self, app_context: &str, in_flight: usize) -> bool {
+        self.conf(app_context)
+            .max_in_flight
+            .is_some_and(|max| in_flight >= max as usize)
+    }
+}
+
+fn is_due(op: &QueueOperation, now: Instant) -> bool {
+    op.next_attempt_after().map_or(true, |at| at <= now)
+}
+
+/// Operations without an app context are scheduled together
+fn app_context_key(op: &QueueOperation) -> String {
+    op.app_context().unwrap_or_default()
+}
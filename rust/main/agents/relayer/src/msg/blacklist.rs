@@ -1,33 +1,374 @@
-use hyperlane_core::HyperlaneMessage;
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
-#[derive(Debug, Clone, Default)]
+use base64::{engine::general_purpose, Engine};
+use ethers::utils::hex;
+use eyre::{bail, eyre, Context, Result};
+use hyperlane_core::{Decode, HyperlaneMessage, H160, H256};
+use hyperlane_warp_route::TokenMessage;
+use itertools::Itertools;
+use prometheus::IntCounterVec;
+use reqwest::{Client, Url};
+use tokio::task::JoinHandle;
+use tracing::{info, info_span, instrument::Instrumented, warn, Instrument};
+
+/// How long fetching the blacklist from a URL may take before the reload fails
+const BLACKLIST_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A single address that messages may not interact with.
+///
+/// Typed entries are decoded from their native encoding and compared against
+/// the 32 byte form Hyperlane uses for that protocol, so they only ever match
+/// whole addresses. Untyped hex entries are kept for backwards compatibility
+/// and are matched as a raw byte sequence anywhere in the message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BlacklistEntry {
+    /// An EVM address, e.g. `evm:0x...`
+    Evm(H160),
+    /// A Sealevel public key, e.g. `sealevel:<base58>`
+    Sealevel(H256),
+    /// A Cosmos bech32 address, e.g. `cosmos:neutron1...`
+    Cosmos { address: String, digest: H256 },
+    /// A TON address in raw (`ton:0:<hex>`) or user-friendly (`ton:EQ...`) form
+    Ton { workchain: i32, hash: H256 },
+    /// Any bytes, matched as a subsequence of the sender, recipient or body
+    Raw(Vec<u8>),
+}
+
+impl BlacklistEntry {
+    /// The 32 byte address this entry corresponds to, if it is typed.
+    pub fn digest(&self) -> Option<H256> {
+        match self {
+            BlacklistEntry::Evm(address) => Some((*address).into()),
+            BlacklistEntry::Sealevel(pubkey) => Some(*pubkey),
+            BlacklistEntry::Cosmos { digest, .. } => Some(*digest),
+            BlacklistEntry::Ton { hash, .. } => Some(*hash),
+            BlacklistEntry::Raw(_) => None,
+        }
+    }
+
+    fn parse_ton(address: &str) -> Result<Self> {
+        if let Some((workchain, hash)) = address.split_once(':') {
+            let workchain = workchain.parse().context("Invalid TON workchain")?;
+            let hash = hex::decode(hash).context("Invalid TON account id")?;
+            if hash.len() != 32 {
+                bail!("TON account id must be 32 bytes, got {}", hash.len());
+            }
+            return Ok(BlacklistEntry::Ton {
+                workchain,
+                hash: H256::from_slice(&hash),
+            });
+        }
+
+        // User-friendly addresses are 36 bytes of flags, workchain, account id and
+        // a CRC16 checksum, in either url-safe or standard base64.
+        let normalized = address.replace('-', "+").replace('_', "/");
+        let bytes = general_purpose::STANDARD
+            .decode(normalized)
+            .context("Invalid TON user-friendly address")?;
+        if bytes.len() != 36 {
+            bail!(
+                "TON user-friendly address must be 36 bytes, got {}",
+                bytes.len()
+            );
+        }
+        let checksum = u16::from_be_bytes([bytes[34], bytes[35]]);
+        if crc16_xmodem(&bytes[..34]) != checksum {
+            bail!("TON user-friendly address has an invalid checksum");
+        }
+        Ok(BlacklistEntry::Ton {
+            workchain: bytes[1] as i8 as i32,
+            hash: H256::from_slice(&bytes[2..34]),
+        })
+    }
+}
+
+impl FromStr for BlacklistEntry {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let Some((protocol, address)) = s.split_once(':') else {
+            return Ok(BlacklistEntry::Raw(decode_hex(s)?));
+        };
+
+        match protocol.to_lowercase().as_str() {
+            "evm" | "ethereum" => {
+                let bytes = decode_hex(address)?;
+                if bytes.len() != 20 {
+                    bail!("EVM address must be 20 bytes, got {}", bytes.len());
+                }
+                Ok(BlacklistEntry::Evm(H160::from_slice(&bytes)))
+            }
+            "sealevel" => {
+                let bytes = bs58::decode(address)
+                    .into_vec()
+                    .context("Invalid base58 Sealevel pubkey")?;
+                if bytes.len() != 32 {
+                    bail!("Sealevel pubkey must be 32 bytes, got {}", bytes.len());
+                }
+                Ok(BlacklistEntry::Sealevel(H256::from_slice(&bytes)))
+            }
+            "cosmos" => {
+                let (_, bytes) = bech32::decode(address).context("Invalid bech32 address")?;
+                if bytes.len() > 32 {
+                    bail!(
+                        "Cosmos address must be at most 32 bytes, got {}",
+                        bytes.len()
+                    );
+                }
+                // Shorter account addresses are left-padded, as in the cosmos chain crate
+                let mut digest = H256::zero();
+                digest.as_bytes_mut()[32 - bytes.len()..].copy_from_slice(&bytes);
+                Ok(BlacklistEntry::Cosmos {
+                    address: address.to_owned(),
+                    digest,
+                })
+            }
+            "ton" => BlacklistEntry::parse_ton(address),
+            "raw" => Ok(BlacklistEntry::Raw(decode_hex(address)?)),
+            _ => Err(eyre!("Unknown address protocol `{protocol}`")),
+        }
+    }
+}
+
+impl Display for BlacklistEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlacklistEntry::Evm(address) => write!(f, "evm:{address:?}"),
+            BlacklistEntry::Sealevel(pubkey) => {
+                write!(f, "sealevel:{}", bs58::encode(pubkey).into_string())
+            }
+            BlacklistEntry::Cosmos { address, .. } => write!(f, "cosmos:{address}"),
+            BlacklistEntry::Ton { workchain, hash } => {
+                write!(f, "ton:{workchain}:{}", hex::encode(hash))
+            }
+            BlacklistEntry::Raw(bytes) => write!(f, "raw:0x{}", hex::encode(bytes)),
+        }
+    }
+}
+
+/// The part of a message a blacklisted address was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageField {
+    Sender,
+    Recipient,
+    /// The recipient of a warp route token transfer
+    TokenRecipient,
+    Body,
+}
+
+impl MessageField {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MessageField::Sender => "sender",
+            MessageField::Recipient => "recipient",
+            MessageField::TokenRecipient => "token_recipient",
+            MessageField::Body => "body",
+        }
+    }
+}
+
+/// A blacklisted address found in a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlacklistHit {
+    pub entry: BlacklistEntry,
+    pub field: MessageField,
+}
+
+impl Display for BlacklistHit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in {}", self.entry, self.field.as_str())
+    }
+}
+
+/// Where to periodically reload additional blacklist entries from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlacklistSource {
+    File(PathBuf),
+    Url(Url),
+}
+
+impl FromStr for BlacklistSource {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match Url::parse(s) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(BlacklistSource::Url(url)),
+            Ok(url) if url.scheme() == "file" => url
+                .to_file_path()
+                .map(BlacklistSource::File)
+                .map_err(|_| eyre!("Invalid file url `{s}`")),
+            Ok(url) => Err(eyre!(
+                "Unsupported blacklist source scheme `{}`",
+                url.scheme()
+            )),
+            Err(_) => Ok(BlacklistSource::File(PathBuf::from(s))),
+        }
+    }
+}
+
+impl BlacklistSource {
+    async fn fetch(&self) -> Result<String> {
+        match self {
+            BlacklistSource::File(path) => tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("Failed to read blacklist file {}", path.display())),
+            BlacklistSource::Url(url) => Ok(Client::builder()
+                .timeout(BLACKLIST_FETCH_TIMEOUT)
+                .build()?
+                .get(url.clone())
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct AddressBlacklist {
-    // A list of addresses that are blocked from being relayed.
-    // Addresses are any length to support different address types.
-    pub blacklist: Vec<Vec<u8>>,
+    // Entries from the agent config, which are never reloaded.
+    configured: Vec<BlacklistEntry>,
+    // Entries from the blacklist source, replaced on every successful reload.
+    loaded: RwLock<Arc<Vec<BlacklistEntry>>>,
+    hits: Option<IntCounterVec>,
 }
 
 impl AddressBlacklist {
-    pub fn new(blacklist: Vec<Vec<u8>>) -> Self {
-        Self { blacklist }
-    }
-
-    /// Returns true if the message is blocked by the blacklist.
-    /// At the moment, this only checks if the sender, recipient, or body of the
-    /// message contains any of the blocked addresses.
-    pub fn find_blacklisted_address(&self, message: &HyperlaneMessage) -> Option<Vec<u8>> {
-        self.blacklist.iter().find_map(|address| {
-            if is_subsequence(message.sender.as_bytes(), address)
-                || is_subsequence(message.recipient.as_bytes(), address)
-                || is_subsequence(&message.body, address)
-            {
-                // Return the blocked address that was found.
-                Some(address.clone())
-            } else {
-                None
+    pub fn new(blacklist: Vec<BlacklistEntry>) -> Self {
+        Self {
+            configured: blacklist,
+            ..Default::default()
+        }
+    }
+
+    /// Count hits per entry and message field in the given counter.
+    pub fn with_metrics(mut self, hits: IntCounterVec) -> Self {
+        self.hits = Some(hits);
+        self
+    }
+
+    /// Replaces the entries loaded from the blacklist source.
+    pub fn replace_loaded(&self, entries: Vec<BlacklistEntry>) {
+        *self.loaded.write().expect("blacklist lock poisoned") = Arc::new(entries);
+    }
+
+    /// Fetches the entries from `source` and replaces the loaded entries with
+    /// them. On error the previously loaded entries are kept.
+    pub async fn reload(&self, source: &BlacklistSource) -> Result<usize> {
+        let entries = parse_entries(&source.fetch().await?)?;
+        let count = entries.len();
+        self.replace_loaded(entries);
+        Ok(count)
+    }
+
+    /// Reloads the blacklist from `source` every `interval`.
+    pub fn spawn_reloader(
+        self: Arc<Self>,
+        source: BlacklistSource,
+        interval: Duration,
+    ) -> Instrumented<JoinHandle<()>> {
+        tokio::spawn(async move {
+            loop {
+                match self.reload(&source).await {
+                    Ok(count) => info!(count, ?source, "Reloaded address blacklist"),
+                    Err(err) => warn!(?err, ?source, "Failed to reload address blacklist"),
+                }
+                tokio::time::sleep(interval).await;
             }
         })
+        .instrument(info_span!("AddressBlacklistReloader"))
     }
+
+    /// Returns the first blacklisted address the message involves, if any.
+    /// Typed entries are compared against the sender, the recipient and, if the
+    /// body is a warp route token message, the token recipient. Raw entries are
+    /// searched for in the sender, recipient and body bytes.
+    pub fn find_blacklisted_address(&self, message: &HyperlaneMessage) -> Option<BlacklistHit> {
+        let token_recipient = TokenMessage::read_from(&mut message.body.as_slice())
+            .ok()
+            .map(|token_message| token_message.recipient());
+
+        let loaded = self.loaded.read().expect("blacklist lock poisoned").clone();
+        let hit = self
+            .configured
+            .iter()
+            .chain(loaded.iter())
+            .find_map(|entry| {
+                let field = match entry.digest() {
+                    Some(digest) if digest == message.sender => MessageField::Sender,
+                    Some(digest) if digest == message.recipient => MessageField::Recipient,
+                    Some(digest) if Some(digest) == token_recipient => MessageField::TokenRecipient,
+                    Some(_) => return None,
+                    None => {
+                        let BlacklistEntry::Raw(bytes) = entry else {
+                            return None;
+                        };
+                        if is_subsequence(message.sender.as_bytes(), bytes) {
+                            MessageField::Sender
+                        } else if is_subsequence(message.recipient.as_bytes(), bytes) {
+                            MessageField::Recipient
+                        } else if is_subsequence(&message.body, bytes) {
+                            MessageField::Body
+                        } else {
+                            return None;
+                        }
+                    }
+                };
+                Some(BlacklistHit {
+                    entry: entry.clone(),
+                    field,
+                })
+            })?;
+
+        if let Some(hits) = &self.hits {
+            let entry = hit.entry.to_string();
+            hits.with_label_values(&[entry.as_str(), hit.field.as_str()])
+                .inc();
+        }
+        Some(hit)
+    }
+}
+
+/// Parses a list of blacklist entries separated by commas or newlines.
+/// Blank lines and lines starting with `#` are ignored.
+pub fn parse_entries(s: &str) -> Result<Vec<BlacklistEntry>> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            entry
+                .parse()
+                .with_context(|| format!("Invalid blacklist entry `{entry}`"))
+        })
+        .try_collect()
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    Ok(hex::decode(s.strip_prefix("0x").unwrap_or(s))?)
+}
+
+/// CRC16/XMODEM, as used for TON user-friendly address checksums.
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
 }
 
 /// Returns true if `needle` is a subsequence of `haystack`.
@@ -50,7 +391,8 @@ fn is_subsequence<T: PartialEq>(mut haystack: &[T], needle: &[T]) -> bool {
 
 #[cfg(test)]
 mod test {
-    use hyperlane_core::H256;
+    use hyperlane_core::{Encode, H256};
+    use prometheus::{opts, Registry};
 
     use super::*;
 
@@ -70,7 +412,13 @@ mod test {
     #[test]
     fn test_is_blocked() {
         let blocked = b"blocked";
-        let blocklist = AddressBlacklist::new(vec![blocked.to_vec()]);
+        let blocklist = AddressBlacklist::new(vec![BlacklistEntry::Raw(blocked.to_vec())]);
+        let hit = |field| {
+            Some(BlacklistHit {
+                entry: BlacklistEntry::Raw(blocked.to_vec()),
+                field,
+            })
+        };
 
         let bytes_with_subsequence = |subsequence: &[u8], index: usize, len: usize| {
             let mut bytes = vec![0; len];
@@ -90,7 +438,7 @@ mod test {
         };
         assert_eq!(
             blocklist.find_blacklisted_address(&message),
-            Some(blocked.to_vec())
+            hit(MessageField::Sender)
         );
 
         // Blocked - recipient includes the blocked address
@@ -100,7 +448,7 @@ mod test {
         };
         assert_eq!(
             blocklist.find_blacklisted_address(&message),
-            Some(blocked.to_vec())
+            hit(MessageField::Recipient)
         );
 
         // Blocked - body includes the blocked address
@@ -110,7 +458,7 @@ mod test {
         };
         assert_eq!(
             blocklist.find_blacklisted_address(&message),
-            Some(blocked.to_vec())
+            hit(MessageField::Body)
         );
 
         // Not blocked - sender, recipient, and body do not include the blocked address
@@ -120,4 +468,138 @@ mod test {
         };
         assert!(blocklist.find_blacklisted_address(&message).is_none());
     }
+
+    #[test]
+    fn test_parse_typed_entries() {
+        let evm: BlacklistEntry = "evm:0x4242424242424242424242424242424242424242"
+            .parse()
+            .unwrap();
+        assert_eq!(evm, BlacklistEntry::Evm(H160::repeat_byte(0x42)));
+        assert_eq!(evm.digest(), Some(H256::from(H160::repeat_byte(0x42))));
+
+        let pubkey = H256::repeat_byte(0x42);
+        let sealevel: BlacklistEntry =
+            format!("sealevel:{}", bs58::encode(pubkey.as_bytes()).into_string())
+                .parse()
+                .unwrap();
+        assert_eq!(sealevel, BlacklistEntry::Sealevel(pubkey));
+
+        let cosmos: BlacklistEntry =
+            "cosmos:dual1pk99xge6q94qtu3568x3qhp68zzv0mx7za4ct008ks36qhx5tvss3qawfh"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            cosmos.digest(),
+            Some(
+                H256::from_str("0d8a53233a016a05f234d1cd105c3a3884c7ecde176b85bde7b423a05cd45b21")
+                    .unwrap()
+            )
+        );
+
+        let ton_raw: BlacklistEntry = format!("ton:0:{}", hex::encode(pubkey)).parse().unwrap();
+        let ton_friendly: BlacklistEntry = "ton:EQBCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQmXi"
+            .parse()
+            .unwrap();
+        assert_eq!(ton_raw, ton_friendly);
+        assert_eq!(
+            "ton:Uf9CQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQsdv"
+                .parse::<BlacklistEntry>()
+                .unwrap(),
+            BlacklistEntry::Ton {
+                workchain: -1,
+                hash: pubkey
+            }
+        );
+
+        // Untyped hex is still accepted as a raw entry
+        assert_eq!(
+            "0x626c6f636b6564".parse::<BlacklistEntry>().unwrap(),
+            BlacklistEntry::Raw(b"blocked".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_entries() {
+        // Too short for an EVM address
+        assert!("evm:0x4242".parse::<BlacklistEntry>().is_err());
+        // Not base58
+        assert!("sealevel:0OIl".parse::<BlacklistEntry>().is_err());
+        // Bad checksum
+        assert!("ton:EQBCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQmXj"
+            .parse::<BlacklistEntry>()
+            .is_err());
+        assert!("foo:0x42".parse::<BlacklistEntry>().is_err());
+    }
+
+    #[test]
+    fn test_typed_entries_match_whole_addresses() {
+        let address = H160::repeat_byte(0x42);
+        let blacklist = AddressBlacklist::new(vec![BlacklistEntry::Evm(address)]);
+
+        let message = HyperlaneMessage {
+            recipient: address.into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            blacklist.find_blacklisted_address(&message).unwrap().field,
+            MessageField::Recipient
+        );
+
+        // The address bytes appearing inside another address or the body is not a hit
+        let mut sender = H256::repeat_byte(0x01);
+        sender.as_bytes_mut()[4..24].copy_from_slice(address.as_bytes());
+        let message = HyperlaneMessage {
+            sender,
+            body: address.as_bytes().to_vec(),
+            ..Default::default()
+        };
+        assert!(blacklist.find_blacklisted_address(&message).is_none());
+    }
+
+    #[test]
+    fn test_token_recipient_is_blocked() {
+        let recipient = H256::repeat_byte(0x42);
+        let blacklist = AddressBlacklist::new(vec![BlacklistEntry::Sealevel(recipient)]);
+        let registry = Registry::new();
+        let hits = IntCounterVec::new(opts!("hits", "help"), &["entry", "field"]).unwrap();
+        registry.register(Box::new(hits.clone())).unwrap();
+        let blacklist = blacklist.with_metrics(hits.clone());
+
+        let message = HyperlaneMessage {
+            body: TokenMessage::new(recipient, 100.into(), vec![]).to_vec(),
+            ..Default::default()
+        };
+        let hit = blacklist.find_blacklisted_address(&message).unwrap();
+        assert_eq!(hit.field, MessageField::TokenRecipient);
+        assert_eq!(
+            hits.with_label_values(&[hit.entry.to_string().as_str(), "token_recipient"])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reload_from_file() {
+        let path = std::env::temp_dir().join(format!("blacklist-{}.txt", H256::random()));
+        let address = H160::repeat_byte(0x42);
+        std::fs::write(&path, format!("# sanctioned\nevm:{address:?}\n\n")).unwrap();
+
+        let blacklist = AddressBlacklist::default();
+        let message = HyperlaneMessage {
+            sender: address.into(),
+            ..Default::default()
+        };
+        assert!(blacklist.find_blacklisted_address(&message).is_none());
+
+        let source = BlacklistSource::from_str(path.to_str().unwrap()).unwrap();
+        assert_eq!(blacklist.reload(&source).await.unwrap(), 1);
+        assert!(blacklist.find_blacklisted_address(&message).is_some());
+
+        // An invalid file keeps the previously loaded entries
+        std::fs::write(&path, "evm:0xzz").unwrap();
+        assert!(blacklist.reload(&source).await.is_err());
+        assert!(blacklist.find_blacklisted_address(&message).is_some());
+
+        std::fs::remove_file(path).unwrap();
+    }
 }
@@ -0,0 +1,690 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::Result;
+use hyperlane_base::{
+    db::{HyperlaneDb, HyperlaneRocksDB},
+    CoreMetrics,
+};
+use hyperlane_core::{GasPaymentKey, HyperlaneMessage, H256, U256};
+use prometheus::{GaugeVec, IntGaugeVec};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, info_span, instrument::Instrumented, warn, Instrument};
+
+use crate::{msg::metadata::AppContextClassifier, settings::matching_list::MatchingList};
+
+/// Label used for messages that don't belong to an app context
+const UNKNOWN_APP_CONTEXT: &str = "Unknown";
+
+/// How long a message that isn't delivered keeps being re-read for new payments
+/// and expenditure. Bounds the work of each report when messages are never
+/// delivered, e.g. because they aren't paid for.
+const OPEN_MESSAGE_TTL: Duration = Duration::from_secs(60 * 60 * 24 * 7);
+
+/// Gas paid for and spent on the messages of one route and app context
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasAccountingEntry {
+    pub origin_domain: u32,
+    pub destination_domain: u32,
+    pub app_context: Option<String>,
+    /// Messages dispatched on the route
+    pub message_count: u64,
+    /// Messages with a gas payment on the origin
+    pub paid_message_count: u64,
+    /// Messages the relayer has spent gas on delivering
+    pub spent_message_count: u64,
+    /// Paid to the IGP, in the smallest unit of the origin's native token
+    pub total_payment: U256,
+    /// Destination gas paid for
+    pub total_gas_amount: U256,
+    /// Spent on delivery, in the smallest unit of the destination's native token
+    pub total_tokens_used: U256,
+    /// Destination gas used by delivery
+    pub total_gas_used: U256,
+}
+
+impl GasAccountingEntry {
+    fn add_message(&mut self, gas: &MessageGas) {
+        self.message_count += 1;
+        self.add_gas(gas);
+    }
+
+    fn add_gas(&mut self, gas: &MessageGas) {
+        if let Some((payment, gas_amount)) = gas.payment {
+            self.paid_message_count += 1;
+            self.total_payment = self.total_payment.saturating_add(payment);
+            self.total_gas_amount = self.total_gas_amount.saturating_add(gas_amount);
+        }
+        if gas.is_spent() {
+            self.spent_message_count += 1;
+            self.total_tokens_used = self.total_tokens_used.saturating_add(gas.tokens_used);
+            self.total_gas_used = self.total_gas_used.saturating_add(gas.gas_used);
+        }
+    }
+
+    fn remove_gas(&mut self, gas: &MessageGas) {
+        if let Some((payment, gas_amount)) = gas.payment {
+            self.paid_message_count = self.paid_message_count.saturating_sub(1);
+            self.total_payment = self.total_payment.saturating_sub(payment);
+            self.total_gas_amount = self.total_gas_amount.saturating_sub(gas_amount);
+        }
+        if gas.is_spent() {
+            self.spent_message_count = self.spent_message_count.saturating_sub(1);
+            self.total_tokens_used = self.total_tokens_used.saturating_sub(gas.tokens_used);
+            self.total_gas_used = self.total_gas_used.saturating_sub(gas.gas_used);
+        }
+    }
+}
+
+/// Payment and expenditure recorded for a message
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct MessageGas {
+    /// Total payment and destination gas paid for, if paid
+    payment: Option<(U256, U256)>,
+    tokens_used: U256,
+    gas_used: U256,
+}
+
+impl MessageGas {
+    fn is_spent(&self) -> bool {
+        !self.tokens_used.is_zero() || !self.gas_used.is_zero()
+    }
+}
+
+type EntryKey = (u32, u32, Option<String>);
+
+/// Running totals, updated with the messages indexed since the last report
+#[derive(Debug, Default)]
+struct AccountingState {
+    entries: BTreeMap<EntryKey, GasAccountingEntry>,
+    cursors: HashMap<u32, NonceCursor>,
+    /// Messages not processed yet, whose payments and expenditure may still change
+    open_messages: HashMap<H256, OpenMessage>,
+}
+
+/// Progress through the messages of an origin
+#[derive(Clone, Debug, Default)]
+struct NonceCursor {
+    next_nonce: u32,
+    /// Nonces below `next_nonce` whose message wasn't indexed yet
+    missing_nonces: BTreeSet<u32>,
+}
+
+#[derive(Debug)]
+struct OpenMessage {
+    key: EntryKey,
+    nonce: u32,
+    gas: MessageGas,
+    /// Unix timestamp in seconds of when the message was first read
+    opened_at: u64,
+}
+
+/// What changed in an origin's database since the last report. Messages come
+/// with whether they were processed on the destination yet.
+struct OriginChanges {
+    new_messages: Vec<(HyperlaneMessage, MessageGas, bool)>,
+    open_messages: Vec<(H256, MessageGas, bool)>,
+    cursor: NonceCursor,
+}
+
+/// Gas accounting of every route and app context the relayer knows messages of
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasAccountingReport {
+    /// Unix timestamp of when the report was built
+    pub generated_at: u64,
+    /// Sorted by origin, destination and app context
+    pub entries: Vec<GasAccountingEntry>,
+}
+
+impl GasAccountingReport {
+    /// Renders the report as CSV, with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "origin_domain,destination_domain,app_context,message_count,paid_message_count,\
+             spent_message_count,total_payment,total_gas_amount,total_tokens_used,total_gas_used\n",
+        );
+        for entry in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                entry.origin_domain,
+                entry.destination_domain,
+                csv_field(entry.app_context.as_deref().unwrap_or_default()),
+                entry.message_count,
+                entry.paid_message_count,
+                entry.spent_message_count,
+                entry.total_payment,
+                entry.total_gas_amount,
+                entry.total_tokens_used,
+                entry.total_gas_used,
+            ));
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// The latest report, unset until the first one is built
+pub type SharedGasAccountingReport = Arc<RwLock<Option<GasAccountingReport>>>;
+
+/// Periodically aggregates the gas payments and expenditures recorded in the
+/// origin databases into a `GasAccountingReport`, and exports it as metrics.
+#[derive(Debug)]
+pub struct GasAccountingReporter {
+    /// Databases of each origin, by domain id
+    dbs: HashMap<u32, HyperlaneRocksDB>,
+    app_context_classifier: AppContextClassifier,
+    /// Chain names used in metric labels, by domain id
+    domain_names: HashMap<u32, String>,
+    metrics: GasAccountingMetrics,
+    report: SharedGasAccountingReport,
+    state: AccountingState,
+}
+
+impl GasAccountingReporter {
+    pub fn new(
+        dbs: HashMap<u32, HyperlaneRocksDB>,
+        metric_app_contexts: Vec<(MatchingList, String)>,
+        domain_names: HashMap<u32, String>,
+        metrics: GasAccountingMetrics,
+    ) -> Self {
+        Self {
+            dbs,
+            app_context_classifier: AppContextClassifier::new(metric_app_contexts),
+            domain_names,
+            metrics,
+            report: Default::default(),
+            state: Default::default(),
+        }
+    }
+
+    /// A handle to the latest report
+    pub fn report(&self) -> SharedGasAccountingReport {
+        self.report.clone()
+    }
+
+    /// Rebuilds the report from the databases every `interval`.
+    pub fn spawn(mut self, interval: Duration) -> Instrumented<JoinHandle<()>> {
+        tokio::spawn(async move {
+            loop {
+                match self.build_report().await {
+                    Ok(report) => {
+                        info!(routes = report.entries.len(), "Built gas accounting report");
+                        self.metrics.update(&report, &self.domain_names);
+                        *self.report.write().expect("gas accounting lock poisoned") = Some(report);
+                    }
+                    Err(err) => warn!(?err, "Failed to build gas accounting report"),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+        .instrument(info_span!("GasAccountingReporter"))
+    }
+
+    /// Aggregates the payment and expenditure totals of every message in the
+    /// databases by route and app context. Totals are kept between reports, so
+    /// only the messages indexed since the last one and those not processed yet
+    /// are read again, the latter for up to `OPEN_MESSAGE_TTL`.
+    pub async fn build_report(&mut self) -> Result<GasAccountingReport> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        for (origin, db) in &self.dbs {
+            let cursor = self.state.cursors.get(origin).cloned().unwrap_or_default();
+            let open_messages = self
+                .state
+                .open_messages
+                .iter()
+                .filter(|(_, open)| open.key.0 == *origin)
+                .map(|(message_id, open)| (*message_id, open.key.1, open.nonce))
+                .collect();
+            let db = db.clone();
+            let changes = tokio::task::spawn_blocking(move || {
+                read_origin_changes(&db, cursor, open_messages)
+            })
+            .await??;
+
+            // Classified up front, so a failure leaves the totals untouched
+            let mut app_contexts = Vec::with_capacity(changes.new_messages.len());
+            for (message, _, _) in &changes.new_messages {
+                app_contexts.push(self.app_context_classifier.get_app_context(message).await?);
+            }
+
+            for ((message, gas, processed), app_context) in
+                changes.new_messages.into_iter().zip(app_contexts)
+            {
+                let key = (*origin, message.destination, app_context.clone());
+                self.state
+                    .entries
+                    .entry(key.clone())
+                    .or_insert_with(|| GasAccountingEntry {
+                        origin_domain: *origin,
+                        destination_domain: message.destination,
+                        app_context,
+                        ..Default::default()
+                    })
+                    .add_message(&gas);
+                if !processed {
+                    self.state.open_messages.insert(
+                        message.id(),
+                        OpenMessage {
+                            key,
+                            nonce: message.nonce,
+                            gas,
+                            opened_at: now,
+                        },
+                    );
+                }
+            }
+            for (message_id, gas, processed) in changes.open_messages {
+                let Some(open) = self.state.open_messages.get_mut(&message_id) else {
+                    continue;
+                };
+                if open.gas != gas {
+                    if let Some(entry) = self.state.entries.get_mut(&open.key) {
+                        entry.remove_gas(&open.gas);
+                        entry.add_gas(&gas);
+                    }
+                    open.gas = gas;
+                }
+                // Retries and top-ups are counted until the message is processed
+                if processed || now.saturating_sub(open.opened_at) >= OPEN_MESSAGE_TTL.as_secs() {
+                    self.state.open_messages.remove(&message_id);
+                }
+            }
+            self.state.cursors.insert(*origin, changes.cursor);
+        }
+
+        Ok(GasAccountingReport {
+            generated_at: now,
+            entries: self.state.entries.values().cloned().collect(),
+        })
+    }
+}
+
+/// Reads the messages indexed after `cursor`, and the current payments and
+/// expenditure of `open_messages`, given as message ids, destinations and nonces
+fn read_origin_changes(
+    db: &HyperlaneRocksDB,
+    mut cursor: NonceCursor,
+    open_messages: Vec<(H256, u32, u32)>,
+) -> Result<OriginChanges> {
+    let mut nonces = std::mem::take(&mut cursor.missing_nonces);
+    if let Some(highest_nonce) = db.retrieve_highest_seen_message_nonce()? {
+        if highest_nonce >= cursor.next_nonce {
+            nonces.extend(cursor.next_nonce..=highest_nonce);
+            cursor.next_nonce = highest_nonce.saturating_add(1);
+        }
+    }
+
+    let mut new_messages = vec![];
+    for nonce in nonces {
+        match db.retrieve_message_by_nonce(nonce)? {
+            Some(message) => {
+                let gas = message_gas(db, message.id(), message.destination)?;
+                let processed = is_processed(db, nonce)?;
+                new_messages.push((message, gas, processed));
+            }
+            None => {
+                cursor.missing_nonces.insert(nonce);
+            }
+        }
+    }
+    let open_messages = open_messages
+        .into_iter()
+        .map(|(message_id, destination, nonce)| {
+            Ok((
+                message_id,
+                message_gas(db, message_id, destination)?,
+                is_processed(db, nonce)?,
+            ))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(OriginChanges {
+        new_messages,
+        open_messages,
+        cursor,
+    })
+}
+
+fn is_processed(db: &HyperlaneRocksDB, nonce: u32) -> Result<bool> {
+    Ok(db.retrieve_processed_by_nonce(&nonce)?.unwrap_or_default())
+}
+
+fn message_gas(db: &HyperlaneRocksDB, message_id: H256, destination: u32) -> Result<MessageGas> {
+    let payment = db.retrieve_gas_payment_by_gas_payment_key(GasPaymentKey {
+        message_id,
+        destination,
+    })?;
+    let expenditure = db.retrieve_gas_expenditure_by_message_id(message_id)?;
+    Ok(MessageGas {
+        payment: payment.map(|p| (p.payment, p.gas_amount)),
+        tokens_used: expenditure.tokens_used,
+        gas_used: expenditure.gas_used,
+    })
+}
+
+/// Gauges mirroring the latest gas accounting report
+#[derive(Debug, Clone)]
+pub struct GasAccountingMetrics {
+    messages: IntGaugeVec,
+    payment: GaugeVec,
+    gas_amount: GaugeVec,
+    tokens_used: GaugeVec,
+    gas_used: GaugeVec,
+}
+
+impl GasAccountingMetrics {
+    pub fn new(metrics: &CoreMetrics) -> Result<Self> {
+        let labels = &["origin", "destination", "app_context"];
+        Ok(Self {
+            messages: metrics.new_int_gauge(
+                "gas_accounting_messages",
+                "Messages per route and app context, by whether they were paid for or spent on",
+                &["origin", "destination", "app_context", "accounting"],
+            )?,
+            payment: metrics.new_gauge(
+                "gas_accounting_payment",
+                "Total paid to the IGP, in the smallest unit of the origin's native token",
+                labels,
+            )?,
+            gas_amount: metrics.new_gauge(
+                "gas_accounting_gas_amount",
+                "Total destination gas paid for",
+                labels,
+            )?,
+            tokens_used: metrics.new_gauge(
+                "gas_accounting_tokens_used",
+                "Total spent on delivery, in the smallest unit of the destination's native token",
+                labels,
+            )?,
+            gas_used: metrics.new_gauge(
+                "gas_accounting_gas_used",
+                "Total destination gas used by delivery",
+                labels,
+            )?,
+        })
+    }
+
+    fn update(&self, report: &GasAccountingReport, domain_names: &HashMap<u32, String>) {
+        let name = |domain: u32| {
+            domain_names
+                .get(&domain)
+                .cloned()
+                .unwrap_or_else(|| domain.to_string())
+        };
+        for entry in &report.entries {
+            let origin = name(entry.origin_domain);
+            let destination = name(entry.destination_domain);
+            let app_context = entry.app_context.as_deref().unwrap_or(UNKNOWN_APP_CONTEXT);
+            let labels = [origin.as_str(), destination.as_str(), app_context];
+            for (accounting, count) in [
+                ("total", entry.message_count),
+                ("paid", entry.paid_message_count),
+                ("spent", entry.spent_message_count),
+            ] {
+                self.messages
+                    .with_label_values(&[
+                        origin.as_str(),
+                        destination.as_str(),
+                        app_context,
+                        accounting,
+                    ])
+                    .set(count as i64);
+            }
+            self.payment
+                .with_label_values(&labels)
+                .set(entry.total_payment.to_f64_lossy());
+            self.gas_amount
+                .with_label_values(&labels)
+                .set(entry.total_gas_amount.to_f64_lossy());
+            self.tokens_used
+                .with_label_values(&labels)
+                .set(entry.total_tokens_used.to_f64_lossy());
+            self.gas_used
+                .with_label_values(&labels)
+                .set(entry.total_gas_used.to_f64_lossy());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{
+        HyperlaneDomain, InterchainGasExpenditure, InterchainGasPayment, KnownHyperlaneDomain,
+        LogMeta, H256,
+    };
+
+    use super::*;
+
+    fn metrics() -> GasAccountingMetrics {
+        let core = CoreMetrics::new("test", 9090, prometheus::Registry::new()).unwrap();
+        GasAccountingMetrics::new(&core).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_by_route_and_app_context() {
+        test_utils::run_test_db(|db| async move {
+            let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let app_sender = H256::from_low_u64_be(1);
+            let messages: Vec<_> = [(app_sender, 2), (app_sender, 2), (H256::zero(), 2)]
+                .into_iter()
+                .enumerate()
+                .map(|(nonce, (sender, destination))| HyperlaneMessage {
+                    nonce: nonce as u32,
+                    origin: origin.id(),
+                    sender,
+                    destination,
+                    ..Default::default()
+                })
+                .collect();
+            for message in &messages {
+                db.store_message(message, 0).unwrap();
+            }
+
+            // Two payments for the first message, none for the second
+            for (log_index, payment) in [(0u64, 100u64), (1, 50)] {
+                db.process_gas_payment(
+                    InterchainGasPayment {
+                        message_id: messages[0].id(),
+                        destination: 2,
+                        payment: payment.into(),
+                        gas_amount: 1000.into(),
+                    },
+                    &LogMeta {
+                        log_index: log_index.into(),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            }
+            for message in &messages[..2] {
+                db.process_gas_expenditure(InterchainGasExpenditure {
+                    message_id: message.id(),
+                    tokens_used: 70.into(),
+                    gas_used: 700.into(),
+                })
+                .unwrap();
+            }
+
+            let app_matching_list =
+                serde_json::from_str(&format!(r#"[{{"senderaddress": "{:?}"}}]"#, app_sender))
+                    .unwrap();
+            let mut reporter = GasAccountingReporter::new(
+                HashMap::from([(origin.id(), db.clone())]),
+                vec![(app_matching_list, "app".to_owned())],
+                HashMap::new(),
+                metrics(),
+            );
+            let report = reporter.build_report().await.unwrap();
+
+            assert_eq!(
+                report.entries,
+                vec![
+                    GasAccountingEntry {
+                        origin_domain: origin.id(),
+                        destination_domain: 2,
+                        app_context: None,
+                        message_count: 1,
+                        ..Default::default()
+                    },
+                    GasAccountingEntry {
+                        origin_domain: origin.id(),
+                        destination_domain: 2,
+                        app_context: Some("app".to_owned()),
+                        message_count: 2,
+                        paid_message_count: 1,
+                        spent_message_count: 2,
+                        total_payment: 150.into(),
+                        total_gas_amount: 2000.into(),
+                        total_tokens_used: 140.into(),
+                        total_gas_used: 1400.into(),
+                    },
+                ]
+            );
+
+            // The next report adds new messages and picks up the delivery of
+            // messages that were still open
+            db.process_gas_expenditure(InterchainGasExpenditure {
+                message_id: messages[2].id(),
+                tokens_used: 30.into(),
+                gas_used: 300.into(),
+            })
+            .unwrap();
+            let new_message = HyperlaneMessage {
+                nonce: 3,
+                origin: origin.id(),
+                destination: 2,
+                ..Default::default()
+            };
+            db.store_message(&new_message, 0).unwrap();
+            let report = reporter.build_report().await.unwrap();
+            assert_eq!(
+                report.entries[0],
+                GasAccountingEntry {
+                    origin_domain: origin.id(),
+                    destination_domain: 2,
+                    app_context: None,
+                    message_count: 2,
+                    spent_message_count: 1,
+                    total_tokens_used: 30.into(),
+                    total_gas_used: 300.into(),
+                    ..Default::default()
+                }
+            );
+            assert_eq!(report.entries[1].message_count, 2);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_counts_retries_until_processed() {
+        test_utils::run_test_db(|db| async move {
+            let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let message = HyperlaneMessage {
+                origin: origin.id(),
+                destination: 2,
+                ..Default::default()
+            };
+            db.store_message(&message, 0).unwrap();
+            let spend = |tokens_used: u64| {
+                db.process_gas_expenditure(InterchainGasExpenditure {
+                    message_id: message.id(),
+                    tokens_used: tokens_used.into(),
+                    gas_used: 100.into(),
+                })
+                .unwrap();
+            };
+
+            let mut reporter = GasAccountingReporter::new(
+                HashMap::from([(origin.id(), db.clone())]),
+                vec![],
+                HashMap::new(),
+                metrics(),
+            );
+            // The first attempt reverted, so the message stays unprocessed
+            spend(10);
+            let report = reporter.build_report().await.unwrap();
+            assert_eq!(report.entries[0].total_tokens_used, 10.into());
+
+            // The retry is counted once the message is processed
+            spend(20);
+            db.store_processed_by_nonce(&message.nonce, &true).unwrap();
+            let report = reporter.build_report().await.unwrap();
+            assert_eq!(report.entries[0].spent_message_count, 1);
+            assert_eq!(report.entries[0].total_tokens_used, 30.into());
+            assert_eq!(report.entries[0].total_gas_used, 200.into());
+            assert!(reporter.state.open_messages.is_empty());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_open_messages_expire() {
+        test_utils::run_test_db(|db| async move {
+            let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let message = HyperlaneMessage {
+                origin: origin.id(),
+                destination: 2,
+                ..Default::default()
+            };
+            db.store_message(&message, 0).unwrap();
+
+            let mut reporter = GasAccountingReporter::new(
+                HashMap::from([(origin.id(), db.clone())]),
+                vec![],
+                HashMap::new(),
+                metrics(),
+            );
+            reporter.build_report().await.unwrap();
+            let open = reporter.state.open_messages.get_mut(&message.id()).unwrap();
+            open.opened_at -= OPEN_MESSAGE_TTL.as_secs();
+
+            reporter.build_report().await.unwrap();
+            assert!(reporter.state.open_messages.is_empty());
+        })
+        .await;
+    }
+
+    #[test]
+    fn test_csv_quotes_app_contexts() {
+        let report = GasAccountingReport {
+            generated_at: 0,
+            entries: vec![GasAccountingEntry {
+                origin_domain: 1,
+                destination_domain: 2,
+                app_context: Some("warp \"USDC\", v2".to_owned()),
+                message_count: 3,
+                total_payment: 10.into(),
+                ..Default::default()
+            }],
+        };
+        let mut lines = report
+            .to_csv()
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines.pop().unwrap(),
+            r#"1,2,"warp ""USDC"", v2",3,0,0,10,0,0,0"#
+        );
+    }
+}
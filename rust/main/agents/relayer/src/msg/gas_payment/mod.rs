@@ -9,7 +9,7 @@ use hyperlane_core::{
 };
 use tracing::{debug, error, trace};
 
-use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone};
+use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone, GasPaymentPolicyUsdValue};
 use crate::{
     msg::gas_payment::policies::GasPaymentPolicyOnChainFeeQuoting,
     settings::{
@@ -17,7 +17,9 @@ use crate::{
     },
 };
 
+pub(crate) mod accounting;
 mod policies;
+pub(crate) mod price_oracle;
 
 pub const GAS_EXPENDITURE_LOG_MESSAGE: &str = "Recording gas expenditure for message";
 
@@ -75,6 +77,15 @@ impl GasPaymentEnforcer {
                         gas_fraction_numerator: n,
                         gas_fraction_denominator: d,
                     } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(n, d)),
+                    GasPaymentEnforcementPolicy::UsdValue {
+                        oracle,
+                        margin,
+                        native_tokens,
+                    } => Box::new(GasPaymentPolicyUsdValue::new(
+                        oracle.into(),
+                        margin,
+                        native_tokens,
+                    )),
                 };
                 (p, cfg.matching_list)
             })
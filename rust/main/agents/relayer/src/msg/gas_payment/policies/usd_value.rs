@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use eyre::{eyre, Result};
+
+use hyperlane_core::{
+    FixedPointNumber, HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment,
+    TxCostEstimate, U256,
+};
+
+use crate::{
+    msg::gas_payment::{price_oracle::TokenPriceOracle, GasPaymentPolicy},
+    settings::NativeTokenConf,
+};
+
+#[derive(Debug)]
+pub struct GasPaymentPolicyUsdValue {
+    oracle: Box<dyn TokenPriceOracle>,
+    /// The delivery cost is multiplied by this before being compared to the
+    /// payment, i.e. `1 + margin`.
+    cost_multiplier: FixedPointNumber,
+    /// Native token of each chain, by domain id
+    native_tokens: HashMap<u32, NativeTokenConf>,
+}
+
+impl GasPaymentPolicyUsdValue {
+    pub fn new(
+        oracle: Box<dyn TokenPriceOracle>,
+        margin: FixedPointNumber,
+        native_tokens: HashMap<u32, NativeTokenConf>,
+    ) -> Self {
+        Self {
+            oracle,
+            cost_multiplier: margin + 1,
+            native_tokens,
+        }
+    }
+
+    /// USD value of `amount` of the smallest unit of `domain`'s native token
+    async fn usd_value(&self, domain: u32, amount: U256) -> Result<FixedPointNumber> {
+        let token = self
+            .native_tokens
+            .get(&domain)
+            .ok_or_else(|| eyre!("No chain configured for domain {domain}"))?;
+        let price = self
+            .oracle
+            .usd_price(&token.chain_name)
+            .await?
+            .ok_or_else(|| eyre!("No USD price for the native token of {}", token.chain_name))?;
+        let one_token = FixedPointNumber::try_from(U256::exp10(token.decimals as usize))?;
+        Ok(FixedPointNumber::try_from(amount)? * price / one_token)
+    }
+}
+
+#[async_trait]
+impl GasPaymentPolicy for GasPaymentPolicyUsdValue {
+    /// UsdValue requires the USD value of the payment, in origin native tokens,
+    /// to cover the USD value of the tokens already spent on the message plus
+    /// the estimated delivery cost, in destination native tokens, with a margin
+    /// on top. Like the Minimum policy, UsdValue requires a payment to exist on
+    /// the IGP specified in the config.
+
+    async fn message_meets_gas_payment_requirement(
+        &self,
+        message: &HyperlaneMessage,
+        current_payment: &InterchainGasPayment,
+        current_expenditure: &InterchainGasExpenditure,
+        tx_cost_estimate: &TxCostEstimate,
+    ) -> Result<Option<U256>> {
+        let estimated_cost: U256 =
+            (FixedPointNumber::try_from(tx_cost_estimate.enforceable_gas_limit())?
+                * tx_cost_estimate.gas_price.clone())
+            .ceil_to_integer()
+            .try_into()?;
+        let total_cost = current_expenditure
+            .tokens_used
+            .saturating_add(estimated_cost);
+
+        let paid_usd = self
+            .usd_value(message.origin, current_payment.payment)
+            .await?;
+        let cost_usd = self.usd_value(message.destination, total_cost).await?;
+        if paid_usd >= cost_usd * self.cost_multiplier.clone() {
+            Ok(Some(tx_cost_estimate.gas_limit))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn requires_payment_found(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use hyperlane_core::H256;
+
+    use super::*;
+    use crate::msg::gas_payment::price_oracle::StaticTokenPriceOracle;
+
+    const ORIGIN: u32 = 1;
+    const DESTINATION: u32 = 2;
+
+    /// Origin tokens are worth 10x destination tokens, and have 6 decimals
+    /// instead of 18
+    fn policy(margin: &str) -> GasPaymentPolicyUsdValue {
+        let oracle = StaticTokenPriceOracle::new(HashMap::from([
+            ("origin".to_owned(), FixedPointNumber::from(20)),
+            ("destination".to_owned(), FixedPointNumber::from(2)),
+        ]));
+        GasPaymentPolicyUsdValue::new(
+            Box::new(oracle),
+            FixedPointNumber::from_str(margin).unwrap(),
+            HashMap::from([
+                (
+                    ORIGIN,
+                    NativeTokenConf {
+                        chain_name: "origin".to_owned(),
+                        decimals: 6,
+                    },
+                ),
+                (
+                    DESTINATION,
+                    NativeTokenConf {
+                        chain_name: "destination".to_owned(),
+                        decimals: 18,
+                    },
+                ),
+            ]),
+        )
+    }
+
+    fn message() -> HyperlaneMessage {
+        HyperlaneMessage {
+            origin: ORIGIN,
+            destination: DESTINATION,
+            ..Default::default()
+        }
+    }
+
+    fn payment(payment: u64) -> InterchainGasPayment {
+        InterchainGasPayment {
+            message_id: H256::zero(),
+            destination: DESTINATION,
+            payment: payment.into(),
+            gas_amount: U256::zero(),
+        }
+    }
+
+    fn expenditure(tokens_used: u64) -> InterchainGasExpenditure {
+        InterchainGasExpenditure {
+            message_id: H256::zero(),
+            gas_used: U256::zero(),
+            tokens_used: tokens_used.into(),
+        }
+    }
+
+    /// Costs 10^15 destination tokens, i.e. $0.002, i.e. 100 origin tokens
+    fn tx_cost_estimate() -> TxCostEstimate {
+        TxCostEstimate {
+            gas_limit: U256::from(100_000u32),
+            gas_price: U256::from(10_000_000_000u64).try_into().unwrap(),
+            l2_gas_limit: None,
+        }
+    }
+
+    async fn meets(
+        policy: &GasPaymentPolicyUsdValue,
+        payment: InterchainGasPayment,
+        expenditure: InterchainGasExpenditure,
+    ) -> Option<U256> {
+        policy
+            .message_meets_gas_payment_requirement(
+                &message(),
+                &payment,
+                &expenditure,
+                &tx_cost_estimate(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_compares_usd_values() {
+        let policy = policy("0");
+        assert_eq!(meets(&policy, payment(99), expenditure(0)).await, None);
+        assert_eq!(
+            meets(&policy, payment(100), expenditure(0)).await,
+            Some(U256::from(100_000u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_applies_margin_and_past_expenditure() {
+        let policy = policy("0.5");
+        assert_eq!(meets(&policy, payment(149), expenditure(0)).await, None);
+        assert_eq!(
+            meets(&policy, payment(150), expenditure(0)).await,
+            Some(U256::from(100_000u32))
+        );
+
+        // A previous attempt already spent as much as the new estimate
+        assert_eq!(
+            meets(&policy, payment(150), expenditure(1_000_000_000_000_000)).await,
+            None
+        );
+        assert_eq!(
+            meets(&policy, payment(300), expenditure(1_000_000_000_000_000)).await,
+            Some(U256::from(100_000u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_price_is_an_error() {
+        let policy = GasPaymentPolicyUsdValue::new(
+            Box::new(StaticTokenPriceOracle::new(HashMap::new())),
+            FixedPointNumber::zero(),
+            policy("0").native_tokens,
+        );
+        assert!(policy
+            .message_meets_gas_payment_requirement(
+                &message(),
+                &payment(100),
+                &expenditure(0),
+                &tx_cost_estimate(),
+            )
+            .await
+            .is_err());
+    }
+}
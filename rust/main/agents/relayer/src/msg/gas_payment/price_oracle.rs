@@ -0,0 +1,334 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use derive_new::new;
+use eyre::{bail, Context, Result};
+use hyperlane_core::FixedPointNumber;
+use reqwest::Url;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::settings::TokenPriceOracleConf;
+
+/// Timeout of a request to an HTTP token price oracle
+const HTTP_ORACLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before retrying a failed fetch, doubled after each consecutive failure
+/// up to the cache TTL
+const HTTP_ORACLE_MIN_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Source of USD prices for the native tokens of chains
+#[async_trait]
+pub trait TokenPriceOracle: Debug + Send + Sync {
+    /// Returns the USD price of one whole native token of `chain_name`, or
+    /// None if the oracle has no price for it.
+    async fn usd_price(&self, chain_name: &str) -> Result<Option<FixedPointNumber>>;
+}
+
+impl From<TokenPriceOracleConf> for Box<dyn TokenPriceOracle> {
+    fn from(conf: TokenPriceOracleConf) -> Self {
+        match conf {
+            TokenPriceOracleConf::Static { prices } => {
+                Box::new(StaticTokenPriceOracle::new(prices))
+            }
+            TokenPriceOracleConf::Http {
+                url,
+                cache_ttl,
+                max_staleness,
+            } => Box::new(HttpTokenPriceOracle::new(url, cache_ttl, max_staleness)),
+        }
+    }
+}
+
+/// Prices set in the config
+#[derive(Debug, new)]
+pub struct StaticTokenPriceOracle {
+    prices: HashMap<String, FixedPointNumber>,
+}
+
+#[async_trait]
+impl TokenPriceOracle for StaticTokenPriceOracle {
+    async fn usd_price(&self, chain_name: &str) -> Result<Option<FixedPointNumber>> {
+        Ok(self.prices.get(chain_name).cloned())
+    }
+}
+
+/// Prices fetched from an endpoint that returns a JSON object of chain names to
+/// USD prices. Responses are reused for `cache_ttl`. When a fetch fails, the
+/// last fetched prices keep being used until they are `max_staleness` old, and
+/// fetches are retried with backoff.
+#[derive(Debug)]
+pub struct HttpTokenPriceOracle {
+    url: Url,
+    cache_ttl: Duration,
+    max_staleness: Duration,
+    client: reqwest::Client,
+    cache: Mutex<PriceCache>,
+    /// Held while fetching, so concurrent lookups share a single request
+    fetch_lock: tokio::sync::Mutex<()>,
+}
+
+#[derive(Debug, Default)]
+struct PriceCache {
+    prices: Option<HashMap<String, FixedPointNumber>>,
+    fetched_at: Option<Instant>,
+    consecutive_failures: u32,
+    retry_after: Option<Instant>,
+}
+
+impl PriceCache {
+    fn is_fresh(&self, cache_ttl: Duration) -> bool {
+        self.fetched_at
+            .is_some_and(|fetched_at| fetched_at.elapsed() < cache_ttl)
+    }
+
+    /// The cached price of `chain_name`, if prices were fetched less than
+    /// `max_staleness` ago
+    fn recent_price(
+        &self,
+        chain_name: &str,
+        max_staleness: Duration,
+    ) -> Option<Option<FixedPointNumber>> {
+        if self.is_fresh(max_staleness) {
+            self.price(chain_name)
+        } else {
+            None
+        }
+    }
+
+    fn is_backing_off(&self) -> bool {
+        self.retry_after
+            .is_some_and(|retry_after| Instant::now() < retry_after)
+    }
+
+    /// The cached price of `chain_name`, if prices were ever fetched
+    fn price(&self, chain_name: &str) -> Option<Option<FixedPointNumber>> {
+        self.prices
+            .as_ref()
+            .map(|prices| prices.get(chain_name).cloned())
+    }
+}
+
+impl HttpTokenPriceOracle {
+    pub fn new(url: Url, cache_ttl: Duration, max_staleness: Duration) -> Self {
+        Self {
+            url,
+            cache_ttl,
+            max_staleness,
+            // `Client::new` panics in the same cases
+            client: reqwest::Client::builder()
+                .timeout(HTTP_ORACLE_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client"),
+            cache: Default::default(),
+            fetch_lock: Default::default(),
+        }
+    }
+
+    async fn fetch(&self) -> Result<HashMap<String, FixedPointNumber>> {
+        let body: Value = self
+            .client
+            .get(self.url.clone())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_usd_prices(&body)
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, PriceCache> {
+        self.cache.lock().expect("price cache lock poisoned")
+    }
+
+    fn backoff(&self, consecutive_failures: u32) -> Duration {
+        HTTP_ORACLE_MIN_BACKOFF
+            .saturating_mul(2u32.saturating_pow(consecutive_failures.saturating_sub(1)))
+            .min(self.cache_ttl.max(HTTP_ORACLE_MIN_BACKOFF))
+    }
+}
+
+#[async_trait]
+impl TokenPriceOracle for HttpTokenPriceOracle {
+    async fn usd_price(&self, chain_name: &str) -> Result<Option<FixedPointNumber>> {
+        {
+            let cache = self.lock_cache();
+            if cache.is_fresh(self.cache_ttl) || cache.is_backing_off() {
+                if let Some(price) = cache.recent_price(chain_name, self.max_staleness) {
+                    return Ok(price);
+                }
+                if cache.is_backing_off() {
+                    bail!("Token prices from {} are unavailable", self.url);
+                }
+            }
+        }
+
+        let _fetching = self.fetch_lock.lock().await;
+        // Another lookup may have fetched the prices while this one waited
+        {
+            let cache = self.lock_cache();
+            if cache.is_fresh(self.cache_ttl) {
+                if let Some(price) = cache.price(chain_name) {
+                    return Ok(price);
+                }
+            }
+        }
+
+        let fetched = self.fetch().await;
+        let mut cache = self.lock_cache();
+        match fetched {
+            Ok(prices) => {
+                let price = prices.get(chain_name).cloned();
+                *cache = PriceCache {
+                    prices: Some(prices),
+                    fetched_at: Some(Instant::now()),
+                    ..Default::default()
+                };
+                Ok(price)
+            }
+            Err(err) => {
+                cache.consecutive_failures += 1;
+                let backoff = self.backoff(cache.consecutive_failures);
+                cache.retry_after = Some(Instant::now() + backoff);
+                let err = err.wrap_err(format!("Failed to fetch token prices from {}", self.url));
+                match cache.recent_price(chain_name, self.max_staleness) {
+                    Some(price) => {
+                        warn!(?err, ?backoff, "Using the last fetched token prices");
+                        Ok(price)
+                    }
+                    None => Err(err),
+                }
+            }
+        }
+    }
+}
+
+/// Parses a JSON object of chain names to USD prices
+pub fn parse_usd_prices(value: &Value) -> Result<HashMap<String, FixedPointNumber>> {
+    let Some(prices) = value.as_object() else {
+        bail!("Expected an object of chain names to USD prices, got `{value}`");
+    };
+    prices
+        .iter()
+        .map(|(chain_name, price)| {
+            let price = parse_decimal(price)
+                .with_context(|| format!("Invalid USD price for {chain_name}"))?;
+            Ok((chain_name.clone(), price))
+        })
+        .collect()
+}
+
+/// Parses a non-negative decimal given as a JSON number or string
+pub fn parse_decimal(value: &Value) -> Result<FixedPointNumber> {
+    let decimal = match value {
+        Value::Number(n) => FixedPointNumber::from_str(&n.to_string())?,
+        Value::String(s) => FixedPointNumber::from_str(s)?,
+        _ => bail!("Expected a decimal number, got `{value}`"),
+    };
+    if decimal < FixedPointNumber::zero() {
+        bail!("Expected a non-negative number, got `{value}`");
+    }
+    Ok(decimal)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use axum::{routing, Json, Router};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_usd_prices() {
+        let prices =
+            parse_usd_prices(&json!({ "ethereum": 3000.5, "solanamainnet": "150" })).unwrap();
+        assert_eq!(
+            prices["ethereum"],
+            FixedPointNumber::from_str("3000.5").unwrap()
+        );
+        assert_eq!(
+            prices["solanamainnet"],
+            FixedPointNumber::from_str("150").unwrap()
+        );
+
+        assert!(parse_usd_prices(&json!(["ethereum"])).is_err());
+        assert!(parse_usd_prices(&json!({ "ethereum": "-1" })).is_err());
+        assert!(parse_usd_prices(&json!({ "ethereum": true })).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_oracle_caches_prices() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let app = Router::new().route(
+            "/prices",
+            routing::get(move || async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Json(json!({ "ethereum": "3000" }))
+            }),
+        );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let url = Url::parse(&format!("http://{}/prices", addr)).unwrap();
+        let oracle =
+            HttpTokenPriceOracle::new(url, Duration::from_secs(60), Duration::from_secs(60));
+
+        assert_eq!(
+            oracle.usd_price("ethereum").await.unwrap(),
+            Some(FixedPointNumber::from(3000))
+        );
+        assert_eq!(oracle.usd_price("arbitrum").await.unwrap(), None);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_http_oracle_falls_back_to_last_prices() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        // Only the first request succeeds
+        let app = Router::new().route(
+            "/prices",
+            routing::get(move || async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Ok(Json(json!({ "ethereum": "3000" })))
+                } else {
+                    Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+                }
+            }),
+        );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let url = Url::parse(&format!("http://{}/prices", addr)).unwrap();
+        let oracle = HttpTokenPriceOracle::new(url, Duration::ZERO, Duration::from_millis(500));
+
+        for _ in 0..3 {
+            assert_eq!(
+                oracle.usd_price("ethereum").await.unwrap(),
+                Some(FixedPointNumber::from(3000))
+            );
+        }
+        // The failed fetch is not retried until the backoff elapses
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        // Prices older than the max staleness are not used anymore
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(oracle.usd_price("ethereum").await.is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use hyperlane_base::db::HyperlaneDb;
+use hyperlane_core::{OperationLifecycleEntry, OperationLifecycleEvent, H256};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
+};
+use tracing::{info_span, instrument::Instrumented, warn, Instrument};
+
+/// Number of lifecycle events kept per message, the oldest being dropped first
+pub const MAX_LIFECYCLE_EVENTS: usize = 100;
+
+type LifecycleRecord = (H256, OperationLifecycleEntry);
+
+/// Appends lifecycle events to the log of their message in the origin database.
+///
+/// Recording only queues the event: a background task writes the queued events
+/// in the order they were recorded, so operations never wait on the database.
+pub struct LifecycleRecorder {
+    origin_db: Arc<dyn HyperlaneDb>,
+    sender: UnboundedSender<LifecycleRecord>,
+    receiver: Mutex<Option<UnboundedReceiver<LifecycleRecord>>>,
+}
+
+impl LifecycleRecorder {
+    pub fn new(origin_db: Arc<dyn HyperlaneDb>) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            origin_db,
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+        }
+    }
+
+    /// Queues `event` to be appended to the log of `message_id`
+    pub fn record(&self, message_id: H256, event: OperationLifecycleEvent) {
+        let entry = OperationLifecycleEntry::new(event);
+        if let Err(err) = self.sender.send((message_id, entry)) {
+            warn!(
+                ?message_id,
+                ?err,
+                "Failed to queue lifecycle event for message"
+            );
+        }
+    }
+
+    /// Spawns the task writing the recorded events. Only the first call spawns it.
+    pub fn spawn(&self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("LifecycleRecorder");
+        let receiver = self
+            .receiver
+            .lock()
+            .expect("lifecycle receiver lock poisoned")
+            .take();
+        let Some(receiver) = receiver else {
+            return tokio::spawn(async {}).instrument(span);
+        };
+        let origin_db = self.origin_db.clone();
+        tokio::spawn(Self::persist(origin_db, receiver)).instrument(span)
+    }
+
+    async fn persist(
+        origin_db: Arc<dyn HyperlaneDb>,
+        mut receiver: UnboundedReceiver<LifecycleRecord>,
+    ) {
+        while let Some(record) = receiver.recv().await {
+            let mut records = vec![record];
+            while let Ok(record) = receiver.try_recv() {
+                records.push(record);
+            }
+            let origin_db = origin_db.clone();
+            if let Err(err) =
+                tokio::task::spawn_blocking(move || append_records(&*origin_db, records)).await
+            {
+                warn!(?err, "Persisting lifecycle events failed");
+            }
+        }
+    }
+}
+
+/// Appends `records` to their message logs, reading and writing each log once
+fn append_records(origin_db: &dyn HyperlaneDb, records: Vec<LifecycleRecord>) {
+    let mut by_message: HashMap<H256, Vec<OperationLifecycleEntry>> = HashMap::new();
+    for (message_id, entry) in records {
+        by_message.entry(message_id).or_default().push(entry);
+    }
+    for (message_id, entries) in by_message {
+        let mut log = match origin_db.retrieve_operation_lifecycle_by_message_id(&message_id) {
+            Ok(log) => log.unwrap_or_default(),
+            Err(e) => {
+                warn!(?message_id, err = %e, "Failed to retrieve lifecycle log for message");
+                continue;
+            }
+        };
+        for entry in entries {
+            log.push_bounded(entry, MAX_LIFECYCLE_EVENTS);
+        }
+        if let Err(e) = origin_db.store_operation_lifecycle_by_message_id(&message_id, &log) {
+            warn!(?message_id, err = %e, "Persisting lifecycle log failed for message");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::{test_utils, HyperlaneRocksDB};
+    use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_events_are_persisted_in_order() {
+        test_utils::run_test_db(|db| async move {
+            let domain: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+            let db = Arc::new(HyperlaneRocksDB::new(&domain, db));
+            let recorder = LifecycleRecorder::new(db.clone());
+            let message_id = H256::random();
+
+            for error in ["first", "second"] {
+                recorder.record(
+                    message_id,
+                    OperationLifecycleEvent::MetadataBuildAttempt {
+                        error: Some(error.to_owned()),
+                    },
+                );
+            }
+            // Dropping the recorder closes the channel, so the task ends once everything is written
+            let task = recorder.spawn();
+            drop(recorder);
+            task.await.unwrap();
+
+            let log = db
+                .retrieve_operation_lifecycle_by_message_id(&message_id)
+                .unwrap()
+                .unwrap();
+            let errors: Vec<_> = log
+                .0
+                .into_iter()
+                .map(|entry| match entry.event {
+                    OperationLifecycleEvent::MetadataBuildAttempt { error } => error.unwrap(),
+                    event => panic!("Unexpected event {event:?}"),
+                })
+                .collect();
+            assert_eq!(errors, vec!["first", "second"]);
+        })
+        .await;
+    }
+}
@@ -410,15 +410,19 @@ impl BaseMetadataBuilder {
                     continue;
                 };
 
-                // If this is a LocalStorage based checkpoint syncer and it's not
-                // allowed, ignore it
+                // If this checkpoint syncer reads from the local machine or network
+                // and it's not allowed, ignore it
+                if !self.allow_local_checkpoint_syncers && config.is_local() {
+                    debug!(?config, "Ignoring disallowed local checkpoint syncer");
+                    continue;
+                }
+
+                // Announced HTTP locations must be served over TLS, unless local
+                // syncers are allowed, e.g. for e2e tests against http://localhost
                 if !self.allow_local_checkpoint_syncers
-                    && matches!(config, CheckpointSyncerConf::LocalStorage { .. })
+                    && matches!(&config, CheckpointSyncerConf::Http { url } if url.scheme() != "https")
                 {
-                    debug!(
-                        ?config,
-                        "Ignoring disallowed LocalStorage based checkpoint syncer"
-                    );
+                    debug!(?config, "Ignoring checkpoint syncer not served over https");
                     continue;
                 }
 
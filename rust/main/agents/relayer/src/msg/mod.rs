@@ -27,12 +27,16 @@
 //!   - FallbackProviderSubmitter (Serialized, but if some RPC provider sucks,
 //!   switch everyone to new one)
 
+pub(crate) mod admin_overrides;
+pub(crate) mod app_context_scheduler;
 pub(crate) mod blacklist;
 pub(crate) mod gas_payment;
+pub(crate) mod lifecycle_recorder;
 pub(crate) mod metadata;
 pub(crate) mod op_queue;
 pub(crate) mod op_submitter;
 pub(crate) mod processor;
+pub(crate) mod submission_limiter;
 
 pub mod pending_message;
 
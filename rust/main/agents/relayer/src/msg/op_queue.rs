@@ -1,4 +1,4 @@
-use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc};
+use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc, time::Instant};
 
 use derive_new::new;
 use hyperlane_core::{PendingOperation, PendingOperationStatus, QueueOperation};
@@ -6,7 +6,15 @@ use prometheus::{IntGauge, IntGaugeVec};
 use tokio::sync::{broadcast::Receiver, Mutex};
 use tracing::{debug, instrument};
 
-use crate::server::{MessageRetryQueueResponse, MessageRetryRequest};
+use crate::{
+    server::{MessageRetryQueueResponse, MessageRetryRequest},
+    settings::matching_list::MatchingList,
+};
+
+use super::{
+    admin_overrides::{AdminOverrides, OverrideAction},
+    app_context_scheduler::AppContextScheduler,
+};
 
 pub type OperationPriorityQueue = Arc<Mutex<BinaryHeap<Reverse<QueueOperation>>>>;
 
@@ -19,20 +27,56 @@ pub struct OpQueue {
     retry_receiver: Arc<Mutex<Receiver<MessageRetryRequest>>>,
     #[new(default)]
     pub queue: OperationPriorityQueue,
+    /// Operations paused through the admin API. These are never popped until resumed.
+    #[new(default)]
+    pub paused: OperationPriorityQueue,
+    /// Shares pops fairly between app contexts. Pops follow queue order if unset.
+    #[new(default)]
+    scheduler: Option<Arc<AppContextScheduler>>,
+    /// Operations matching a persisted pause are pushed into the paused queue
+    #[new(default)]
+    admin_overrides: Option<Arc<AdminOverrides>>,
 }
 
 impl OpQueue {
+    pub fn with_scheduler(mut self, scheduler: AppContextScheduler) -> Self {
+        self.scheduler = Some(Arc::new(scheduler));
+        self
+    }
+
+    pub fn with_admin_overrides(mut self, admin_overrides: Arc<AdminOverrides>) -> Self {
+        self.admin_overrides = Some(admin_overrides);
+        self
+    }
+
     /// Push an element onto the queue and update metrics
     /// Arguments:
     /// - `op`: the operation to push onto the queue
     /// - `new_status`: optional new status to set for the operation. When an operation is added to a queue,
     /// it's very likely that its status has just changed, so this forces the caller to consider the new status
+    ///
+    /// Operations matching a persisted pause go into the paused queue instead, so
+    /// a pause holds operations at whichever stage they reach next.
     #[instrument(skip(self), ret, fields(queue_label=%self.queue_metrics_label), level = "trace")]
     pub async fn push(&self, mut op: QueueOperation, new_status: Option<PendingOperationStatus>) {
         let new_metric = Arc::new(self.get_new_operation_metric(op.as_ref(), new_status.clone()));
         op.set_status_and_update_metrics(new_status, new_metric);
 
-        self.queue.lock().await.push(Reverse(op));
+        let paused_by = self
+            .admin_overrides
+            .as_ref()
+            .and_then(|overrides| overrides.find_for_op(OverrideAction::Pause, &op));
+        if let Some(paused_by) = paused_by {
+            debug!(
+                ?op,
+                override_id = paused_by.id,
+                queue_label = %self.queue_metrics_label,
+                "Operation paused by admin override"
+            );
+            self.paused.lock().await.push(Reverse(op));
+        } else {
+            self.queue.lock().await.push(Reverse(op));
+        }
     }
 
     /// Pop an element from the queue and update metrics
@@ -46,14 +90,25 @@ impl OpQueue {
     #[instrument(skip(self), fields(queue_label=%self.queue_metrics_label), level = "debug")]
     pub async fn pop_many(&mut self, limit: usize) -> Vec<QueueOperation> {
         self.process_retry_requests().await;
-        let mut queue = self.queue.lock().await;
-        let mut popped = vec![];
-        while let Some(Reverse(op)) = queue.pop() {
-            popped.push(op);
-            if popped.len() >= limit {
-                break;
+        let popped = if let Some(scheduler) = &self.scheduler {
+            let in_flight = scheduler.in_flight().await;
+            scheduler.pop(
+                &mut *self.queue.lock().await,
+                limit,
+                &in_flight,
+                Instant::now(),
+            )
+        } else {
+            let mut queue = self.queue.lock().await;
+            let mut popped = vec![];
+            while let Some(Reverse(op)) = queue.pop() {
+                popped.push(op);
+                if popped.len() >= limit {
+                    break;
+                }
             }
-        }
+            popped
+        };
         // This function is called very often by the op_submitter tasks, so only log when there are operations to pop
         // to avoid spamming the logs
         if !popped.is_empty() {
@@ -105,6 +160,83 @@ impl OpQueue {
         }
     }
 
+    /// Number of operations held by this queue, including paused ones
+    pub async fn total_len(&self) -> usize {
+        self.queue.lock().await.len() + self.paused.lock().await.len()
+    }
+
+    /// Move the operations matching `pattern` into the paused queue.
+    /// Returns the number of paused operations.
+    pub async fn pause_matching(&self, pattern: &MatchingList) -> u64 {
+        let mut queue = self.queue.lock().await;
+        let mut paused = self.paused.lock().await;
+        Self::move_matching(&mut queue, &mut paused, pattern)
+    }
+
+    /// Move the paused operations matching `pattern` back into the queue.
+    /// Returns the number of resumed operations.
+    pub async fn resume_matching(&self, pattern: &MatchingList) -> u64 {
+        let mut queue = self.queue.lock().await;
+        let mut paused = self.paused.lock().await;
+        Self::move_matching(&mut paused, &mut queue, pattern)
+    }
+
+    /// Reset the attempts of the queued operations matching `pattern`, so they are
+    /// processed without waiting out their backoff. Returns the number of matching operations.
+    pub async fn retry_matching(&self, pattern: &MatchingList) -> u64 {
+        let mut queue = self.queue.lock().await;
+        let mut matching = Self::drain_matching(&mut queue, pattern);
+        let matched = matching.len() as u64;
+        for op in matching.iter_mut() {
+            op.reset_attempts();
+        }
+        queue.extend(matching.into_iter().map(Reverse));
+        matched
+    }
+
+    /// Remove the operations matching `pattern` from the queue, and from the paused
+    /// queue if `include_paused` is set.
+    pub async fn remove_matching(
+        &self,
+        pattern: &MatchingList,
+        include_paused: bool,
+    ) -> Vec<QueueOperation> {
+        let mut removed = Self::drain_matching(&mut *self.queue.lock().await, pattern);
+        if include_paused {
+            removed.extend(Self::drain_matching(
+                &mut *self.paused.lock().await,
+                pattern,
+            ));
+        }
+        removed
+    }
+
+    pub fn queue_metrics_label(&self) -> &str {
+        &self.queue_metrics_label
+    }
+
+    fn move_matching(
+        from: &mut BinaryHeap<Reverse<QueueOperation>>,
+        to: &mut BinaryHeap<Reverse<QueueOperation>>,
+        pattern: &MatchingList,
+    ) -> u64 {
+        let matching = Self::drain_matching(from, pattern);
+        let matched = matching.len() as u64;
+        to.extend(matching.into_iter().map(Reverse));
+        matched
+    }
+
+    fn drain_matching(
+        queue: &mut BinaryHeap<Reverse<QueueOperation>>,
+        pattern: &MatchingList,
+    ) -> Vec<QueueOperation> {
+        let (matching, rest): (Vec<_>, Vec<_>) = queue
+            .drain()
+            .partition(|Reverse(op)| pattern.op_matches(op));
+        queue.extend(rest);
+        matching.into_iter().map(|Reverse(op)| op).collect()
+    }
+
     /// Get the metric associated with this operation
     fn get_new_operation_metric(
         &self,
@@ -159,205 +291,23 @@ impl OpQueue {
 pub mod test {
     use crate::{
         server::ENDPOINT_MESSAGES_QUEUE_SIZE,
-        settings::matching_list::{Filter, ListElement, MatchingList},
+        settings::{
+            matching_list::{Filter, ListElement, MatchingList},
+            AppContextSchedulingConf,
+        },
     };
 
     use super::*;
     use hyperlane_core::{
-        HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneDomainTechnicalStack,
-        HyperlaneDomainType, HyperlaneMessage, KnownHyperlaneDomain, PendingOperationResult,
-        TryBatchAs, TxOutcome, H256, U256,
+        test_utils::MockPendingOperation, HyperlaneDomain, KnownHyperlaneDomain, H256,
     };
-    use serde::Serialize;
+    use proptest::prelude::*;
     use std::{
-        collections::VecDeque,
-        str::FromStr,
-        time::{Duration, Instant},
+        collections::{HashMap, VecDeque},
+        time::Duration,
     };
     use tokio::sync::{self, mpsc};
 
-    #[derive(Debug, Clone, Serialize)]
-    pub struct MockPendingOperation {
-        id: H256,
-        sender_address: H256,
-        origin_domain_id: u32,
-        destination_domain_id: u32,
-        recipient_address: H256,
-        seconds_to_next_attempt: u64,
-        destination_domain: HyperlaneDomain,
-    }
-
-    impl MockPendingOperation {
-        pub fn new(seconds_to_next_attempt: u64, destination_domain: HyperlaneDomain) -> Self {
-            Self {
-                id: H256::random(),
-                seconds_to_next_attempt,
-                destination_domain_id: destination_domain.id(),
-                destination_domain,
-                sender_address: H256::random(),
-                recipient_address: H256::random(),
-                origin_domain_id: 0,
-            }
-        }
-
-        pub fn with_message_data(message: HyperlaneMessage) -> Self {
-            Self {
-                id: message.id(),
-                sender_address: message.sender,
-                recipient_address: message.recipient,
-                origin_domain_id: message.origin,
-                destination_domain_id: message.destination,
-                seconds_to_next_attempt: 0,
-                destination_domain: HyperlaneDomain::Unknown {
-                    domain_id: message.destination,
-                    domain_name: "test".to_string(),
-                    domain_type: HyperlaneDomainType::Unknown,
-                    domain_protocol: HyperlaneDomainProtocol::Ethereum,
-                    domain_technical_stack: HyperlaneDomainTechnicalStack::Other,
-                },
-            }
-        }
-
-        pub fn with_id(self, id: &str) -> Self {
-            Self {
-                id: H256::from_str(id).unwrap(),
-                ..self
-            }
-        }
-
-        pub fn with_sender_address(self, sender_address: &str) -> Self {
-            Self {
-                sender_address: H256::from_str(sender_address).unwrap(),
-                ..self
-            }
-        }
-
-        pub fn with_recipient_address(self, recipient_address: &str) -> Self {
-            Self {
-                recipient_address: H256::from_str(recipient_address).unwrap(),
-                ..self
-            }
-        }
-
-        pub fn with_origin_domain(self, domain: HyperlaneDomain) -> Self {
-            let domain_id = match domain {
-                HyperlaneDomain::Known(d) => d as u32,
-                HyperlaneDomain::Unknown { domain_id, .. } => domain_id,
-            };
-            Self {
-                origin_domain_id: domain_id,
-                ..self
-            }
-        }
-    }
-
-    impl TryBatchAs<HyperlaneMessage> for MockPendingOperation {}
-
-    #[async_trait::async_trait]
-    #[typetag::serialize]
-    impl PendingOperation for MockPendingOperation {
-        fn id(&self) -> H256 {
-            self.id
-        }
-
-        fn status(&self) -> PendingOperationStatus {
-            PendingOperationStatus::FirstPrepareAttempt
-        }
-
-        fn set_status(&mut self, _status: PendingOperationStatus) {}
-
-        fn reset_attempts(&mut self) {
-            self.seconds_to_next_attempt = 0;
-        }
-
-        fn sender_address(&self) -> &H256 {
-            &self.sender_address
-        }
-
-        fn recipient_address(&self) -> &H256 {
-            &self.recipient_address
-        }
-
-        fn get_metric(&self) -> Option<Arc<IntGauge>> {
-            None
-        }
-
-        fn set_metric(&mut self, _metric: Arc<IntGauge>) {}
-
-        fn priority(&self) -> u32 {
-            todo!()
-        }
-
-        fn retrieve_status_from_db(&self) -> Option<PendingOperationStatus> {
-            todo!()
-        }
-
-        fn get_operation_labels(&self) -> (String, String) {
-            Default::default()
-        }
-
-        fn origin_domain_id(&self) -> u32 {
-            self.origin_domain_id
-        }
-
-        fn destination_domain(&self) -> &HyperlaneDomain {
-            &self.destination_domain
-        }
-
-        fn app_context(&self) -> Option<String> {
-            todo!()
-        }
-
-        async fn prepare(&mut self) -> PendingOperationResult {
-            todo!()
-        }
-
-        /// Submit this operation to the blockchain and report if it was successful
-        /// or not.
-        async fn submit(&mut self) -> PendingOperationResult {
-            todo!()
-        }
-
-        fn set_submission_outcome(&mut self, _outcome: TxOutcome) {
-            todo!()
-        }
-
-        fn get_tx_cost_estimate(&self) -> Option<U256> {
-            todo!()
-        }
-
-        /// This will be called after the operation has been submitted and is
-        /// responsible for checking if the operation has reached a point at
-        /// which we consider it safe from reorgs.
-        async fn confirm(&mut self) -> PendingOperationResult {
-            todo!()
-        }
-
-        fn set_operation_outcome(
-            &mut self,
-            _submission_outcome: TxOutcome,
-            _submission_estimated_cost: U256,
-        ) {
-            todo!()
-        }
-
-        fn next_attempt_after(&self) -> Option<Instant> {
-            Some(
-                Instant::now()
-                    .checked_add(Duration::from_secs(self.seconds_to_next_attempt))
-                    .unwrap(),
-            )
-        }
-
-        fn set_next_attempt_after(&mut self, _delay: Duration) {
-            todo!()
-        }
-
-        fn set_retries(&mut self, _retries: u32) {
-            todo!()
-        }
-    }
-
     pub fn dummy_metrics_and_label() -> (IntGaugeVec, String) {
         (
             IntGaugeVec::new(
@@ -831,4 +781,411 @@ pub mod test {
         assert_eq!(retry_response.evaluated, 7);
         assert_eq!(retry_response.matched, 2);
     }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_matching() {
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let mut op_queue = initialize_queue(&broadcaster);
+
+        let destination_domain_1: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let destination_domain_2: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+        let ops = vec![
+            Box::new(MockPendingOperation::new(1, destination_domain_1.clone())) as QueueOperation,
+            Box::new(MockPendingOperation::new(2, destination_domain_2.clone())) as QueueOperation,
+            Box::new(MockPendingOperation::new(3, destination_domain_2.clone())) as QueueOperation,
+        ];
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops {
+            op_queue
+                .push(op, Some(PendingOperationStatus::FirstPrepareAttempt))
+                .await;
+        }
+
+        let pattern = MatchingList::with_destination_domain(destination_domain_2.id());
+        assert_eq!(op_queue.pause_matching(&pattern).await, 2);
+        assert_eq!(op_queue.total_len().await, 3);
+
+        // Paused operations are never popped
+        assert_eq!(op_queue.pop().await.unwrap().id(), op_ids[0]);
+        assert!(op_queue.pop().await.is_none());
+
+        assert_eq!(op_queue.resume_matching(&pattern).await, 2);
+        let mut popped = vec![];
+        while let Some(op) = op_queue.pop().await {
+            popped.push(op.id());
+        }
+        assert_eq!(popped, vec![op_ids[1], op_ids[2]]);
+    }
+
+    #[tokio::test]
+    async fn test_remove_matching() {
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let op_queue = initialize_queue(&broadcaster);
+
+        let destination_domain: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let ops = generate_test_messages(destination_domain, 3);
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops {
+            op_queue
+                .push(op, Some(PendingOperationStatus::FirstPrepareAttempt))
+                .await;
+        }
+        op_queue
+            .pause_matching(&MatchingList::with_message_id(op_ids[0]))
+            .await;
+
+        // Paused operations are only removed when asked to
+        let pattern = MatchingList(Some(vec![ListElement::new(
+            Filter::Enumerated(vec![op_ids[0], op_ids[1]]),
+            Filter::Wildcard,
+            Filter::Wildcard,
+            Filter::Wildcard,
+            Filter::Wildcard,
+        )]));
+        let removed = op_queue.remove_matching(&pattern, false).await;
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id(), op_ids[1]);
+        assert_eq!(op_queue.total_len().await, 2);
+
+        let removed = op_queue.remove_matching(&pattern, true).await;
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id(), op_ids[0]);
+        assert_eq!(op_queue.total_len().await, 1);
+    }
+
+    fn push_with_app_context(
+        app_context: &str,
+        seconds_to_next_attempt: u64,
+    ) -> (H256, QueueOperation) {
+        let op = MockPendingOperation::new(
+            seconds_to_next_attempt,
+            KnownHyperlaneDomain::Injective.into(),
+        )
+        .with_app_context(app_context);
+        (op.id(), Box::new(op) as QueueOperation)
+    }
+
+    /// Pops through the queue's scheduler as if it was `now`
+    async fn scheduled_pop(op_queue: &OpQueue, limit: usize, now: Instant) -> Vec<QueueOperation> {
+        let scheduler = op_queue.scheduler.as_ref().unwrap();
+        let in_flight = scheduler.in_flight().await;
+        scheduler.pop(&mut *op_queue.queue.lock().await, limit, &in_flight, now)
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_shares_pops_by_weight() {
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let confs = HashMap::from([(
+            "quiet".to_owned(),
+            AppContextSchedulingConf {
+                weight: 2,
+                max_in_flight: None,
+            },
+        )]);
+        let op_queue =
+            initialize_queue(&broadcaster).with_scheduler(AppContextScheduler::new(confs, vec![]));
+
+        let mut noisy_ids = vec![];
+        for seconds_to_next_attempt in 1..=6 {
+            let (id, op) = push_with_app_context("noisy", seconds_to_next_attempt);
+            noisy_ids.push(id);
+            op_queue.push(op, None).await;
+        }
+        let mut quiet_ids = vec![];
+        for seconds_to_next_attempt in [2, 3, 4, 20] {
+            let (id, op) = push_with_app_context("quiet", seconds_to_next_attempt);
+            quiet_ids.push(id);
+            op_queue.push(op, None).await;
+        }
+
+        // Everything except the last `quiet` operation is due
+        let now = Instant::now() + Duration::from_secs(10);
+        let popped: Vec<_> = scheduled_pop(&op_queue, 6, now)
+            .await
+            .into_iter()
+            .map(|op| op.id())
+            .collect();
+        // `quiet` has twice the weight, so gets two pops for each of `noisy`'s,
+        // each app context's operations stay in queue order, and `quiet`'s
+        // operation that is not due yet is left for `noisy`
+        assert_eq!(
+            popped,
+            vec![
+                noisy_ids[0],
+                quiet_ids[0],
+                quiet_ids[1],
+                noisy_ids[1],
+                quiet_ids[2],
+                noisy_ids[2],
+            ]
+        );
+        assert_eq!(op_queue.total_len().await, 4);
+
+        // Operations that are not due are never popped
+        let popped = scheduled_pop(&op_queue, 6, now).await;
+        assert_eq!(
+            popped.iter().map(|op| op.id()).collect::<Vec<_>>(),
+            noisy_ids[3..].to_vec()
+        );
+        assert_eq!(scheduled_pop(&op_queue, 6, now).await.len(), 0);
+        assert_eq!(op_queue.total_len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_respects_max_in_flight() {
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let in_flight_queue = initialize_queue(&broadcaster);
+        let confs = HashMap::from([(
+            "capped".to_owned(),
+            AppContextSchedulingConf {
+                weight: 1,
+                max_in_flight: Some(2),
+            },
+        )]);
+        let op_queue = initialize_queue(&broadcaster).with_scheduler(AppContextScheduler::new(
+            confs,
+            vec![in_flight_queue.queue.clone()],
+        ));
+
+        let (_, op) = push_with_app_context("capped", 1);
+        in_flight_queue.push(op, None).await;
+        for seconds_to_next_attempt in 1..=3 {
+            let (_, op) = push_with_app_context("capped", seconds_to_next_attempt);
+            op_queue.push(op, None).await;
+        }
+        let (other_id, op) = push_with_app_context("other", 5);
+        op_queue.push(op, None).await;
+
+        // Only one more `capped` operation fits next to the one in flight
+        let popped = scheduled_pop(&op_queue, 10, Instant::now() + Duration::from_secs(10)).await;
+        assert_eq!(popped.len(), 2);
+        assert_eq!(
+            popped
+                .iter()
+                .filter(|op| op.app_context().as_deref() == Some("capped"))
+                .count(),
+            1
+        );
+        assert!(popped.iter().any(|op| op.id() == other_id));
+        assert_eq!(op_queue.total_len().await, 2);
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(4)
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    const DESTINATIONS: [KnownHyperlaneDomain; 3] = [
+        KnownHyperlaneDomain::Injective,
+        KnownHyperlaneDomain::Ethereum,
+        KnownHyperlaneDomain::Arbitrum,
+    ];
+
+    proptest! {
+        #[test]
+        fn prop_pop_many_follows_next_attempt_order(
+            seconds_to_next_attempt in prop::collection::vec(1..100u64, 0..50),
+            limit in 1..20usize,
+        ) {
+            let popped = block_on(async {
+                let broadcaster = sync::broadcast::Sender::new(100);
+                let mut op_queue = initialize_queue(&broadcaster);
+                for seconds in &seconds_to_next_attempt {
+                    let op = MockPendingOperation::new(*seconds, DESTINATIONS[0].into());
+                    op_queue.push(Box::new(op), None).await;
+                }
+
+                let mut popped = vec![];
+                loop {
+                    let remaining = op_queue.total_len().await;
+                    let batch = op_queue.pop_many(limit).await;
+                    assert_eq!(batch.len(), remaining.min(limit));
+                    if batch.is_empty() {
+                        break;
+                    }
+                    popped.extend(batch);
+                }
+                popped
+            });
+
+            // Operations due at different times are at least a second apart, so
+            // popping never goes back to an earlier attempt time
+            prop_assert_eq!(popped.len(), seconds_to_next_attempt.len());
+            let attempt_times: Vec<_> = popped
+                .iter()
+                .map(|op| op.next_attempt_after().unwrap())
+                .collect();
+            prop_assert!(attempt_times
+                .windows(2)
+                .all(|pair| pair[0] <= pair[1] + Duration::from_millis(500)));
+        }
+
+        #[test]
+        fn prop_retry_requests_match_by_destination(
+            ops in prop::collection::vec((0..DESTINATIONS.len(), 1..100u64), 0..50),
+            retried in 0..DESTINATIONS.len(),
+        ) {
+            let retried_domain: HyperlaneDomain = DESTINATIONS[retried].into();
+            let (response, popped) = block_on(async {
+                let broadcaster = sync::broadcast::Sender::new(100);
+                let mut op_queue = initialize_queue(&broadcaster);
+                for (destination, seconds) in &ops {
+                    let op =
+                        MockPendingOperation::new(*seconds, DESTINATIONS[*destination].into());
+                    op_queue.push(Box::new(op), None).await;
+                }
+
+                let (transmitter, mut receiver) = mpsc::channel(ENDPOINT_MESSAGES_QUEUE_SIZE);
+                broadcaster
+                    .send(MessageRetryRequest {
+                        uuid: "0ab2b5c4-5a45-4a62-8a8e-2c3b3b0c9d3e".to_string(),
+                        pattern: MatchingList::with_destination_domain(retried_domain.id()),
+                        transmitter,
+                    })
+                    .unwrap();
+                op_queue.process_retry_requests().await;
+                let response = receiver.recv().await.unwrap();
+
+                let mut popped = vec![];
+                while let Some(op) = op_queue.pop().await {
+                    popped.push(op);
+                }
+                (response, popped)
+            });
+
+            let expected_matches = ops
+                .iter()
+                .filter(|(destination, _)| *destination == retried)
+                .count();
+            prop_assert_eq!(response.evaluated, ops.len());
+            prop_assert_eq!(response.matched as usize, expected_matches);
+            // Retried operations jump ahead of everything else
+            let (retried_ops, rest) = popped.split_at(expected_matches);
+            prop_assert!(retried_ops
+                .iter()
+                .all(|op| op.destination_domain() == &retried_domain));
+            prop_assert!(rest
+                .iter()
+                .all(|op| op.destination_domain() != &retried_domain));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn prop_metrics_track_queue_lengths_under_concurrency(
+            pushers in 1..4usize,
+            ops_per_pusher in 0..30usize,
+            moved_fraction in 0.0..=1.0f64,
+            batch_size in 1..10usize,
+            retries in 0..10usize,
+        ) {
+            let total = pushers * ops_per_pusher;
+            let to_move = (total as f64 * moved_fraction) as usize;
+            let destination: HyperlaneDomain = DESTINATIONS[0].into();
+            let (metrics, _) = dummy_metrics_and_label();
+            let broadcaster = sync::broadcast::Sender::new(100);
+            let new_queue = |label: &str| {
+                OpQueue::new(
+                    metrics.clone(),
+                    label.to_string(),
+                    Arc::new(Mutex::new(broadcaster.subscribe())),
+                )
+            };
+            let prepare_queue = new_queue("prepare_queue");
+            let submit_queue = new_queue("submit_queue");
+
+            let (prepare_len, submit_len) = block_on(async {
+                let mut tasks = vec![];
+                for _ in 0..pushers {
+                    let prepare_queue = prepare_queue.clone();
+                    let destination = destination.clone();
+                    tasks.push(tokio::spawn(async move {
+                        for seconds in 0..ops_per_pusher {
+                            let op =
+                                MockPendingOperation::new(seconds as u64, destination.clone());
+                            prepare_queue
+                                .push(
+                                    Box::new(op),
+                                    Some(PendingOperationStatus::FirstPrepareAttempt),
+                                )
+                                .await;
+                            tokio::task::yield_now().await;
+                        }
+                    }));
+                }
+
+                let mut mover_queue = prepare_queue.clone();
+                let mover_submit_queue = submit_queue.clone();
+                tasks.push(tokio::spawn(async move {
+                    let mut moved = 0;
+                    while moved < to_move {
+                        let batch = mover_queue
+                            .pop_many(batch_size.min(to_move - moved))
+                            .await;
+                        moved += batch.len();
+                        for op in batch {
+                            mover_submit_queue
+                                .push(op, Some(PendingOperationStatus::ReadyToSubmit))
+                                .await;
+                        }
+                        tokio::task::yield_now().await;
+                    }
+                }));
+
+                let mut retry_queue = prepare_queue.clone();
+                let retry_broadcaster = broadcaster.clone();
+                let retried_domain = destination.id();
+                tasks.push(tokio::spawn(async move {
+                    let (transmitter, mut receiver) =
+                        mpsc::channel(ENDPOINT_MESSAGES_QUEUE_SIZE);
+                    for _ in 0..retries {
+                        retry_broadcaster
+                            .send(MessageRetryRequest {
+                                uuid: "b7f0e6a2-3c1d-4e5f-9a8b-7c6d5e4f3a2b".to_string(),
+                                pattern: MatchingList::with_destination_domain(retried_domain),
+                                transmitter: transmitter.clone(),
+                            })
+                            .unwrap();
+                        retry_queue.process_retry_requests().await;
+                        // Either this task or the mover may have processed the request
+                        receiver.recv().await.unwrap();
+                    }
+                }));
+
+                for task in tasks {
+                    task.await.unwrap();
+                }
+                (prepare_queue.total_len().await, submit_queue.total_len().await)
+            });
+
+            let (destination_label, app_context_label) =
+                MockPendingOperation::new(0, destination).get_operation_labels();
+            let gauge = |queue_label: &str, status: PendingOperationStatus| {
+                metrics
+                    .with_label_values(&[
+                        &destination_label,
+                        queue_label,
+                        &status.to_string(),
+                        &app_context_label,
+                    ])
+                    .get()
+            };
+            prop_assert_eq!(prepare_len, total - to_move);
+            prop_assert_eq!(submit_len, to_move);
+            prop_assert_eq!(
+                gauge("prepare_queue", PendingOperationStatus::FirstPrepareAttempt),
+                prepare_len as i64
+            );
+            prop_assert_eq!(
+                gauge("submit_queue", PendingOperationStatus::ReadyToSubmit),
+                submit_len as i64
+            );
+        }
+    }
 }
@@ -1,8 +1,9 @@
 #![allow(clippy::doc_markdown)] // TODO: `rustc` 1.80.1 clippy issue
 #![allow(clippy::doc_lazy_continuation)] // TODO: `rustc` 1.80.1 clippy issue
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use derive_new::new;
 use futures::future::join_all;
@@ -10,13 +11,14 @@ use futures_util::future::try_join_all;
 use hyperlane_core::total_estimated_cost;
 use hyperlane_core::BatchResult;
 use hyperlane_core::ConfirmReason::*;
+use hyperlane_core::HoldReason;
 use hyperlane_core::PendingOperation;
 use hyperlane_core::PendingOperationStatus;
 use hyperlane_core::ReprepareReason;
 use itertools::Either;
 use itertools::Itertools;
-use prometheus::{IntCounter, IntGaugeVec};
-use tokio::sync::broadcast::Sender;
+use prometheus::{Gauge, IntCounter, IntGaugeVec};
+use tokio::sync::broadcast::{self, error::RecvError, Sender};
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
@@ -28,14 +30,21 @@ use tracing::{info, warn};
 use hyperlane_base::CoreMetrics;
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, HyperlaneDomain, HyperlaneDomainProtocol,
-    PendingOperationResult, QueueOperation, TxOutcome,
+    PendingOperationResult, QueueOperation, TxOutcome, U256,
 };
 
 use crate::msg::pending_message::CONFIRM_DELAY;
-use crate::server::MessageRetryRequest;
+use crate::server::{
+    MessageRetryRequest, OperationAdminAction, OperationAdminQueueResponse, OperationAdminRequest,
+    SubmitterQueue,
+};
+use crate::settings::{AppContextSchedulingConf, SubmissionLimitsConf};
 
+use super::admin_overrides::AdminOverrides;
+use super::app_context_scheduler::AppContextScheduler;
 use super::op_queue::OpQueue;
 use super::op_queue::OperationPriorityQueue;
+use super::submission_limiter::SubmissionLimiter;
 
 /// This is needed for logic where we need to allocate
 /// based on how many queues exist in each OpSubmitter.
@@ -102,35 +111,61 @@ pub struct SerialSubmitter {
     max_batch_size: u32,
     /// tokio task monitor
     task_monitor: TaskMonitor,
+    /// Receiver for operator requests from the admin API.
+    admin_rx: Option<broadcast::Receiver<OperationAdminRequest>>,
+    /// Limits on how much and how fast to submit to the destination.
+    submission_limits: SubmissionLimitsConf,
     prepare_queue: OpQueue,
     submit_queue: OpQueue,
     confirm_queue: OpQueue,
 }
 
 impl SerialSubmitter {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         domain: HyperlaneDomain,
         rx: mpsc::UnboundedReceiver<QueueOperation>,
         retry_op_transmitter: &Sender<MessageRetryRequest>,
+        admin_op_transmitter: &Sender<OperationAdminRequest>,
+        admin_overrides: Arc<AdminOverrides>,
+        submission_limits: SubmissionLimitsConf,
+        app_context_scheduling: HashMap<String, AppContextSchedulingConf>,
         metrics: SerialSubmitterMetrics,
         max_batch_size: u32,
         task_monitor: TaskMonitor,
     ) -> Self {
-        let prepare_queue = OpQueue::new(
+        let mut prepare_queue = OpQueue::new(
             metrics.submitter_queue_length.clone(),
             "prepare_queue".to_string(),
             Arc::new(Mutex::new(retry_op_transmitter.subscribe())),
         );
-        let submit_queue = OpQueue::new(
+        let mut submit_queue = OpQueue::new(
             metrics.submitter_queue_length.clone(),
             "submit_queue".to_string(),
             Arc::new(Mutex::new(retry_op_transmitter.subscribe())),
         );
-        let confirm_queue = OpQueue::new(
+        let mut confirm_queue = OpQueue::new(
             metrics.submitter_queue_length.clone(),
             "confirm_queue".to_string(),
             Arc::new(Mutex::new(retry_op_transmitter.subscribe())),
         );
+        // Persisted pauses are enforced whenever an operation enters a queue
+        prepare_queue = prepare_queue.with_admin_overrides(admin_overrides.clone());
+        submit_queue = submit_queue.with_admin_overrides(admin_overrides.clone());
+        confirm_queue = confirm_queue.with_admin_overrides(admin_overrides.clone());
+        if !app_context_scheduling.is_empty() {
+            // Operations in the submit and confirm queues count as in flight
+            prepare_queue = prepare_queue.with_scheduler(AppContextScheduler::new(
+                app_context_scheduling.clone(),
+                vec![submit_queue.queue.clone(), confirm_queue.queue.clone()],
+            ));
+            submit_queue = submit_queue.with_scheduler(AppContextScheduler::new(
+                app_context_scheduling.clone(),
+                vec![],
+            ));
+            confirm_queue = confirm_queue
+                .with_scheduler(AppContextScheduler::new(app_context_scheduling, vec![]));
+        }
 
         Self {
             domain,
@@ -139,6 +174,8 @@ impl SerialSubmitter {
             metrics,
             max_batch_size,
             task_monitor,
+            admin_rx: Some(admin_op_transmitter.subscribe()),
+            submission_limits,
             prepare_queue,
             submit_queue,
             confirm_queue,
@@ -164,12 +201,17 @@ impl SerialSubmitter {
 
     async fn run(mut self) {
         let rx_prepare = self.rx.take().expect("rx should be initialised");
+        let admin_rx = self
+            .admin_rx
+            .take()
+            .expect("admin_rx should be initialised");
 
         let tasks = [
             self.create_receive_task(rx_prepare),
             self.create_prepare_task(),
             self.create_submit_task(),
             self.create_confirm_task(),
+            self.create_admin_task(admin_rx),
         ];
 
         if let Err(err) = try_join_all(tasks).await {
@@ -225,6 +267,7 @@ impl SerialSubmitter {
                     self.submit_queue.clone(),
                     self.confirm_queue.clone(),
                     self.max_batch_size,
+                    SubmissionLimiter::new(self.submission_limits.clone()),
                     self.metrics.clone(),
                 ),
             ))
@@ -248,6 +291,27 @@ impl SerialSubmitter {
             .expect("spawning tokio task from Builder is infallible")
     }
 
+    fn create_admin_task(
+        &self,
+        admin_rx: broadcast::Receiver<OperationAdminRequest>,
+    ) -> JoinHandle<()> {
+        let name = Self::task_name("admin::", &self.domain);
+        tokio::task::Builder::new()
+            .name(&name)
+            .spawn(TaskMonitor::instrument(
+                &self.task_monitor,
+                admin_task(
+                    self.domain.clone(),
+                    admin_rx,
+                    self.prepare_queue.clone(),
+                    self.submit_queue.clone(),
+                    self.confirm_queue.clone(),
+                    self.metrics.clone(),
+                ),
+            ))
+            .expect("spawning tokio task from Builder is infallible")
+    }
+
     fn task_name(prefix: &str, domain: &HyperlaneDomain) -> String {
         format!("op_submitter::{}{}", prefix, domain.name())
     }
@@ -352,38 +416,119 @@ async fn submit_task(
     mut submit_queue: OpQueue,
     mut confirm_queue: OpQueue,
     max_batch_size: u32,
+    mut limiter: SubmissionLimiter,
     metrics: SerialSubmitterMetrics,
 ) {
     let recv_limit = max_batch_size as usize;
     loop {
-        let mut batch = submit_queue.pop_many(recv_limit).await;
-        match batch.len().cmp(&1) {
-            std::cmp::Ordering::Less => {
-                // The queue is empty, so give some time before checking again to prevent burning CPU
-                sleep(Duration::from_millis(100)).await;
-                continue;
-            }
-            std::cmp::Ordering::Equal => {
-                let op = batch.pop().unwrap();
-                submit_single_operation(op, &mut prepare_queue, &mut confirm_queue, &metrics).await;
+        let batch = submit_queue.pop_many(recv_limit).await;
+        if batch.is_empty() {
+            // The queue is empty, so give some time before checking again to prevent burning CPU
+            sleep(Duration::from_millis(100)).await;
+            continue;
+        }
+
+        // Admit operations while their estimated cost, added to what was spent and
+        // reserved in the current window, stays within the limits
+        let now = Instant::now();
+        let mut batch = batch.into_iter();
+        let mut admitted = vec![];
+        let mut admitted_estimate = U256::zero();
+        let mut hold_reason = None;
+        for op in batch.by_ref() {
+            let estimate =
+                admitted_estimate.saturating_add(limiter.estimate_spend(op.get_tx_cost_estimate()));
+            if let Some(reason) = limiter.check(now, estimate) {
+                hold_reason = Some((reason, op));
+                break;
             }
-            std::cmp::Ordering::Greater => {
-                OperationBatch::new(batch, domain.clone())
-                    .submit(&mut prepare_queue, &mut confirm_queue, &metrics)
-                    .await;
+            admitted_estimate = estimate;
+            admitted.push(op);
+        }
+        metrics.set_window_spend(&limiter, now);
+
+        if let Some((reason, held_op)) = hold_reason {
+            let held = std::iter::once(held_op).chain(batch).collect_vec();
+            hold_operations(held, &reason, &mut submit_queue, &metrics).await;
+            if admitted.is_empty() {
+                // Nothing can be submitted until the window reopens
+                let reopens_at = limiter.reopens_at(now, &reason);
+                sleep(reopens_at.saturating_duration_since(now)).await;
+                continue;
             }
+        } else {
+            metrics.set_held(None, 0);
         }
+
+        if admitted.len() == 1 {
+            let op = admitted.pop().unwrap();
+            submit_single_operation(
+                op,
+                &mut prepare_queue,
+                &mut confirm_queue,
+                &mut limiter,
+                &metrics,
+            )
+            .await;
+        } else {
+            OperationBatch::new(admitted, domain.clone())
+                .submit(
+                    &mut prepare_queue,
+                    &mut submit_queue,
+                    &mut confirm_queue,
+                    &mut limiter,
+                    &metrics,
+                )
+                .await;
+        }
+        metrics.set_window_spend(&limiter, Instant::now());
     }
 }
 
-#[instrument(skip(prepare_queue, confirm_queue, metrics), ret, level = "debug")]
+/// Puts operations back in the submit queue until the submission limits allow
+/// sending them
+async fn hold_operations(
+    held: Vec<QueueOperation>,
+    reason: &HoldReason,
+    submit_queue: &mut OpQueue,
+    metrics: &SerialSubmitterMetrics,
+) {
+    debug!(
+        ?reason,
+        held = held.len(),
+        "Submission limit reached, holding operations"
+    );
+    metrics.set_held(Some(reason), held.len());
+    for op in held {
+        submit_queue
+            .push(op, Some(PendingOperationStatus::Held(reason.clone())))
+            .await;
+    }
+}
+
+#[instrument(
+    skip(prepare_queue, confirm_queue, limiter, metrics),
+    ret,
+    level = "debug"
+)]
 async fn submit_single_operation(
     mut op: QueueOperation,
     prepare_queue: &mut OpQueue,
     confirm_queue: &mut OpQueue,
+    limiter: &mut SubmissionLimiter,
     metrics: &SerialSubmitterMetrics,
 ) {
+    let estimate = limiter.estimate_spend(op.get_tx_cost_estimate());
+    let reservation = limiter.reserve(Instant::now(), estimate);
     let status = op.submit().await;
+    let sent = matches!(
+        status,
+        PendingOperationResult::Success | PendingOperationResult::Confirm(_)
+    );
+    limiter.settle(
+        reservation,
+        op.get_submission_outcome().filter(|_| sent).as_ref(),
+    );
     match status {
         PendingOperationResult::Reprepare(reprepare_reason) => {
             prepare_queue
@@ -518,6 +663,95 @@ async fn confirm_operation(
     operation_result
 }
 
+#[instrument(skip_all, fields(%domain))]
+async fn admin_task(
+    domain: HyperlaneDomain,
+    mut admin_rx: broadcast::Receiver<OperationAdminRequest>,
+    prepare_queue: OpQueue,
+    submit_queue: OpQueue,
+    confirm_queue: OpQueue,
+    metrics: SerialSubmitterMetrics,
+) {
+    let queues = [
+        (SubmitterQueue::Prepare, prepare_queue),
+        (SubmitterQueue::Submit, submit_queue),
+        (SubmitterQueue::Confirm, confirm_queue),
+    ];
+    loop {
+        let request = match admin_rx.recv().await {
+            Ok(request) => request,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(skipped, "Operation admin requests were dropped");
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+        info!(uuid = request.uuid, action = ?request.action, "Processing operation admin request");
+        for response in apply_admin_request(&domain, &request, &queues, &metrics).await {
+            if let Err(err) = request.transmitter.send(response).await {
+                tracing::error!(?err, "Failed to send operation admin response");
+            }
+        }
+    }
+}
+
+/// Applies an operator request to the operations held by each queue, returning
+/// the number of operations evaluated and matched per queue.
+async fn apply_admin_request(
+    domain: &HyperlaneDomain,
+    request: &OperationAdminRequest,
+    queues: &[(SubmitterQueue, OpQueue)],
+    metrics: &SerialSubmitterMetrics,
+) -> Vec<OperationAdminQueueResponse> {
+    let mut responses = vec![];
+    for (kind, queue) in queues {
+        let evaluated = queue.total_len().await;
+        let matched = match &request.action {
+            OperationAdminAction::Pause => queue.pause_matching(&request.pattern).await,
+            OperationAdminAction::Resume => queue.resume_matching(&request.pattern).await,
+            OperationAdminAction::Retry => queue.retry_matching(&request.pattern).await,
+            OperationAdminAction::Drop => {
+                let dropped = queue.remove_matching(&request.pattern, true).await;
+                for op in &dropped {
+                    debug!(?op, "Operation dropped by operator");
+                    metrics.ops_dropped.inc();
+                    op.decrement_metric_if_exists();
+                }
+                dropped.len() as u64
+            }
+            OperationAdminAction::Move { to } if to == kind => 0,
+            OperationAdminAction::Move { to } => {
+                let moved = queue.remove_matching(&request.pattern, false).await;
+                let matched = moved.len() as u64;
+                let (_, target) = queues
+                    .iter()
+                    .find(|(kind, _)| kind == to)
+                    .expect("all submitter queues are present");
+                let status = match to {
+                    SubmitterQueue::Prepare => {
+                        PendingOperationStatus::Retry(ReprepareReason::ManuallyMoved)
+                    }
+                    SubmitterQueue::Submit => PendingOperationStatus::ReadyToSubmit,
+                    SubmitterQueue::Confirm => PendingOperationStatus::Confirm(ManuallyMoved),
+                };
+                for mut op in moved {
+                    debug!(?op, ?to, "Operation moved by operator");
+                    op.reset_attempts();
+                    target.push(op, Some(status.clone())).await;
+                }
+                matched
+            }
+        };
+        responses.push(OperationAdminQueueResponse::new(
+            domain.name().to_owned(),
+            queue.queue_metrics_label().to_owned(),
+            evaluated,
+            matched,
+        ));
+    }
+    responses
+}
+
 #[derive(Debug, Clone)]
 pub struct SerialSubmitterMetrics {
     submitter_queue_length: IntGaugeVec,
@@ -526,6 +760,9 @@ pub struct SerialSubmitterMetrics {
     ops_confirmed: IntCounter,
     ops_failed: IntCounter,
     ops_dropped: IntCounter,
+    held_operations: IntGaugeVec,
+    window_spend: Gauge,
+    destination: String,
 }
 
 impl SerialSubmitterMetrics {
@@ -548,8 +785,36 @@ impl SerialSubmitterMetrics {
             ops_dropped: metrics
                 .operations_processed_count()
                 .with_label_values(&["dropped", destination]),
+            held_operations: metrics.submitter_held_operations(),
+            window_spend: metrics
+                .submitter_window_spend()
+                .with_label_values(&[destination]),
+            destination: destination.to_owned(),
         }
     }
+
+    /// Sets the number of operations held back for `reason`, clearing the count
+    /// for any other reason
+    fn set_held(&self, reason: Option<&HoldReason>, count: usize) {
+        for other in [HoldReason::SpendBudgetExhausted, HoldReason::TxRateLimited] {
+            let held = if reason == Some(&other) { count } else { 0 };
+            self.held_operations
+                .with_label_values(&[&self.destination, hold_reason_label(&other)])
+                .set(held as i64);
+        }
+    }
+
+    fn set_window_spend(&self, limiter: &SubmissionLimiter, now: Instant) {
+        self.window_spend
+            .set(limiter.window_spend(now).to_f64_lossy());
+    }
+}
+
+fn hold_reason_label(reason: &HoldReason) -> &'static str {
+    match reason {
+        HoldReason::SpendBudgetExhausted => "spend_budget_exhausted",
+        HoldReason::TxRateLimited => "tx_rate_limited",
+    }
 }
 
 #[derive(new, Debug)]
@@ -563,14 +828,22 @@ impl OperationBatch {
     async fn submit(
         self,
         prepare_queue: &mut OpQueue,
+        submit_queue: &mut OpQueue,
         confirm_queue: &mut OpQueue,
+        limiter: &mut SubmissionLimiter,
         metrics: &SerialSubmitterMetrics,
     ) {
+        let estimate = self.operations.iter().fold(U256::zero(), |acc, op| {
+            acc.saturating_add(limiter.estimate_spend(op.get_tx_cost_estimate()))
+        });
+        let reservation = limiter.reserve(Instant::now(), estimate);
         let excluded_ops = match self.try_submit_as_batch(metrics).await {
             Ok(batch_result) => {
+                limiter.settle(reservation, batch_result.outcome.as_ref());
                 Self::handle_batch_result(self.operations, batch_result, confirm_queue).await
             }
             Err(e) => {
+                limiter.settle(reservation, None);
                 warn!(error=?e, batch=?self.operations, "Error when submitting batch");
                 self.operations
             }
@@ -579,7 +852,7 @@ impl OperationBatch {
         if !excluded_ops.is_empty() {
             warn!(excluded_ops=?excluded_ops, "Either operations reverted in the batch or the txid wasn't included. Falling back to serial submission.");
             OperationBatch::new(excluded_ops, self.domain)
-                .submit_serially(prepare_queue, confirm_queue, metrics)
+                .submit_serially(prepare_queue, submit_queue, confirm_queue, limiter, metrics)
                 .await;
         }
     }
@@ -644,14 +917,25 @@ impl OperationBatch {
         }
     }
 
+    /// Submits the operations one by one while the submission limits allow it,
+    /// holding back the rest
     async fn submit_serially(
         self,
         prepare_queue: &mut OpQueue,
+        submit_queue: &mut OpQueue,
         confirm_queue: &mut OpQueue,
+        limiter: &mut SubmissionLimiter,
         metrics: &SerialSubmitterMetrics,
     ) {
-        for op in self.operations.into_iter() {
-            submit_single_operation(op, prepare_queue, confirm_queue, metrics).await;
+        let mut operations = self.operations.into_iter();
+        while let Some(op) = operations.next() {
+            let estimate = limiter.estimate_spend(op.get_tx_cost_estimate());
+            if let Some(reason) = limiter.check(Instant::now(), estimate) {
+                let held = std::iter::once(op).chain(operations).collect_vec();
+                hold_operations(held, &reason, submit_queue, metrics).await;
+                return;
+            }
+            submit_single_operation(op, prepare_queue, confirm_queue, limiter, metrics).await;
         }
     }
 }
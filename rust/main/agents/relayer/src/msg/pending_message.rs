@@ -17,13 +17,14 @@ use hyperlane_base::{db::HyperlaneDb, CoreMetrics};
 use hyperlane_core::{
     gas_used_by_operation, BatchItem, ChainCommunicationError, ChainResult, ConfirmReason,
     HyperlaneChain, HyperlaneDomain, HyperlaneMessage, Mailbox, MessageSubmissionData,
-    PendingOperation, PendingOperationResult, PendingOperationStatus, ReprepareReason, TryBatchAs,
-    TxOutcome, H256, U256,
+    OperationLifecycleEvent, PendingOperation, PendingOperationResult, PendingOperationStatus,
+    ReprepareReason, TryBatchAs, TxOutcome, H256, U256,
 };
 use hyperlane_operation_verifier::ApplicationOperationVerifier;
 
 use super::{
     gas_payment::{GasPaymentEnforcer, GasPolicyStatus},
+    lifecycle_recorder::LifecycleRecorder,
     metadata::{BaseMetadataBuilder, MessageMetadataBuilder, Metadata, MetadataBuilder},
 };
 
@@ -60,6 +61,8 @@ pub struct MessageContext {
     pub metrics: MessageSubmissionMetrics,
     /// Application operation verifier
     pub application_operation_verifier: Option<Arc<dyn ApplicationOperationVerifier>>,
+    /// Persists the lifecycle events of messages to the origin database
+    pub lifecycle_recorder: Arc<LifecycleRecorder>,
 }
 
 /// A message that the submitter can and should try to submit.
@@ -94,6 +97,10 @@ pub struct PendingMessage {
     #[new(default)]
     #[serde(skip_serializing)]
     metric: Option<Arc<IntGauge>>,
+    /// Outcome of the last recorded confirm attempt, as `(delivered, errored)`
+    #[new(default)]
+    #[serde(skip_serializing)]
+    last_confirm_outcome: Option<(bool, bool)>,
 }
 
 impl Debug for PendingMessage {
@@ -184,6 +191,10 @@ impl PendingOperation for PendingMessage {
         &self.message.recipient
     }
 
+    fn body(&self) -> &[u8] {
+        &self.message.body
+    }
+
     fn retrieve_status_from_db(&self) -> Option<PendingOperationStatus> {
         match self.ctx.origin_db.retrieve_status_by_message_id(&self.id()) {
             Ok(status) => status,
@@ -198,6 +209,10 @@ impl PendingOperation for PendingMessage {
         self.app_context.clone()
     }
 
+    fn record_lifecycle_event(&self, event: OperationLifecycleEvent) {
+        self.ctx.lifecycle_recorder.record(self.message.id(), event);
+    }
+
     #[instrument(skip(self), fields(id=?self.id()), level = "debug")]
     async fn prepare(&mut self) -> PendingOperationResult {
         if !self.is_ready() {
@@ -267,6 +282,7 @@ impl PendingOperation for PendingMessage {
         {
             Ok(message_metadata_builder) => message_metadata_builder,
             Err(err) => {
+                self.record_metadata_build_attempt(Some(err.to_string()));
                 return self.on_reprepare(Some(err), ReprepareReason::ErrorGettingMetadataBuilder);
             }
         };
@@ -277,21 +293,27 @@ impl PendingOperation for PendingMessage {
         {
             Ok(metadata) => metadata,
             Err(err) => {
+                self.record_metadata_build_attempt(Some(err.to_string()));
                 return self.on_reprepare(Some(err), ReprepareReason::ErrorBuildingMetadata);
             }
         };
 
         let metadata_bytes = match metadata {
             Metadata::Found(metadata_bytes) => {
+                self.record_metadata_build_attempt(None);
                 self.metadata = Some(metadata_bytes.clone());
                 metadata_bytes
             }
             Metadata::CouldNotFetch => {
+                self.record_metadata_build_attempt(Some(
+                    ReprepareReason::CouldNotFetchMetadata.to_string(),
+                ));
                 return self.on_reprepare::<String>(None, ReprepareReason::CouldNotFetchMetadata);
             }
             // If the metadata building is refused, we still allow it to be retried later.
             Metadata::Refused(reason) => {
                 warn!(?reason, "Metadata building refused");
+                self.record_metadata_build_attempt(Some(format!("Refused: {reason}")));
                 return self.on_reprepare::<String>(None, ReprepareReason::MessageMetadataRefused);
             }
         };
@@ -331,14 +353,17 @@ impl PendingOperation for PendingMessage {
 
         let gas_limit = match gas_limit {
             GasPolicyStatus::NoPaymentFound => {
-                return self.on_reprepare::<String>(None, ReprepareReason::GasPaymentNotFound)
+                return self.on_gas_policy_rejected(ReprepareReason::GasPaymentNotFound)
             }
             GasPolicyStatus::PolicyNotMet => {
-                return self
-                    .on_reprepare::<String>(None, ReprepareReason::GasPaymentRequirementNotMet)
+                return self.on_gas_policy_rejected(ReprepareReason::GasPaymentRequirementNotMet)
             }
             GasPolicyStatus::PolicyMet(gas_limit) => gas_limit,
         };
+        self.record_lifecycle_event(OperationLifecycleEvent::GasPolicyDecision {
+            gas_limit: Some(gas_limit),
+            rejection: None,
+        });
 
         // Go ahead and attempt processing of message to destination chain.
         debug!(
@@ -404,6 +429,9 @@ impl PendingOperation for PendingMessage {
             }
             Err(e) => {
                 error!(error=?e, "Error when processing message");
+                self.record_lifecycle_event(OperationLifecycleEvent::SubmissionFailed {
+                    error: e.to_string(),
+                });
                 return PendingOperationResult::Reprepare(ReprepareReason::ErrorSubmitting);
             }
         }
@@ -413,6 +441,10 @@ impl PendingOperation for PendingMessage {
         self.submission_outcome = Some(outcome);
     }
 
+    fn get_submission_outcome(&self) -> Option<TxOutcome> {
+        self.submission_outcome.clone()
+    }
+
     fn get_tx_cost_estimate(&self) -> Option<U256> {
         self.submission_data.as_ref().map(|d| d.gas_limit)
     }
@@ -430,9 +462,11 @@ impl PendingOperation for PendingMessage {
         {
             Ok(is_delivered) => is_delivered,
             Err(err) => {
+                self.record_confirm_attempt(false, Some(err.to_string()));
                 return self.on_reconfirm(Some(err), "Error confirming message delivery");
             }
         };
+        self.record_confirm_attempt(is_delivered, None);
 
         if is_delivered {
             if let Err(err) = self.record_message_process_success() {
@@ -489,6 +523,11 @@ impl PendingOperation for PendingMessage {
         {
             error!(error=?e, "Error when recording tx outcome");
         }
+        self.record_lifecycle_event(OperationLifecycleEvent::TransactionSubmitted {
+            transaction_id: operation_outcome.transaction_id,
+            executed: operation_outcome.executed,
+            gas_used: operation_outcome.gas_used,
+        });
         // set the outcome in `Self` as well, for later logging
         self.set_submission_outcome(operation_outcome);
         debug!(
@@ -628,6 +667,29 @@ impl PendingMessage {
         PendingOperationResult::Reprepare(reason)
     }
 
+    fn on_gas_policy_rejected(&mut self, reason: ReprepareReason) -> PendingOperationResult {
+        self.record_lifecycle_event(OperationLifecycleEvent::GasPolicyDecision {
+            gas_limit: None,
+            rejection: Some(reason.clone()),
+        });
+        self.on_reprepare::<String>(None, reason)
+    }
+
+    fn record_metadata_build_attempt(&self, error: Option<String>) {
+        self.record_lifecycle_event(OperationLifecycleEvent::MetadataBuildAttempt { error });
+    }
+
+    /// Records a confirm attempt only when its outcome differs from the last one,
+    /// so a message waiting on confirmation doesn't push everything else out of its log
+    fn record_confirm_attempt(&mut self, delivered: bool, error: Option<String>) {
+        let outcome = (delivered, error.is_some());
+        if self.last_confirm_outcome == Some(outcome) {
+            return;
+        }
+        self.last_confirm_outcome = Some(outcome);
+        self.record_lifecycle_event(OperationLifecycleEvent::ConfirmAttempt { delivered, error });
+    }
+
     fn on_reconfirm<E: Debug>(&mut self, err: Option<E>, reason: &str) -> PendingOperationResult {
         self.inc_attempts();
         if let Some(e) = err {
@@ -858,6 +920,15 @@ mod test {
                 &self,
                 message_id: &H256,
             ) -> DbResult<Option<PendingOperationStatus>>;
+            fn store_operation_lifecycle_by_message_id(
+                &self,
+                message_id: &H256,
+                log: &OperationLifecycleLog,
+            ) -> DbResult<()>;
+            fn retrieve_operation_lifecycle_by_message_id(
+                &self,
+                message_id: &H256,
+            ) -> DbResult<Option<OperationLifecycleLog>>;
             fn store_interchain_gas_payment_data_by_gas_payment_key(
                 &self,
                 key: &GasPaymentKey,
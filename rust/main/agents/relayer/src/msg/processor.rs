@@ -8,7 +8,6 @@ use std::{
 
 use async_trait::async_trait;
 use derive_new::new;
-use ethers::utils::hex;
 use eyre::Result;
 use hyperlane_base::{
     db::{HyperlaneDb, HyperlaneRocksDB},
@@ -17,9 +16,14 @@ use hyperlane_base::{
 use hyperlane_core::{HyperlaneDomain, HyperlaneMessage, QueueOperation};
 use prometheus::IntGauge;
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, info, instrument, trace, warn};
 
-use super::{blacklist::AddressBlacklist, metadata::AppContextClassifier, pending_message::*};
+use super::{
+    admin_overrides::{AdminOverrides, OverrideAction},
+    blacklist::AddressBlacklist,
+    metadata::AppContextClassifier,
+    pending_message::*,
+};
 use crate::{processor::ProcessorExt, settings::matching_list::MatchingList};
 
 /// Finds unprocessed messages from an origin and submits then through a channel
@@ -32,6 +36,8 @@ pub struct MessageProcessor {
     message_blacklist: Arc<MatchingList>,
     /// Addresses that messages may not interact with.
     address_blacklist: Arc<AddressBlacklist>,
+    /// Persisted operator overrides, re-applied as messages are rehydrated.
+    admin_overrides: Arc<AdminOverrides>,
     metrics: MessageProcessorMetrics,
     /// channel for each destination chain to send operations (i.e. message
     /// submissions) to
@@ -280,18 +286,44 @@ impl ProcessorExt for MessageProcessor {
             {
                 debug!(
                     ?msg,
-                    blacklisted_address = hex::encode(blacklisted_address),
+                    %blacklisted_address,
                     "Message involves blacklisted address, skipping"
                 );
                 return Ok(());
             }
 
+            // Skip if an operator dropped the message
+            if let Some(dropped_by) = self
+                .admin_overrides
+                .find_for_message(OverrideAction::Drop, &msg)
+            {
+                warn!(
+                    ?msg,
+                    override_id = dropped_by.id,
+                    "Message dropped by admin override, skipping"
+                );
+                return Ok(());
+            }
+
             // Skip if the message is intended for a destination we do not service
             if !self.send_channels.contains_key(&destination) {
                 debug!(?msg, "Message destined for unknown domain, skipping");
                 return Ok(());
             }
 
+            // Forget the persisted backoff once if an operator asked for the message to be retried
+            let origin_db = &self.destination_ctxs[&destination].origin_db;
+            if let Some(retried_by) = self.admin_overrides.find_unapplied_retry(&msg, origin_db)? {
+                info!(
+                    ?msg,
+                    override_id = retried_by.id,
+                    "Resetting retry count per admin override"
+                );
+                origin_db.store_pending_message_retry_count_by_message_id(&msg.id(), &0)?;
+                self.admin_overrides
+                    .mark_retry_applied(&retried_by, &msg, origin_db)?;
+            }
+
             debug!(%msg, "Sending message to submitter");
 
             let app_context_classifier =
@@ -322,6 +354,7 @@ impl MessageProcessor {
         message_whitelist: Arc<MatchingList>,
         message_blacklist: Arc<MatchingList>,
         address_blacklist: Arc<AddressBlacklist>,
+        admin_overrides: Arc<AdminOverrides>,
         metrics: MessageProcessorMetrics,
         send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
         destination_ctxs: HashMap<u32, Arc<MessageContext>>,
@@ -332,6 +365,7 @@ impl MessageProcessor {
             message_whitelist,
             message_blacklist,
             address_blacklist,
+            admin_overrides,
             metrics,
             send_channels,
             destination_ctxs,
@@ -413,7 +447,7 @@ mod test {
     };
     use hyperlane_core::{
         test_utils::dummy_domain, GasPaymentKey, InterchainGasPayment, InterchainGasPaymentMeta,
-        MerkleTreeInsertion, PendingOperationStatus, H256,
+        MerkleTreeInsertion, OperationLifecycleLog, PendingOperationStatus, H256,
     };
     use hyperlane_operation_verifier::{
         ApplicationOperationVerifier, ApplicationOperationVerifierReport,
@@ -424,6 +458,7 @@ mod test {
         merkle_tree::builder::MerkleTreeBuilder,
         msg::{
             gas_payment::GasPaymentEnforcer,
+            lifecycle_recorder::LifecycleRecorder,
             metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
         },
         processor::Processor,
@@ -525,6 +560,7 @@ mod test {
             transaction_gas_limit: Default::default(),
             metrics: dummy_submission_metrics(),
             application_operation_verifier: Some(Arc::new(DummyApplicationOperationVerifier {})),
+            lifecycle_recorder: Arc::new(LifecycleRecorder::new(Arc::new(db.clone()))),
         });
 
         let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
@@ -534,6 +570,7 @@ mod test {
                 Default::default(),
                 Default::default(),
                 Default::default(),
+                Default::default(),
                 dummy_processor_metrics(origin_domain.id()),
                 HashMap::from([(destination_domain.id(), send_channel)]),
                 HashMap::from([(destination_domain.id(), message_context)]),
@@ -685,6 +722,17 @@ mod test {
                 message_id: &H256,
             ) -> DbResult<Option<PendingOperationStatus>>;
 
+            fn store_operation_lifecycle_by_message_id(
+                &self,
+                message_id: &H256,
+                log: &OperationLifecycleLog,
+            ) -> DbResult<()>;
+
+            fn retrieve_operation_lifecycle_by_message_id(
+                &self,
+                message_id: &H256,
+            ) -> DbResult<Option<OperationLifecycleLog>>;
+
             fn store_interchain_gas_payment_data_by_gas_payment_key(
                 &self,
                 key: &GasPaymentKey,
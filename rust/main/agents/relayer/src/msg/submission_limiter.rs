@@ -0,0 +1,360 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use hyperlane_core::{FixedPointNumber, HoldReason, TxOutcome, H512, U256};
+use tracing::warn;
+
+use crate::settings::SubmissionLimitsConf;
+
+/// Window over which `max_tx_per_minute` is enforced
+const TX_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Identifies the spend of a transaction within the limiter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpendKey {
+    /// Estimated spend of a transaction that is being submitted
+    Reserved(u64),
+    /// Spend of a transaction that was sent
+    Tx(H512),
+}
+
+#[derive(Debug)]
+struct Spend {
+    at: Instant,
+    key: SpendKey,
+    amount: U256,
+}
+
+/// A spend reserved for a transaction before submitting it
+#[derive(Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Reservation(u64);
+
+/// Tracks the transactions a submitter sent to its destination and decides
+/// whether it may send more under the configured limits.
+///
+/// Operations reserve their estimated spend before they are submitted. Once
+/// submitted, the reservation is settled with the actual spend, keyed by
+/// transaction id so that a transaction is only ever counted once.
+#[derive(Debug)]
+pub struct SubmissionLimiter {
+    conf: SubmissionLimitsConf,
+    /// Transactions sent or being sent during the longest window, oldest first
+    spends: VecDeque<Spend>,
+    next_reservation: u64,
+    /// Gas price paid by the most recently settled transaction
+    last_gas_price: Option<FixedPointNumber>,
+}
+
+impl SubmissionLimiter {
+    pub fn new(conf: SubmissionLimitsConf) -> Self {
+        Self {
+            conf,
+            spends: VecDeque::new(),
+            next_reservation: 0,
+            last_gas_price: None,
+        }
+    }
+
+    /// Converts an operation's gas estimate into native tokens at the gas
+    /// price last paid. Without an estimate or a known gas price, the whole
+    /// spend budget is assumed, so that unknown costs are never waved through.
+    pub fn estimate_spend(&self, gas_estimate: Option<U256>) -> U256 {
+        let unknown = self.conf.spend_budget.unwrap_or_default();
+        let (Some(gas_estimate), Some(gas_price)) = (gas_estimate, self.last_gas_price.as_ref())
+        else {
+            return unknown;
+        };
+        FixedPointNumber::try_from(gas_estimate)
+            .and_then(|gas| TryInto::<U256>::try_into(gas * gas_price.clone()))
+            .unwrap_or(unknown)
+    }
+
+    /// Returns why a transaction with the given estimated spend should be
+    /// held back at `now`, if it should
+    pub fn check(&mut self, now: Instant, estimate: U256) -> Option<HoldReason> {
+        self.prune(now);
+        if let Some(budget) = self.conf.spend_budget {
+            if self.window_spend(now).saturating_add(estimate) > budget {
+                return Some(HoldReason::SpendBudgetExhausted);
+            }
+        }
+        if let Some(max_tx_per_minute) = self.conf.max_tx_per_minute {
+            if self.tx_count(now) >= max_tx_per_minute as usize {
+                return Some(HoldReason::TxRateLimited);
+            }
+        }
+        None
+    }
+
+    /// Counts a transaction with the given estimated spend until it is settled
+    pub fn reserve(&mut self, now: Instant, estimate: U256) -> Reservation {
+        let id = self.next_reservation;
+        self.next_reservation += 1;
+        self.spends.push_back(Spend {
+            at: now,
+            key: SpendKey::Reserved(id),
+            amount: estimate,
+        });
+        Reservation(id)
+    }
+
+    /// Replaces a reservation with the spend of the transaction that was sent,
+    /// or releases it if no transaction was sent. A transaction that was
+    /// already counted only releases the reservation.
+    pub fn settle(&mut self, reservation: Reservation, outcome: Option<&TxOutcome>) {
+        let Some(index) = self
+            .spends
+            .iter()
+            .position(|spend| spend.key == SpendKey::Reserved(reservation.0))
+        else {
+            // The reservation already left the window
+            return;
+        };
+        let already_counted = outcome.map_or(true, |outcome| {
+            self.spends
+                .iter()
+                .any(|spend| spend.key == SpendKey::Tx(outcome.transaction_id))
+        });
+        if let Some(outcome) = outcome {
+            self.last_gas_price = Some(outcome.gas_price.clone());
+        }
+        match outcome {
+            Some(outcome) if !already_counted => {
+                let spend = &mut self.spends[index];
+                spend.key = SpendKey::Tx(outcome.transaction_id);
+                spend.amount = tx_spend(outcome);
+            }
+            _ => {
+                self.spends.remove(index);
+            }
+        }
+    }
+
+    /// When a hold for `reason` lifts at the earliest, assuming nothing else is sent
+    pub fn reopens_at(&self, now: Instant, reason: &HoldReason) -> Instant {
+        let window = match reason {
+            HoldReason::TxRateLimited => TX_RATE_WINDOW,
+            _ => self.conf.spend_window,
+        };
+        self.spends
+            .iter()
+            .find(|spend| now.duration_since(spend.at) < window)
+            .map(|oldest| oldest.at + window)
+            // Nothing is left to expire, e.g. a single transaction exceeding the budget
+            .unwrap_or(now + window)
+    }
+
+    /// Native tokens spent or reserved during the current spend window
+    pub fn window_spend(&self, now: Instant) -> U256 {
+        self.spends
+            .iter()
+            .filter(|spend| now.duration_since(spend.at) < self.conf.spend_window)
+            .fold(U256::zero(), |acc, spend| acc.saturating_add(spend.amount))
+    }
+
+    fn tx_count(&self, now: Instant) -> usize {
+        self.spends
+            .iter()
+            .filter(|spend| now.duration_since(spend.at) < TX_RATE_WINDOW)
+            .count()
+    }
+
+    fn prune(&mut self, now: Instant) {
+        let longest_window = self.conf.spend_window.max(TX_RATE_WINDOW);
+        while matches!(self.spends.front(), Some(spend) if now.duration_since(spend.at) >= longest_window)
+        {
+            self.spends.pop_front();
+        }
+    }
+}
+
+/// The native tokens paid for a transaction
+fn tx_spend(outcome: &TxOutcome) -> U256 {
+    FixedPointNumber::try_from(outcome.gas_used)
+        .and_then(|gas_used| TryInto::<U256>::try_into(gas_used * outcome.gas_price.clone()))
+        .unwrap_or_else(|err| {
+            warn!(
+                ?err,
+                ?outcome,
+                "Failed to compute transaction spend, counting it as zero"
+            );
+            U256::zero()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn outcome(tx: u64, gas_used: u64, gas_price: u64) -> TxOutcome {
+        TxOutcome {
+            transaction_id: H512::from_low_u64_be(tx),
+            executed: true,
+            gas_used: gas_used.into(),
+            gas_price: gas_price.into(),
+        }
+    }
+
+    fn send(limiter: &mut SubmissionLimiter, at: Instant, outcome: &TxOutcome) {
+        let reservation = limiter.reserve(at, U256::zero());
+        limiter.settle(reservation, Some(outcome));
+    }
+
+    #[test]
+    fn test_no_limits_never_hold() {
+        let mut limiter = SubmissionLimiter::new(SubmissionLimitsConf::default());
+        let now = Instant::now();
+        for tx in 0..100 {
+            send(&mut limiter, now, &outcome(tx, 100_000, 1_000_000_000));
+        }
+        assert_eq!(limiter.check(now, U256::MAX), None);
+    }
+
+    #[test]
+    fn test_spend_budget_is_rolling() {
+        let mut limiter = SubmissionLimiter::new(SubmissionLimitsConf {
+            spend_budget: Some(U256::from(1_000)),
+            spend_window: Duration::from_secs(600),
+            max_tx_per_minute: None,
+        });
+        let start = Instant::now();
+
+        send(&mut limiter, start, &outcome(1, 10, 60));
+        assert_eq!(limiter.check(start, U256::zero()), None);
+        send(
+            &mut limiter,
+            start + Duration::from_secs(300),
+            &outcome(2, 10, 40),
+        );
+        let later = start + Duration::from_secs(300);
+        assert_eq!(limiter.window_spend(later), U256::from(1_000));
+        assert_eq!(
+            limiter.check(later, U256::one()),
+            Some(HoldReason::SpendBudgetExhausted)
+        );
+        assert_eq!(
+            limiter.reopens_at(later, &HoldReason::SpendBudgetExhausted),
+            start + Duration::from_secs(600)
+        );
+
+        // The first transaction leaves the window
+        let reopened = start + Duration::from_secs(600);
+        assert_eq!(limiter.check(reopened, U256::from(600)), None);
+        assert_eq!(limiter.window_spend(reopened), U256::from(400));
+    }
+
+    #[test]
+    fn test_estimate_is_reserved_until_settled() {
+        let mut limiter = SubmissionLimiter::new(SubmissionLimitsConf {
+            spend_budget: Some(U256::from(1_000)),
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        // The estimate alone would exceed the budget
+        assert_eq!(
+            limiter.check(now, U256::from(1_001)),
+            Some(HoldReason::SpendBudgetExhausted)
+        );
+
+        let reservation = limiter.reserve(now, U256::from(800));
+        assert_eq!(
+            limiter.check(now, U256::from(300)),
+            Some(HoldReason::SpendBudgetExhausted)
+        );
+
+        // The transaction was cheaper than estimated
+        limiter.settle(reservation, Some(&outcome(1, 10, 20)));
+        assert_eq!(limiter.window_spend(now), U256::from(200));
+
+        // A submission that sent no transaction releases its reservation
+        let reservation = limiter.reserve(now, U256::from(800));
+        limiter.settle(reservation, None);
+        assert_eq!(limiter.window_spend(now), U256::from(200));
+    }
+
+    #[test]
+    fn test_gas_estimates_are_priced_in_native_tokens() {
+        let mut limiter = SubmissionLimiter::new(SubmissionLimitsConf {
+            spend_budget: Some(U256::from(1_000)),
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        // Before any transaction was paid for, costs count as the whole budget
+        assert_eq!(
+            limiter.estimate_spend(Some(U256::from(10))),
+            U256::from(1_000)
+        );
+        assert_eq!(limiter.estimate_spend(None), U256::from(1_000));
+        let estimate = limiter.estimate_spend(Some(U256::from(10)));
+        let reservation = limiter.reserve(now, estimate);
+        assert_eq!(
+            limiter.check(now, limiter.estimate_spend(Some(U256::from(1)))),
+            Some(HoldReason::SpendBudgetExhausted)
+        );
+
+        // 10 gas at a price of 20 settles as 200 native tokens
+        limiter.settle(reservation, Some(&outcome(1, 10, 20)));
+        assert_eq!(limiter.window_spend(now), U256::from(200));
+
+        // 40 gas units are priced at 800, which still fits in the budget
+        let estimate = limiter.estimate_spend(Some(U256::from(40)));
+        assert_eq!(estimate, U256::from(800));
+        assert_eq!(limiter.check(now, estimate), None);
+        let estimate = limiter.estimate_spend(Some(U256::from(41)));
+        assert_eq!(
+            limiter.check(now, estimate),
+            Some(HoldReason::SpendBudgetExhausted)
+        );
+        // Unknown costs are still held back
+        assert_eq!(
+            limiter.check(now, limiter.estimate_spend(None)),
+            Some(HoldReason::SpendBudgetExhausted)
+        );
+    }
+
+    #[test]
+    fn test_tx_is_counted_once() {
+        let mut limiter = SubmissionLimiter::new(SubmissionLimitsConf {
+            max_tx_per_minute: Some(2),
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        send(&mut limiter, now, &outcome(1, 10, 20));
+        // A stale outcome of the same transaction is reported again
+        send(&mut limiter, now, &outcome(1, 10, 20));
+        assert_eq!(limiter.window_spend(now), U256::from(200));
+        assert_eq!(limiter.check(now, U256::zero()), None);
+    }
+
+    #[test]
+    fn test_tx_rate_limit() {
+        let mut limiter = SubmissionLimiter::new(SubmissionLimitsConf {
+            max_tx_per_minute: Some(2),
+            ..Default::default()
+        });
+        let start = Instant::now();
+
+        send(&mut limiter, start, &outcome(1, 1, 1));
+        assert_eq!(limiter.check(start, U256::zero()), None);
+        let later = start + Duration::from_secs(30);
+        send(&mut limiter, later, &outcome(2, 1, 1));
+        assert_eq!(
+            limiter.check(later, U256::zero()),
+            Some(HoldReason::TxRateLimited)
+        );
+        assert_eq!(
+            limiter.reopens_at(later, &HoldReason::TxRateLimited),
+            start + Duration::from_secs(60)
+        );
+        assert_eq!(
+            limiter.check(start + Duration::from_secs(60), U256::zero()),
+            None
+        );
+    }
+}
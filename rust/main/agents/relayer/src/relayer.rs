@@ -2,6 +2,7 @@ use std::{
     collections::{HashMap, HashSet},
     fmt::{Debug, Formatter},
     sync::Arc,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -37,15 +38,23 @@ use hyperlane_operation_verifier::ApplicationOperationVerifier;
 use crate::{
     merkle_tree::builder::MerkleTreeBuilder,
     msg::{
-        blacklist::AddressBlacklist,
-        gas_payment::GasPaymentEnforcer,
+        admin_overrides::AdminOverrides,
+        blacklist::{AddressBlacklist, BlacklistSource},
+        gas_payment::{
+            accounting::{GasAccountingMetrics, GasAccountingReporter},
+            GasPaymentEnforcer,
+        },
+        lifecycle_recorder::LifecycleRecorder,
         metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
         op_submitter::{SerialSubmitter, SerialSubmitterMetrics},
         pending_message::{MessageContext, MessageSubmissionMetrics},
         processor::{MessageProcessor, MessageProcessorMetrics},
     },
     server::{self as relayer_server},
-    settings::{matching_list::MatchingList, RelayerSettings},
+    settings::{
+        matching_list::MatchingList, AppContextSchedulingConf, RelayerSettings,
+        SubmissionLimitsConf,
+    },
 };
 use crate::{
     merkle_tree::processor::{MerkleTreeProcessor, MerkleTreeProcessorMetrics},
@@ -81,11 +90,19 @@ pub struct Relayer {
     message_whitelist: Arc<MatchingList>,
     message_blacklist: Arc<MatchingList>,
     address_blacklist: Arc<AddressBlacklist>,
+    address_blacklist_source: Option<BlacklistSource>,
+    address_blacklist_refresh_interval: Duration,
     transaction_gas_limit: Option<U256>,
     skip_transaction_gas_limit_for: HashSet<u32>,
     allow_local_checkpoint_syncers: bool,
     metric_app_contexts: Vec<(MatchingList, String)>,
+    app_context_scheduling: HashMap<String, AppContextSchedulingConf>,
     max_retries: u32,
+    admin_api_key: Option<String>,
+    admin_overrides: Arc<AdminOverrides>,
+    submission_limits: HashMap<HyperlaneDomain, SubmissionLimitsConf>,
+    gas_accounting_reporter: Option<GasAccountingReporter>,
+    gas_accounting_interval: Duration,
     core_metrics: Arc<CoreMetrics>,
     // TODO: decide whether to consolidate `agent_metrics` and `chain_metrics` into a single struct
     // or move them in `core_metrics`, like the validator metrics
@@ -139,6 +156,19 @@ impl BaseAgent for Relayer {
             .iter()
             .map(|origin| (origin.clone(), HyperlaneRocksDB::new(origin, db.clone())))
             .collect::<HashMap<_, _>>();
+        let admin_overrides = Arc::new(AdminOverrides::load(dbs.values().cloned().collect())?);
+        let gas_accounting_reporter = GasAccountingReporter::new(
+            dbs.iter()
+                .map(|(origin, db)| (origin.id(), db.clone()))
+                .collect(),
+            settings.metric_app_contexts.clone(),
+            settings
+                .chains
+                .values()
+                .map(|chain| (chain.domain.id(), chain.domain.name().to_owned()))
+                .collect(),
+            GasAccountingMetrics::new(&core_metrics)?,
+        );
 
         let application_operation_verifiers =
             Self::build_application_operation_verifiers(&settings, &core_metrics, &chain_metrics)
@@ -198,7 +228,15 @@ impl BaseAgent for Relayer {
 
         let message_whitelist = Arc::new(settings.whitelist);
         let message_blacklist = Arc::new(settings.blacklist);
-        let address_blacklist = Arc::new(AddressBlacklist::new(settings.address_blacklist));
+        let address_blacklist = Arc::new(
+            AddressBlacklist::new(settings.address_blacklist).with_metrics(
+                core_metrics.new_int_counter(
+                    "address_blacklist_hits",
+                    "Number of messages skipped because they involve a blacklisted address",
+                    &["entry", "field"],
+                )?,
+            ),
+        );
         let skip_transaction_gas_limit_for = settings.skip_transaction_gas_limit_for;
         let transaction_gas_limit = settings.transaction_gas_limit;
 
@@ -206,6 +244,7 @@ impl BaseAgent for Relayer {
             %message_whitelist,
             %message_blacklist,
             ?address_blacklist,
+            address_blacklist_source = ?settings.address_blacklist_source,
             ?transaction_gas_limit,
             ?skip_transaction_gas_limit_for,
             "Whitelist configuration"
@@ -274,6 +313,7 @@ impl BaseAgent for Relayer {
                     ),
                 );
 
+                let origin_db = Arc::new(dbs.get(origin).unwrap().clone());
                 msg_ctxs.insert(
                     ContextKey {
                         origin: origin.id(),
@@ -281,12 +321,13 @@ impl BaseAgent for Relayer {
                     },
                     Arc::new(MessageContext {
                         destination_mailbox: dest_mailbox.clone(),
-                        origin_db: Arc::new(dbs.get(origin).unwrap().clone()),
+                        origin_db: origin_db.clone(),
                         metadata_builder: Arc::new(metadata_builder),
                         origin_gas_payment_enforcer: gas_payment_enforcers[origin].clone(),
                         transaction_gas_limit,
                         metrics: MessageSubmissionMetrics::new(&core_metrics, origin, destination),
                         application_operation_verifier: application_operation_verifier.cloned(),
+                        lifecycle_recorder: Arc::new(LifecycleRecorder::new(origin_db)),
                     }),
                 );
             }
@@ -305,11 +346,19 @@ impl BaseAgent for Relayer {
             message_whitelist,
             message_blacklist,
             address_blacklist,
+            address_blacklist_source: settings.address_blacklist_source,
+            address_blacklist_refresh_interval: settings.address_blacklist_refresh_interval,
             transaction_gas_limit,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers: settings.allow_local_checkpoint_syncers,
             metric_app_contexts: settings.metric_app_contexts,
+            app_context_scheduling: settings.app_context_scheduling,
             max_retries: settings.max_retries,
+            admin_api_key: settings.admin_api_key,
+            admin_overrides,
+            submission_limits: settings.submission_limits,
+            gas_accounting_reporter: Some(gas_accounting_reporter),
+            gas_accounting_interval: settings.gas_accounting_interval,
             core_metrics,
             agent_metrics,
             chain_metrics,
@@ -336,6 +385,7 @@ impl BaseAgent for Relayer {
             tasks.push(console_server.instrument(info_span!("Tokio console server")));
         }
         let sender = BroadcastSender::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
+        let admin_sender = BroadcastSender::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
         // send channels by destination chain
         let mut send_channels = HashMap::with_capacity(self.destination_chains.len());
         let mut prep_queues = HashMap::with_capacity(self.destination_chains.len());
@@ -346,6 +396,13 @@ impl BaseAgent for Relayer {
                 dest_domain.clone(),
                 receive_channel,
                 &sender,
+                &admin_sender,
+                self.admin_overrides.clone(),
+                self.submission_limits
+                    .get(dest_domain)
+                    .cloned()
+                    .unwrap_or_default(),
+                self.app_context_scheduling.clone(),
                 SerialSubmitterMetrics::new(&self.core.metrics, dest_domain),
                 // Default to submitting one message at a time if there is no batch config
                 self.core.settings.chains[dest_domain.name()]
@@ -401,11 +458,39 @@ impl BaseAgent for Relayer {
                 .await,
             );
         }
+        for msg_ctx in self.msg_ctxs.values() {
+            tasks.push(msg_ctx.lifecycle_recorder.spawn());
+        }
+        if let Some(source) = self.address_blacklist_source.clone() {
+            tasks.push(
+                self.address_blacklist
+                    .clone()
+                    .spawn_reloader(source, self.address_blacklist_refresh_interval),
+            );
+        }
+
         // run server
-        let custom_routes = relayer_server::Server::new(self.destination_chains.len())
-            .with_op_retry(sender.clone())
+        let mut relayer_api = relayer_server::Server::new(self.destination_chains.len())
+            .with_op_retry(sender.clone(), self.admin_overrides.clone())
             .with_message_queue(prep_queues)
-            .routes();
+            .with_operation_history(
+                self.dbs
+                    .iter()
+                    .map(|(domain, db)| (domain.id(), db.clone()))
+                    .collect(),
+            );
+        if let Some(api_key) = self.admin_api_key.clone() {
+            relayer_api = relayer_api.with_operation_admin(
+                admin_sender.clone(),
+                api_key,
+                self.admin_overrides.clone(),
+            );
+        }
+        if let Some(reporter) = self.gas_accounting_reporter.take() {
+            relayer_api = relayer_api.with_gas_accounting(reporter.report());
+            tasks.push(reporter.spawn(self.gas_accounting_interval));
+        }
+        let custom_routes = relayer_api.routes();
 
         let server = self
             .core
@@ -604,6 +689,7 @@ impl Relayer {
             self.message_whitelist.clone(),
             self.message_blacklist.clone(),
             self.address_blacklist.clone(),
+            self.admin_overrides.clone(),
             metrics,
             send_channels,
             destination_ctxs,
@@ -738,6 +824,7 @@ mod test {
     use std::{
         collections::{HashMap, HashSet},
         path::PathBuf,
+        time::Duration,
     };
 
     use crate::settings::{matching_list::MatchingList, RelayerSettings};
@@ -851,11 +938,17 @@ mod test {
             whitelist: MatchingList::default(),
             blacklist: MatchingList::default(),
             address_blacklist: Vec::new(),
+            address_blacklist_source: None,
+            address_blacklist_refresh_interval: Duration::from_secs(300),
             transaction_gas_limit: None,
             skip_transaction_gas_limit_for: HashSet::new(),
             allow_local_checkpoint_syncers: true,
             metric_app_contexts: Vec::new(),
+            app_context_scheduling: HashMap::new(),
             max_retries: 1,
+            admin_api_key: None,
+            submission_limits: HashMap::new(),
+            gas_accounting_interval: Duration::from_secs(300),
         }
     }
 
@@ -0,0 +1,132 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing, Json, Router,
+};
+use derive_new::new;
+use serde::Deserialize;
+
+use crate::msg::gas_payment::accounting::SharedGasAccountingReport;
+
+const GAS_ACCOUNTING_API_BASE: &str = "/gas_accounting";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GasAccountingFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct GasAccountingRequest {
+    #[serde(default)]
+    format: GasAccountingFormat,
+}
+
+#[derive(new, Clone)]
+pub struct GasAccountingApi {
+    report: SharedGasAccountingReport,
+}
+
+async fn gas_accounting(
+    State(report): State<SharedGasAccountingReport>,
+    Query(request): Query<GasAccountingRequest>,
+) -> Response {
+    let Some(report) = report.read().expect("gas accounting lock poisoned").clone() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "The gas accounting report has not been built yet",
+        )
+            .into_response();
+    };
+    match request.format {
+        GasAccountingFormat::Json => Json(report).into_response(),
+        GasAccountingFormat::Csv => {
+            ([(header::CONTENT_TYPE, "text/csv")], report.to_csv()).into_response()
+        }
+    }
+}
+
+impl GasAccountingApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(gas_accounting))
+            .with_state(self.report.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (GAS_ACCOUNTING_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, RwLock},
+    };
+
+    use hyperlane_core::U256;
+
+    use super::*;
+    use crate::msg::gas_payment::accounting::{GasAccountingEntry, GasAccountingReport};
+
+    fn setup_test_server(report: SharedGasAccountingReport) -> SocketAddr {
+        let api = GasAccountingApi::new(report);
+        let (path, router) = api.get_route();
+        let app = Router::new().nest(path, router);
+
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_serves_report_as_json_and_csv() {
+        let report = GasAccountingReport {
+            generated_at: 1700000000,
+            entries: vec![GasAccountingEntry {
+                origin_domain: 1,
+                destination_domain: 2,
+                app_context: Some("app".to_owned()),
+                message_count: 1,
+                paid_message_count: 1,
+                total_payment: U256::from(100),
+                total_gas_amount: U256::from(1000),
+                ..Default::default()
+            }],
+        };
+        let addr = setup_test_server(Arc::new(RwLock::new(Some(report.clone()))));
+
+        let response = reqwest::get(format!("http://{}{}", addr, GAS_ACCOUNTING_API_BASE))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let resp: GasAccountingReport = response.json().await.unwrap();
+        assert_eq!(resp, report);
+
+        let response = reqwest::get(format!(
+            "http://{}{}?format=csv",
+            addr, GAS_ACCOUNTING_API_BASE
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(response.text().await.unwrap(), report.to_csv());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_before_first_report() {
+        let addr = setup_test_server(Default::default());
+
+        let response = reqwest::get(format!("http://{}{}", addr, GAS_ACCOUNTING_API_BASE))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
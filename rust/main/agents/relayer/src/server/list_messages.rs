@@ -81,14 +81,11 @@ impl ListOperationsApi {
 
 #[cfg(test)]
 mod tests {
-    use crate::msg::op_queue::{
-        test::{dummy_metrics_and_label, MockPendingOperation},
-        OpQueue,
-    };
+    use crate::msg::op_queue::{test::dummy_metrics_and_label, OpQueue};
 
     use super::*;
     use axum::http::StatusCode;
-    use hyperlane_core::KnownHyperlaneDomain;
+    use hyperlane_core::{test_utils::MockPendingOperation, KnownHyperlaneDomain};
     use std::{cmp::Reverse, net::SocketAddr, sync::Arc};
     use tokio::sync::{self, Mutex};
 
@@ -1,7 +1,16 @@
-use crate::{msg::op_submitter::SUBMITTER_QUEUE_COUNT, settings::matching_list::MatchingList};
-use axum::{extract::State, routing, Json, Router};
+use std::sync::Arc;
+
+use crate::{
+    msg::{
+        admin_overrides::{AdminOverrides, OverrideAction},
+        op_submitter::SUBMITTER_QUEUE_COUNT,
+    },
+    settings::matching_list::MatchingList,
+};
+use axum::{extract::State, http::StatusCode, routing, Json, Router};
 use derive_new::new;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tokio::sync::{broadcast::Sender, mpsc};
 
 const MESSAGE_RETRY_API_BASE: &str = "/message_retry";
@@ -10,6 +19,9 @@ const MESSAGE_RETRY_API_BASE: &str = "/message_retry";
 pub struct MessageRetryApi {
     retry_request_transmitter: Sender<MessageRetryRequest>,
     destination_chains: usize,
+    /// Retries are persisted so that matching messages are retried after a restart too,
+    /// until they expire after `RETRY_OVERRIDE_TTL`
+    overrides: Arc<AdminOverrides>,
 }
 
 #[derive(Clone, Debug)]
@@ -35,16 +47,37 @@ pub struct MessageRetryResponse {
     pub evaluated: usize,
     /// how many of the pending operations matched the retry request pattern
     pub matched: u64,
+    /// ID of the persisted retry override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_id: Option<String>,
 }
 
 async fn retry_message(
     State(state): State<MessageRetryApi>,
-    Json(retry_req_payload): Json<MatchingList>,
-) -> Result<Json<MessageRetryResponse>, String> {
+    Json(pattern): Json<Value>,
+) -> Result<Json<MessageRetryResponse>, (StatusCode, String)> {
+    let retry_req_payload: MatchingList =
+        serde_json::from_value(pattern.clone()).map_err(|err| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Invalid matching list: {err}"),
+            )
+        })?;
     let uuid = uuid::Uuid::new_v4();
     let uuid_string = uuid.to_string();
 
     tracing::debug!(?retry_req_payload);
+    // Persisted before the request is sent, so messages that are rehydrated
+    // concurrently are still retried
+    let retry_override = state
+        .overrides
+        .add(OverrideAction::Retry, pattern)
+        .map_err(|err| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to persist retry request: {err}"),
+            )
+        })?;
     tracing::debug!(uuid = uuid_string, "Sending message retry request");
 
     // Create a channel that can hold each chain's SerialSubmitter
@@ -62,13 +95,17 @@ async fn retry_message(
         .map_err(|err| {
             // Technically it's bad practice to print the error message to the user, but
             // this endpoint is for debugging purposes only.
-            format!("Failed to send retry request to the queue: {}", err)
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to send retry request to the queue: {}", err),
+            )
         })?;
 
     let mut resp = MessageRetryResponse {
         uuid: uuid_string,
         evaluated: 0,
         matched: 0,
+        override_id: Some(retry_override.id),
     };
 
     // Wait for responses from relayer
@@ -100,11 +137,11 @@ impl MessageRetryApi {
 
 #[cfg(test)]
 mod tests {
-    use crate::{msg::op_queue::test::MockPendingOperation, server::ENDPOINT_MESSAGES_QUEUE_SIZE};
+    use crate::server::ENDPOINT_MESSAGES_QUEUE_SIZE;
 
     use super::*;
     use axum::http::StatusCode;
-    use hyperlane_core::{HyperlaneMessage, QueueOperation};
+    use hyperlane_core::{test_utils::MockPendingOperation, HyperlaneMessage, QueueOperation};
     use serde::de::DeserializeOwned;
     use serde_json::json;
     use std::net::SocketAddr;
@@ -119,7 +156,7 @@ mod tests {
     fn setup_test_server() -> TestServerSetup {
         let broadcast_tx = Sender::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
 
-        let message_retry_api = MessageRetryApi::new(broadcast_tx.clone(), 10);
+        let message_retry_api = MessageRetryApi::new(broadcast_tx.clone(), 10, Default::default());
         let (path, retry_router) = message_retry_api.get_route();
 
         let app = Router::new().nest(path, retry_router);
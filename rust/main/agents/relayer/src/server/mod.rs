@@ -1,30 +1,51 @@
 use axum::Router;
 use derive_new::new;
-use std::collections::HashMap;
+use hyperlane_base::db::HyperlaneRocksDB;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::broadcast::Sender;
 
-use crate::msg::op_queue::OperationPriorityQueue;
+use crate::msg::{
+    admin_overrides::AdminOverrides, gas_payment::accounting::SharedGasAccountingReport,
+    op_queue::OperationPriorityQueue,
+};
 
 pub const ENDPOINT_MESSAGES_QUEUE_SIZE: usize = 100;
 
+pub use gas_accounting::*;
 pub use list_messages::*;
 pub use message_retry::*;
+pub use operation_admin::*;
+pub use operation_history::*;
 
+mod gas_accounting;
 mod list_messages;
 mod message_retry;
+mod operation_admin;
+mod operation_history;
 
 #[derive(new)]
 pub struct Server {
     destination_chains: usize,
     #[new(default)]
-    retry_transmitter: Option<Sender<MessageRetryRequest>>,
+    retry_transmitter: Option<(Sender<MessageRetryRequest>, Arc<AdminOverrides>)>,
     #[new(default)]
     op_queues: Option<HashMap<u32, OperationPriorityQueue>>,
+    #[new(default)]
+    admin: Option<(Sender<OperationAdminRequest>, String, Arc<AdminOverrides>)>,
+    #[new(default)]
+    origin_dbs: Option<HashMap<u32, HyperlaneRocksDB>>,
+    #[new(default)]
+    gas_accounting_report: Option<SharedGasAccountingReport>,
 }
 
 impl Server {
-    pub fn with_op_retry(mut self, transmitter: Sender<MessageRetryRequest>) -> Self {
-        self.retry_transmitter = Some(transmitter);
+    /// Enables the message retry endpoint. Retries are persisted to `overrides`.
+    pub fn with_op_retry(
+        mut self,
+        transmitter: Sender<MessageRetryRequest>,
+        overrides: Arc<AdminOverrides>,
+    ) -> Self {
+        self.retry_transmitter = Some((transmitter, overrides));
         self
     }
 
@@ -33,16 +54,51 @@ impl Server {
         self
     }
 
+    /// Enables the operation admin endpoints, authenticated with `api_key`.
+    /// Interventions are persisted to `overrides`.
+    pub fn with_operation_admin(
+        mut self,
+        transmitter: Sender<OperationAdminRequest>,
+        api_key: String,
+        overrides: Arc<AdminOverrides>,
+    ) -> Self {
+        self.admin = Some((transmitter, api_key, overrides));
+        self
+    }
+
+    /// Enables the operation history endpoint, reading from the databases of each origin
+    pub fn with_operation_history(mut self, origin_dbs: HashMap<u32, HyperlaneRocksDB>) -> Self {
+        self.origin_dbs = Some(origin_dbs);
+        self
+    }
+
+    /// Enables the gas accounting endpoint, serving the latest `report`
+    pub fn with_gas_accounting(mut self, report: SharedGasAccountingReport) -> Self {
+        self.gas_accounting_report = Some(report);
+        self
+    }
+
     /// Returns a vector of agent-specific endpoint routes to be served.
     /// Can be extended with additional routes and feature flags to enable/disable individually.
     pub fn routes(self) -> Vec<(&'static str, Router)> {
         let mut routes = vec![];
-        if let Some(tx) = self.retry_transmitter {
-            routes.push(MessageRetryApi::new(tx, self.destination_chains).get_route());
+        if let Some((tx, overrides)) = self.retry_transmitter {
+            routes.push(MessageRetryApi::new(tx, self.destination_chains, overrides).get_route());
         }
         if let Some(op_queues) = self.op_queues {
             routes.push(ListOperationsApi::new(op_queues).get_route());
         }
+        if let Some((tx, api_key, overrides)) = self.admin {
+            routes.push(
+                OperationAdminApi::new(tx, self.destination_chains, api_key, overrides).get_route(),
+            );
+        }
+        if let Some(origin_dbs) = self.origin_dbs {
+            routes.push(OperationHistoryApi::new(origin_dbs).get_route());
+        }
+        if let Some(report) = self.gas_accounting_report {
+            routes.push(GasAccountingApi::new(report).get_route());
+        }
 
         routes
     }
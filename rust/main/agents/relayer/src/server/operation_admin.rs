@@ -0,0 +1,565 @@
+use std::sync::Arc;
+
+use crate::{
+    msg::{
+        admin_overrides::{AdminOverride, AdminOverrides, OverrideAction},
+        op_submitter::SUBMITTER_QUEUE_COUNT,
+    },
+    settings::matching_list::MatchingList,
+};
+use axum::{
+    extract::{Path, State},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    routing, Json, Router,
+};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::{broadcast::Sender, mpsc};
+
+const OPERATION_ADMIN_API_BASE: &str = "/operations";
+
+/// The queues of a `SerialSubmitter`
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmitterQueue {
+    Prepare,
+    Submit,
+    Confirm,
+}
+
+/// An action an operator can apply to the operations matching a pattern
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum OperationAdminAction {
+    /// Stop popping matching operations from their queue until resumed
+    Pause,
+    /// Undo a previous pause
+    Resume,
+    /// Remove matching operations from the submitter entirely
+    Drop,
+    /// Reset the backoff of matching operations so they are retried right away
+    Retry,
+    /// Move matching operations into another queue, resetting their backoff
+    Move { to: SubmitterQueue },
+}
+
+#[derive(Clone, Debug, new)]
+pub struct OperationAdminApi {
+    admin_request_transmitter: Sender<OperationAdminRequest>,
+    destination_chains: usize,
+    api_key: String,
+    overrides: Arc<AdminOverrides>,
+}
+
+#[derive(Clone, Debug)]
+pub struct OperationAdminRequest {
+    pub uuid: String,
+    pub action: OperationAdminAction,
+    pub pattern: MatchingList,
+    pub transmitter: mpsc::Sender<OperationAdminQueueResponse>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, new)]
+pub struct OperationAdminQueueResponse {
+    /// name of the destination chain the queue submits to
+    pub destination: String,
+    /// label of the queue, e.g. `prepare_queue`
+    pub queue: String,
+    /// how many pending operations were evaluated
+    pub evaluated: usize,
+    /// how many of the pending operations matched the request pattern
+    pub matched: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct OperationAdminResponse {
+    /// ID of the admin request
+    pub uuid: String,
+    pub action: OperationAdminAction,
+    /// how many pending operations were evaluated across all queues
+    pub evaluated: usize,
+    /// how many of the pending operations matched the request pattern across all queues
+    pub matched: u64,
+    /// the per-queue breakdown
+    pub queues: Vec<OperationAdminQueueResponse>,
+    /// ID of the override persisted for this request, which re-applies it after restarts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_id: Option<String>,
+    /// IDs of the persisted overrides this request revoked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revoked_overrides: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MoveOperationsPayload {
+    to: SubmitterQueue,
+    pattern: Value,
+}
+
+type ApiError = (StatusCode, String);
+type AdminResult = Result<Json<OperationAdminResponse>, ApiError>;
+
+fn internal_error(err: eyre::Report) -> ApiError {
+    (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+}
+
+async fn pause_operations(
+    State(state): State<OperationAdminApi>,
+    headers: HeaderMap,
+    Json(pattern): Json<Value>,
+) -> AdminResult {
+    state
+        .handle(&headers, OperationAdminAction::Pause, pattern)
+        .await
+}
+
+async fn resume_operations(
+    State(state): State<OperationAdminApi>,
+    headers: HeaderMap,
+    Json(pattern): Json<Value>,
+) -> AdminResult {
+    state
+        .handle(&headers, OperationAdminAction::Resume, pattern)
+        .await
+}
+
+async fn drop_operations(
+    State(state): State<OperationAdminApi>,
+    headers: HeaderMap,
+    Json(pattern): Json<Value>,
+) -> AdminResult {
+    state
+        .handle(&headers, OperationAdminAction::Drop, pattern)
+        .await
+}
+
+async fn retry_operations(
+    State(state): State<OperationAdminApi>,
+    headers: HeaderMap,
+    Json(pattern): Json<Value>,
+) -> AdminResult {
+    state
+        .handle(&headers, OperationAdminAction::Retry, pattern)
+        .await
+}
+
+async fn list_overrides(
+    State(state): State<OperationAdminApi>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<AdminOverride>>, ApiError> {
+    state.authorize(&headers)?;
+    Ok(Json(state.overrides.list()))
+}
+
+async fn revoke_override(
+    State(state): State<OperationAdminApi>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<AdminOverride>, ApiError> {
+    state.authorize(&headers)?;
+    let revoked = state
+        .overrides
+        .revoke(&id)
+        .map_err(internal_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("No override with id {id}")))?;
+    tracing::info!(id, action = ?revoked.action, "Revoked admin override");
+
+    // Operations held back by a revoked pause are released right away
+    if revoked.action == OverrideAction::Pause {
+        state
+            .dispatch(
+                OperationAdminAction::Resume,
+                revoked.matching_list().clone(),
+            )
+            .await?;
+    }
+    Ok(Json(revoked))
+}
+
+async fn move_operations(
+    State(state): State<OperationAdminApi>,
+    headers: HeaderMap,
+    Json(payload): Json<MoveOperationsPayload>,
+) -> AdminResult {
+    state
+        .handle(
+            &headers,
+            OperationAdminAction::Move { to: payload.to },
+            payload.pattern,
+        )
+        .await
+}
+
+impl OperationAdminApi {
+    /// Checks the request carries `Authorization: Bearer <api key>`
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), (StatusCode, String)> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        match token {
+            Some(token) if constant_time_eq(token.as_bytes(), self.api_key.as_bytes()) => Ok(()),
+            _ => Err((
+                StatusCode::UNAUTHORIZED,
+                "Missing or invalid API key".to_owned(),
+            )),
+        }
+    }
+
+    async fn handle(
+        &self,
+        headers: &HeaderMap,
+        action: OperationAdminAction,
+        pattern: Value,
+    ) -> AdminResult {
+        self.authorize(headers)?;
+        let matching_list: MatchingList =
+            serde_json::from_value(pattern.clone()).map_err(|err| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid matching list: {err}"),
+                )
+            })?;
+
+        // Persist the intervention before dispatching it, so operations entering a
+        // queue while the request is in flight already see it
+        let mut override_id = None;
+        let mut revoked_overrides = vec![];
+        let persisted = match action {
+            OperationAdminAction::Pause => Some(OverrideAction::Pause),
+            OperationAdminAction::Drop => Some(OverrideAction::Drop),
+            OperationAdminAction::Retry => Some(OverrideAction::Retry),
+            OperationAdminAction::Resume => {
+                revoked_overrides = self
+                    .overrides
+                    .revoke_pattern(OverrideAction::Pause, &pattern)
+                    .map_err(internal_error)?
+                    .into_iter()
+                    .map(|o| o.id)
+                    .collect();
+                None
+            }
+            OperationAdminAction::Move { .. } => None,
+        };
+        if let Some(override_action) = persisted {
+            let new_override = self
+                .overrides
+                .add(override_action, pattern)
+                .map_err(internal_error)?;
+            override_id = Some(new_override.id);
+        }
+
+        let mut resp = self.dispatch(action, matching_list).await?;
+        resp.override_id = override_id;
+        resp.revoked_overrides = revoked_overrides;
+        Ok(Json(resp))
+    }
+
+    /// Sends the request to every submitter and collects the per-queue responses
+    async fn dispatch(
+        &self,
+        action: OperationAdminAction,
+        pattern: MatchingList,
+    ) -> Result<OperationAdminResponse, ApiError> {
+        let uuid = uuid::Uuid::new_v4().to_string();
+        tracing::info!(uuid, ?action, ?pattern, "Sending operation admin request");
+
+        // Each chain's SerialSubmitter responds once per queue
+        let (transmitter, mut receiver) =
+            mpsc::channel(SUBMITTER_QUEUE_COUNT * self.destination_chains);
+        self.admin_request_transmitter
+            .send(OperationAdminRequest {
+                uuid: uuid.clone(),
+                action: action.clone(),
+                pattern,
+                transmitter,
+            })
+            .map_err(|err| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to send admin request to the queues: {}", err),
+                )
+            })?;
+
+        let mut resp = OperationAdminResponse {
+            uuid,
+            action,
+            evaluated: 0,
+            matched: 0,
+            queues: vec![],
+            override_id: None,
+            revoked_overrides: vec![],
+        };
+        while let Some(queue_resp) = receiver.recv().await {
+            resp.evaluated += queue_resp.evaluated;
+            resp.matched += queue_resp.matched;
+            resp.queues.push(queue_resp);
+        }
+        tracing::info!(
+            uuid = resp.uuid,
+            evaluated = resp.evaluated,
+            matched = resp.matched,
+            "Operation admin request processed"
+        );
+
+        Ok(resp)
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/pause", routing::post(pause_operations))
+            .route("/resume", routing::post(resume_operations))
+            .route("/drop", routing::post(drop_operations))
+            .route("/move", routing::post(move_operations))
+            .route("/retry", routing::post(retry_operations))
+            .route("/overrides", routing::get(list_overrides))
+            .route("/overrides/:id", routing::delete(revoke_override))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (OPERATION_ADMIN_API_BASE, self.router())
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::ENDPOINT_MESSAGES_QUEUE_SIZE;
+
+    use super::*;
+    use hyperlane_core::H256;
+    use serde_json::json;
+    use std::net::SocketAddr;
+    use tokio::sync::broadcast::Receiver;
+
+    const API_KEY: &str = "secret";
+
+    fn setup_test_server() -> (SocketAddr, Receiver<OperationAdminRequest>) {
+        setup_test_server_with_overrides(Default::default())
+    }
+
+    fn setup_test_server_with_overrides(
+        overrides: Arc<AdminOverrides>,
+    ) -> (SocketAddr, Receiver<OperationAdminRequest>) {
+        let broadcast_tx = Sender::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
+
+        let api = OperationAdminApi::new(broadcast_tx.clone(), 1, API_KEY.to_owned(), overrides);
+        let (path, router) = api.get_route();
+        let app = Router::new().nest(path, router);
+
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        (addr, broadcast_tx.subscribe())
+    }
+
+    async fn respond_per_queue(mut rx: Receiver<OperationAdminRequest>, matched: [u64; 3]) {
+        let req = rx.recv().await.unwrap();
+        for (queue, matched) in ["prepare_queue", "submit_queue", "confirm_queue"]
+            .into_iter()
+            .zip(matched)
+        {
+            req.transmitter
+                .send(OperationAdminQueueResponse::new(
+                    "test".to_owned(),
+                    queue.to_owned(),
+                    5,
+                    matched,
+                ))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_or_wrong_api_key() {
+        let (addr, _rx) = setup_test_server();
+        let client = reqwest::Client::new();
+        let url = format!("http://{}{}/pause", addr, OPERATION_ADMIN_API_BASE);
+
+        let response = client
+            .post(&url)
+            .json(&json!([{ "destinationdomain": 42 }]))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = client
+            .post(&url)
+            .bearer_auth("wrong")
+            .json(&json!([{ "destinationdomain": 42 }]))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_pause_returns_per_queue_counts() {
+        let (addr, rx) = setup_test_server();
+        let client = reqwest::Client::new();
+
+        let respond_task = respond_per_queue(rx, [2, 1, 0]);
+        let response = client
+            .post(format!("http://{}{}/pause", addr, OPERATION_ADMIN_API_BASE))
+            .bearer_auth(API_KEY)
+            .json(&json!([{ "destinationdomain": 42 }]))
+            .send();
+        let (_, response) = tokio::join!(respond_task, response);
+
+        let response = response.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let resp: OperationAdminResponse = response.json().await.unwrap();
+        assert_eq!(resp.action, OperationAdminAction::Pause);
+        assert_eq!(resp.evaluated, 15);
+        assert_eq!(resp.matched, 3);
+        assert_eq!(
+            resp.queues.iter().map(|q| q.matched).collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_move_forwards_target_queue() {
+        let (addr, mut rx) = setup_test_server();
+        let client = reqwest::Client::new();
+        let message_id = H256::random();
+
+        let respond_task = async move {
+            let req = rx.recv().await.unwrap();
+            assert_eq!(
+                req.action,
+                OperationAdminAction::Move {
+                    to: SubmitterQueue::Prepare
+                }
+            );
+            req.transmitter
+                .send(OperationAdminQueueResponse::new(
+                    "test".to_owned(),
+                    "confirm_queue".to_owned(),
+                    1,
+                    1,
+                ))
+                .await
+                .unwrap();
+        };
+        let response = client
+            .post(format!("http://{}{}/move", addr, OPERATION_ADMIN_API_BASE))
+            .bearer_auth(API_KEY)
+            .json(&json!({
+                "to": "prepare",
+                "pattern": [{ "messageid": message_id }]
+            }))
+            .send();
+        let (_, response) = tokio::join!(respond_task, response);
+
+        let resp: OperationAdminResponse = response.unwrap().json().await.unwrap();
+        assert_eq!(resp.matched, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_pattern() {
+        let (addr, _rx) = setup_test_server();
+        let response = reqwest::Client::new()
+            .post(format!("http://{}{}/drop", addr, OPERATION_ADMIN_API_BASE))
+            .bearer_auth(API_KEY)
+            .json(&json!({ "not": "a list" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_pause_persists_override_until_resumed() {
+        let overrides = Arc::new(AdminOverrides::default());
+        let (addr, mut rx) = setup_test_server_with_overrides(overrides.clone());
+        let client = reqwest::Client::new();
+        let pattern = json!([{ "destinationdomain": 42 }]);
+
+        // Drop the response transmitters straight away; no queues match
+        let respond_task = async move {
+            for _ in 0..2 {
+                rx.recv().await.unwrap();
+            }
+        };
+        let requests = async {
+            let paused: OperationAdminResponse = client
+                .post(format!("http://{}{}/pause", addr, OPERATION_ADMIN_API_BASE))
+                .bearer_auth(API_KEY)
+                .json(&pattern)
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            let listed: Vec<AdminOverride> = client
+                .get(format!(
+                    "http://{}{}/overrides",
+                    addr, OPERATION_ADMIN_API_BASE
+                ))
+                .bearer_auth(API_KEY)
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            let resumed: OperationAdminResponse = client
+                .post(format!(
+                    "http://{}{}/resume",
+                    addr, OPERATION_ADMIN_API_BASE
+                ))
+                .bearer_auth(API_KEY)
+                .json(&pattern)
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            (paused, listed, resumed)
+        };
+        let (_, (paused, listed, resumed)) = tokio::join!(respond_task, requests);
+
+        let override_id = paused.override_id.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, override_id);
+        assert_eq!(listed[0].action, OverrideAction::Pause);
+        assert_eq!(resumed.override_id, None);
+        assert_eq!(resumed.revoked_overrides, vec![override_id]);
+        assert!(overrides.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_unknown_override() {
+        let (addr, _rx) = setup_test_server();
+        let response = reqwest::Client::new()
+            .delete(format!(
+                "http://{}{}/overrides/unknown",
+                addr, OPERATION_ADMIN_API_BASE
+            ))
+            .bearer_auth(API_KEY)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing, Json, Router,
+};
+use derive_new::new;
+use hyperlane_base::db::{HyperlaneDb, HyperlaneRocksDB};
+use hyperlane_core::{OperationLifecycleEntry, PendingOperationStatus, H256};
+use serde::{Deserialize, Serialize};
+
+const OPERATION_HISTORY_API_BASE: &str = "/operations";
+
+/// The recorded lifecycle of a message, as stored in its origin's database
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OperationHistoryResponse {
+    pub message_id: H256,
+    pub origin_domain: u32,
+    /// Unset if the message itself is not in the database
+    pub destination_domain: Option<u32>,
+    /// The last persisted status of the operation
+    pub status: Option<PendingOperationStatus>,
+    /// The most recent lifecycle events, oldest first
+    pub events: Vec<OperationLifecycleEntry>,
+}
+
+#[derive(new, Clone)]
+pub struct OperationHistoryApi {
+    /// Databases of each origin, by domain id
+    dbs: HashMap<u32, HyperlaneRocksDB>,
+}
+
+async fn operation_history(
+    State(dbs): State<HashMap<u32, HyperlaneRocksDB>>,
+    Path(message_id): Path<H256>,
+) -> Result<Json<OperationHistoryResponse>, (StatusCode, String)> {
+    let db_error = |err| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read the database: {}", err),
+        )
+    };
+
+    // Message ids are unique across origins, so the first database that knows
+    // about the message holds its history
+    let mut origins: Vec<_> = dbs.keys().copied().collect();
+    origins.sort_unstable();
+    for origin_domain in origins {
+        let db = &dbs[&origin_domain];
+        let log = db
+            .retrieve_operation_lifecycle_by_message_id(&message_id)
+            .map_err(db_error)?;
+        let message = db.retrieve_message_by_id(&message_id).map_err(db_error)?;
+        if log.is_none() && message.is_none() {
+            continue;
+        }
+        let status = db
+            .retrieve_status_by_message_id(&message_id)
+            .map_err(db_error)?;
+        return Ok(Json(OperationHistoryResponse {
+            message_id,
+            origin_domain,
+            destination_domain: message.map(|m| m.destination),
+            status,
+            events: log.map(|log| log.0).unwrap_or_default(),
+        }));
+    }
+
+    Err((
+        StatusCode::NOT_FOUND,
+        format!("No history found for message {:?}", message_id),
+    ))
+}
+
+impl OperationHistoryApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/:message_id", routing::get(operation_history))
+            .with_state(self.dbs.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (OPERATION_HISTORY_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{
+        HyperlaneDomain, HyperlaneMessage, KnownHyperlaneDomain, OperationLifecycleEvent,
+        OperationLifecycleLog, ReprepareReason,
+    };
+    use std::net::SocketAddr;
+
+    fn setup_test_server(dbs: HashMap<u32, HyperlaneRocksDB>) -> SocketAddr {
+        let api = OperationHistoryApi::new(dbs);
+        let (path, router) = api.get_route();
+        let app = Router::new().nest(path, router);
+
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_returns_recorded_lifecycle() {
+        test_utils::run_test_db(|db| async move {
+            let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let message = HyperlaneMessage {
+                origin: origin.id(),
+                destination: KnownHyperlaneDomain::Optimism as u32,
+                ..Default::default()
+            };
+            db.store_message_by_id(&message.id(), &message).unwrap();
+            let status = PendingOperationStatus::Retry(ReprepareReason::GasPaymentNotFound);
+            db.store_status_by_message_id(&message.id(), &status)
+                .unwrap();
+            let mut log = OperationLifecycleLog::default();
+            log.push_bounded(
+                OperationLifecycleEntry::new(OperationLifecycleEvent::GasPolicyDecision {
+                    gas_limit: None,
+                    rejection: Some(ReprepareReason::GasPaymentNotFound),
+                }),
+                10,
+            );
+            db.store_operation_lifecycle_by_message_id(&message.id(), &log)
+                .unwrap();
+
+            let addr = setup_test_server(HashMap::from([(origin.id(), db)]));
+            let response = reqwest::get(format!(
+                "http://{}{}/{:?}",
+                addr,
+                OPERATION_HISTORY_API_BASE,
+                message.id()
+            ))
+            .await
+            .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let resp: OperationHistoryResponse = response.json().await.unwrap();
+            assert_eq!(resp.message_id, message.id());
+            assert_eq!(resp.origin_domain, origin.id());
+            assert_eq!(resp.destination_domain, Some(message.destination));
+            assert_eq!(resp.status, Some(status));
+            assert_eq!(resp.events, log.0);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_unknown_message_is_not_found() {
+        test_utils::run_test_db(|db| async move {
+            let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+            let db = HyperlaneRocksDB::new(&origin, db);
+
+            let addr = setup_test_server(HashMap::from([(origin.id(), db)]));
+            let response = reqwest::get(format!(
+                "http://{}{}/{:?}",
+                addr,
+                OPERATION_HISTORY_API_BASE,
+                H256::random()
+            ))
+            .await
+            .unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        })
+        .await;
+    }
+}
@@ -10,8 +10,10 @@ use std::{
 
 use derive_new::new;
 use hyperlane_core::{
-    config::StrOrInt, utils::hex_or_base58_to_h256, HyperlaneMessage, QueueOperation, H256,
+    config::StrOrInt, utils::hex_or_base58_to_h256, Decode, HyperlaneMessage, QueueOperation, H256,
+    U256,
 };
+use hyperlane_warp_route::TokenMessage;
 use serde::{
     de::{Error, SeqAccess, Visitor},
     Deserialize, Deserializer,
@@ -25,6 +27,10 @@ use serde::{
 /// - wildcard "*"
 /// - single value in decimal or hex (must start with `0x`) format
 /// - list of values in decimal or hex format
+///
+/// Rules may additionally match on the message body, either by raw byte
+/// prefixes or by decoding it as a warp route `TokenMessage` and checking its
+/// recipient and amount.
 #[derive(Debug, Default, Clone)]
 pub struct MatchingList(pub Option<Vec<ListElement>>);
 
@@ -49,6 +55,25 @@ impl<T: PartialEq> Filter<T> {
     }
 }
 
+impl Filter<BodyPrefix> {
+    fn matches_prefix(&self, body: &[u8]) -> bool {
+        match self {
+            Filter::Wildcard => true,
+            Filter::Enumerated(list) => list.iter().any(|prefix| body.starts_with(&prefix.0)),
+        }
+    }
+}
+
+/// Leading bytes of a message body
+#[derive(Clone, PartialEq)]
+pub struct BodyPrefix(pub Vec<u8>);
+
+impl Debug for BodyPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.0))
+    }
+}
+
 impl<T: Debug> Display for Filter<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -196,6 +221,39 @@ impl<'de> Visitor<'de> for FilterVisitor<H256> {
     }
 }
 
+impl<'de> Visitor<'de> for FilterVisitor<BodyPrefix> {
+    type Value = Filter<BodyPrefix>;
+
+    fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "Expecting either a wildcard \"*\", hex bytes string, or list of hex bytes strings"
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(if v == "*" {
+            Self::Value::Wildcard
+        } else {
+            Self::Value::Enumerated(vec![parse_body_prefix(v)?])
+        })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(i) = seq.next_element::<String>()? {
+            values.push(parse_body_prefix(&i)?)
+        }
+        Ok(Self::Value::Enumerated(values))
+    }
+}
+
 impl<'de> Deserialize<'de> for MatchingList {
     fn deserialize<D>(d: D) -> Result<Self, D::Error>
     where
@@ -223,6 +281,15 @@ impl<'de> Deserialize<'de> for Filter<H256> {
     }
 }
 
+impl<'de> Deserialize<'de> for Filter<BodyPrefix> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_any(FilterVisitor::<BodyPrefix>(Default::default()))
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, new)]
 #[serde(tag = "type")]
 pub struct ListElement {
@@ -236,18 +303,60 @@ pub struct ListElement {
     destination_domain: Filter<u32>,
     #[serde(default, rename = "recipientaddress")]
     recipient_address: Filter<H256>,
+    #[serde(default, rename = "bodyprefix")]
+    #[new(default)]
+    body_prefix: Filter<BodyPrefix>,
+    /// Recipient of the transfer when the body is decoded as a `TokenMessage`
+    #[serde(default, rename = "tokenrecipient")]
+    #[new(default)]
+    token_recipient: Filter<H256>,
+    /// Inclusive lower bound of the transferred amount
+    #[serde(default, rename = "minamount", deserialize_with = "deserialize_amount")]
+    #[new(default)]
+    min_amount: Option<U256>,
+    /// Inclusive upper bound of the transferred amount
+    #[serde(default, rename = "maxamount", deserialize_with = "deserialize_amount")]
+    #[new(default)]
+    max_amount: Option<U256>,
+}
+
+impl ListElement {
+    fn has_token_message_predicates(&self) -> bool {
+        self.token_recipient != Filter::Wildcard
+            || self.min_amount.is_some()
+            || self.max_amount.is_some()
+    }
+
+    /// Check the transfer predicates against the body decoded as a `TokenMessage`.
+    /// Bodies that cannot be decoded only match rules without such predicates.
+    fn token_message_matches(&self, body: &[u8]) -> bool {
+        if !self.has_token_message_predicates() {
+            return true;
+        }
+        let Ok(token_message) = TokenMessage::read_from(&mut &body[..]) else {
+            return false;
+        };
+        let amount = token_message.amount();
+        self.token_recipient.matches(&token_message.recipient())
+            && self.min_amount.map_or(true, |min| amount >= min)
+            && self.max_amount.map_or(true, |max| amount <= max)
+    }
 }
 
 impl Display for ListElement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{messageId: {}, originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}}}",
+            "{{messageId: {}, originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}, bodyPrefix: {}, tokenRecipient: {}, minAmount: {:?}, maxAmount: {:?}}}",
             self.message_id,
             self.origin_domain,
             self.sender_address,
             self.destination_domain,
-            self.recipient_address
+            self.recipient_address,
+            self.body_prefix,
+            self.token_recipient,
+            self.min_amount,
+            self.max_amount
         )
     }
 }
@@ -259,6 +368,7 @@ struct MatchInfo<'a> {
     src_addr: &'a H256,
     dst_domain: u32,
     dst_addr: &'a H256,
+    body: &'a [u8],
 }
 
 impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
@@ -269,6 +379,7 @@ impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
             src_addr: &msg.sender,
             dst_domain: msg.destination,
             dst_addr: &msg.recipient,
+            body: &msg.body,
         }
     }
 }
@@ -281,29 +392,30 @@ impl<'a> From<&'a QueueOperation> for MatchInfo<'a> {
             src_addr: op.sender_address(),
             dst_domain: op.destination_domain().id(),
             dst_addr: op.recipient_address(),
+            body: op.body(),
         }
     }
 }
 
 impl MatchingList {
     pub fn with_message_id(message_id: H256) -> Self {
-        Self(Some(vec![ListElement {
-            message_id: Filter::Enumerated(vec![message_id]),
-            origin_domain: Default::default(),
-            sender_address: Default::default(),
-            destination_domain: Default::default(),
-            recipient_address: Default::default(),
-        }]))
+        Self(Some(vec![ListElement::new(
+            Filter::Enumerated(vec![message_id]),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )]))
     }
 
     pub fn with_destination_domain(destination_domain: u32) -> Self {
-        Self(Some(vec![ListElement {
-            message_id: Default::default(),
-            origin_domain: Default::default(),
-            sender_address: Default::default(),
-            destination_domain: Filter::Enumerated(vec![destination_domain]),
-            recipient_address: Default::default(),
-        }]))
+        Self(Some(vec![ListElement::new(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Filter::Enumerated(vec![destination_domain]),
+            Default::default(),
+        )]))
     }
 
     /// Check if a message matches any of the rules.
@@ -336,6 +448,8 @@ fn matches_any_rule<'a>(mut rules: impl Iterator<Item = &'a ListElement>, info:
             && rule.sender_address.matches(info.src_addr)
             && rule.destination_domain.matches(&info.dst_domain)
             && rule.recipient_address.matches(info.dst_addr)
+            && rule.body_prefix.matches_prefix(info.body)
+            && rule.token_message_matches(info.body)
     })
 }
 
@@ -361,11 +475,35 @@ fn parse_addr<E: Error>(addr_str: &str) -> Result<H256, E> {
     hex_or_base58_to_h256(addr_str).map_err(to_serde_err)
 }
 
+fn parse_body_prefix<E: Error>(prefix_str: &str) -> Result<BodyPrefix, E> {
+    let prefix_str = prefix_str.strip_prefix("0x").unwrap_or(prefix_str);
+    hex::decode(prefix_str)
+        .map(BodyPrefix)
+        .map_err(to_serde_err)
+}
+
+/// Amounts are given in decimal or hex (must start with `0x`) format, as
+/// strings so that they are not limited to 64 bits.
+fn deserialize_amount<'de, D>(d: D) -> Result<Option<U256>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let amount = match StrOrInt::deserialize(d)? {
+        StrOrInt::Str(s) => match s.strip_prefix("0x") {
+            Some(hex) => hex.parse::<U256>().map_err(to_serde_err)?,
+            None => U256::from_dec_str(&s).map_err(to_serde_err)?,
+        },
+        StrOrInt::Int(i) => u64::try_from(i).map_err(to_serde_err)?.into(),
+    };
+    Ok(Some(amount))
+}
+
 #[cfg(test)]
 mod test {
-    use hyperlane_core::{H160, H256};
+    use hyperlane_core::{Encode, H160, H256};
+    use hyperlane_warp_route::TokenMessage;
 
-    use super::{Filter::*, MatchingList};
+    use super::{BodyPrefix, Filter::*, MatchingList};
     use crate::settings::matching_list::MatchInfo;
 
     #[test]
@@ -393,7 +531,8 @@ mod test {
                 src_domain: 0,
                 src_addr: &H256::default(),
                 dst_domain: 0,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                body: &[]
             },
             false
         ));
@@ -407,7 +546,8 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                body: &[]
             },
             false
         ))
@@ -449,7 +589,8 @@ mod test {
                 dst_addr: &"9d4454B023096f34B160D6B654540c56A1F81688"
                     .parse::<H160>()
                     .unwrap()
-                    .into()
+                    .into(),
+                body: &[]
             },
             false
         ));
@@ -463,7 +604,8 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                body: &[]
             },
             false
         ));
@@ -483,6 +625,94 @@ mod test {
         assert_eq!(elem.sender_address, Wildcard);
     }
 
+    fn token_message_body(recipient: H256, amount: u64) -> Vec<u8> {
+        TokenMessage::new(recipient, amount.into(), vec![]).to_vec()
+    }
+
+    #[test]
+    fn config_with_body_prefix() {
+        let list: MatchingList =
+            serde_json::from_str(r#"[{"bodyprefix": ["0xdeadbeef", "cafe"]}]"#).unwrap();
+        let elem = &list.0.as_ref().unwrap()[0];
+        assert_eq!(
+            elem.body_prefix,
+            Enumerated(vec![
+                BodyPrefix(vec![0xde, 0xad, 0xbe, 0xef]),
+                BodyPrefix(vec![0xca, 0xfe])
+            ])
+        );
+
+        let info = |body| MatchInfo {
+            src_msg_id: H256::default(),
+            src_domain: 0,
+            src_addr: &H256::zero(),
+            dst_domain: 0,
+            dst_addr: &H256::zero(),
+            body,
+        };
+        assert!(list.matches(info(&[0xde, 0xad, 0xbe, 0xef, 0x01]), false));
+        assert!(list.matches(info(&[0xca, 0xfe]), false));
+        assert!(!list.matches(info(&[0xde, 0xad]), false));
+        assert!(!list.matches(info(&[]), false));
+    }
+
+    #[test]
+    fn config_with_token_message_predicates() {
+        let recipient = H256::repeat_byte(0x42);
+        let list: MatchingList = serde_json::from_str(&format!(
+            r#"[{{"tokenrecipient": "{recipient:?}", "minamount": "1000", "maxamount": "0x2710"}}]"#
+        ))
+        .unwrap();
+        let elem = &list.0.as_ref().unwrap()[0];
+        assert_eq!(elem.min_amount, Some(1000.into()));
+        assert_eq!(elem.max_amount, Some(10000.into()));
+
+        let matches_body = |body: &[u8]| {
+            list.matches(
+                MatchInfo {
+                    src_msg_id: H256::default(),
+                    src_domain: 0,
+                    src_addr: &H256::zero(),
+                    dst_domain: 0,
+                    dst_addr: &H256::zero(),
+                    body,
+                },
+                false,
+            )
+        };
+        assert!(matches_body(&token_message_body(recipient, 1000)));
+        assert!(matches_body(&token_message_body(recipient, 10000)));
+        assert!(!matches_body(&token_message_body(recipient, 999)));
+        assert!(!matches_body(&token_message_body(recipient, 10001)));
+        assert!(!matches_body(&token_message_body(H256::zero(), 5000)));
+        // Bodies that are not token messages do not match transfer predicates
+        assert!(!matches_body(&[0x01; 16]));
+    }
+
+    #[test]
+    fn config_with_amount_only_matches_dust() {
+        let list: MatchingList = serde_json::from_str(r#"[{"maxamount": 100}]"#).unwrap();
+        let body = token_message_body(H256::random(), 99);
+
+        assert!(list.matches(
+            MatchInfo {
+                src_msg_id: H256::default(),
+                src_domain: 0,
+                src_addr: &H256::zero(),
+                dst_domain: 0,
+                dst_addr: &H256::zero(),
+                body: &body,
+            },
+            false
+        ));
+    }
+
+    #[test]
+    fn config_with_invalid_amount() {
+        assert!(serde_json::from_str::<MatchingList>(r#"[{"minamount": -1}]"#).is_err());
+        assert!(serde_json::from_str::<MatchingList>(r#"[{"minamount": "1k"}]"#).is_err());
+    }
+
     #[test]
     fn config_with_empty_list_is_none() {
         let whitelist: MatchingList = serde_json::from_str(r#"[]"#).unwrap();
@@ -497,6 +727,7 @@ mod test {
             src_addr: &H256::default(),
             dst_domain: 0,
             dst_addr: &H256::default(),
+            body: &[],
         };
         // whitelist use
         assert!(MatchingList(None).matches(info, true));
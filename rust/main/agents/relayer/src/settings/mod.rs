@@ -4,11 +4,14 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::Duration,
+};
 
 use convert_case::Case;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
-use ethers::utils::hex;
 use eyre::{eyre, Context};
 use hyperlane_base::{
     impl_loadable_from_settings,
@@ -17,15 +20,37 @@ use hyperlane_base::{
         Settings,
     },
 };
-use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, U256};
+use hyperlane_core::{cfg_unwrap_all, config::*, FixedPointNumber, HyperlaneDomain, U256};
 use itertools::Itertools;
+use reqwest::Url;
 use serde::Deserialize;
 use serde_json::Value;
 
 use crate::{
-    msg::pending_message::DEFAULT_MAX_MESSAGE_RETRIES, settings::matching_list::MatchingList,
+    msg::{
+        blacklist::{BlacklistEntry, BlacklistSource},
+        gas_payment::price_oracle::{parse_decimal, parse_usd_prices},
+        pending_message::DEFAULT_MAX_MESSAGE_RETRIES,
+    },
+    settings::matching_list::MatchingList,
 };
 
+/// Default interval between reloads of the address blacklist source
+const DEFAULT_ADDRESS_BLACKLIST_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Default window over which a destination's spend budget applies
+const DEFAULT_SPEND_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Default interval between rebuilds of the gas accounting report
+const DEFAULT_GAS_ACCOUNTING_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Default time for which prices fetched from an HTTP token price oracle are reused
+const DEFAULT_TOKEN_PRICE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Default age after which prices from an HTTP token price oracle are no longer
+/// used when fetching fresh ones fails
+const DEFAULT_TOKEN_PRICE_MAX_STALENESS: Duration = Duration::from_secs(900);
+
 pub mod matching_list;
 
 /// Settings for `Relayer`
@@ -50,21 +75,75 @@ pub struct RelayerSettings {
     /// Filter for what messages to block.
     pub blacklist: MatchingList,
     /// Filter for what addresses to block interactions with.
-    /// This is intentionally not an H256 to allow for addresses of any length without
-    /// adding any padding.
-    pub address_blacklist: Vec<Vec<u8>>,
+    /// Entries are typed by protocol (e.g. `evm:0x...`, `sealevel:<base58>`); untyped
+    /// hex entries of any length are matched as raw bytes.
+    pub address_blacklist: Vec<BlacklistEntry>,
+    /// Optional file or URL to periodically reload additional blacklisted addresses from.
+    pub address_blacklist_source: Option<BlacklistSource>,
+    /// How often to reload `address_blacklist_source`.
+    pub address_blacklist_refresh_interval: Duration,
     /// This is optional. If not specified, any amount of gas will be valid, otherwise this
     /// is the max allowed gas in wei to relay a transaction.
     pub transaction_gas_limit: Option<U256>,
     /// List of domain ids to skip transaction gas for.
     pub skip_transaction_gas_limit_for: HashSet<u32>,
-    /// If true, allows local storage based checkpoint syncers.
-    /// Not intended for production use.
+    /// If true, allows checkpoint syncers reading from the local machine or
+    /// network, and HTTP locations not served over https. Not intended for
+    /// production use.
     pub allow_local_checkpoint_syncers: bool,
     /// App contexts used for metrics.
     pub metric_app_contexts: Vec<(MatchingList, String)>,
+    /// How to share the op queues between app contexts, by app context name.
+    /// Operations are popped in plain queue order if empty.
+    pub app_context_scheduling: HashMap<String, AppContextSchedulingConf>,
     /// Maximum number of retries per operation
     pub max_retries: u32,
+    /// API key for the operation admin endpoints. The endpoints are disabled if unset.
+    pub admin_api_key: Option<String>,
+    /// Limits on transaction submission, by destination chain
+    pub submission_limits: HashMap<HyperlaneDomain, SubmissionLimitsConf>,
+    /// How often to rebuild the gas accounting report from the database
+    pub gas_accounting_interval: Duration,
+}
+
+/// Weighted fair scheduling config of an app context
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppContextSchedulingConf {
+    /// Share of the op queues relative to other app contexts
+    pub weight: u32,
+    /// Max operations past the prepare queue at once. Unlimited if unset.
+    pub max_in_flight: Option<u32>,
+}
+
+impl Default for AppContextSchedulingConf {
+    fn default() -> Self {
+        Self {
+            weight: 1,
+            max_in_flight: None,
+        }
+    }
+}
+
+/// Limits on how much and how fast a destination's submitter may spend
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmissionLimitsConf {
+    /// Max native tokens (in the smallest unit) to spend on transactions
+    /// during any `spend_window`. Unlimited if unset.
+    pub spend_budget: Option<U256>,
+    /// Rolling window `spend_budget` applies to
+    pub spend_window: Duration,
+    /// Max transactions to submit per minute. Unlimited if unset.
+    pub max_tx_per_minute: Option<u32>,
+}
+
+impl Default for SubmissionLimitsConf {
+    fn default() -> Self {
+        Self {
+            spend_budget: None,
+            spend_window: DEFAULT_SPEND_WINDOW,
+            max_tx_per_minute: None,
+        }
+    }
 }
 
 /// Config for gas payment enforcement
@@ -98,6 +177,42 @@ pub enum GasPaymentEnforcementPolicy {
         gas_fraction_numerator: u64,
         gas_fraction_denominator: u64,
     },
+    /// The USD value of the payment, in the origin's native token, covers the
+    /// USD value of the delivery cost, in the destination's native token, plus
+    /// a margin. UsdValue requires a payment to exist on the IGP specified in
+    /// the config.
+    UsdValue {
+        oracle: TokenPriceOracleConf,
+        /// Fraction of the delivery cost to require on top of it, e.g. 0.1
+        /// requires payments worth 110% of the cost
+        margin: FixedPointNumber,
+        /// Native token of each chain, by domain id
+        native_tokens: HashMap<u32, NativeTokenConf>,
+    },
+}
+
+/// Config for the source of native token USD prices
+#[derive(Debug, Clone)]
+pub enum TokenPriceOracleConf {
+    /// Fixed prices, by chain name
+    Static {
+        prices: HashMap<String, FixedPointNumber>,
+    },
+    /// Prices fetched from an endpoint returning a JSON object of chain names
+    /// to USD prices
+    Http {
+        url: Url,
+        cache_ttl: Duration,
+        /// How old the last fetched prices may get while fetches fail
+        max_staleness: Duration,
+    },
+}
+
+/// The native token of a chain
+#[derive(Debug, Clone)]
+pub struct NativeTokenConf {
+    pub chain_name: String,
+    pub decimals: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -217,6 +332,61 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
                                 .unwrap_or(1),
                         })
                     }
+                    Some("usdValue") => {
+                        let oracle = policy
+                            .chain(&mut err)
+                            .get_key("oracle")
+                            .end()
+                            .and_then(|oracle| parse_token_price_oracle(oracle).take_config_err(&mut err));
+                        let margin = policy
+                            .chain(&mut err)
+                            .get_opt_key("margin")
+                            .end()
+                            .and_then(|margin| {
+                                parse_decimal(margin.val)
+                                    .into_config_result(|| margin.cwp.clone())
+                                    .take_config_err(&mut err)
+                            })
+                            .unwrap_or_else(FixedPointNumber::zero);
+                        let token_decimals: HashMap<String, u32> = policy
+                            .chain(&mut err)
+                            .get_opt_key("tokenDecimals")
+                            .into_obj_iter()
+                            .map(|decimals| {
+                                decimals
+                                    .filter_map(|(chain_name, d)| {
+                                        d.chain(&mut err).parse_u32().end().map(|d| (chain_name, d))
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        // `tokenDecimals` overrides the decimals of the chain's native token
+                        let native_tokens = base
+                            .iter()
+                            .flat_map(|base| &base.chains)
+                            .filter_map(|(chain_name, chain)| {
+                                let decimals = token_decimals
+                                    .get(chain_name)
+                                    .copied()
+                                    .or_else(|| chain.connection.native_token_decimals())
+                                    .ok_or_else(|| eyre!("Unknown native token decimals for chain `{chain_name}`, set them in `tokenDecimals`"))
+                                    .take_err(&mut err, || &policy.cwp + "token_decimals")?;
+                                Some((
+                                    chain.domain.id(),
+                                    NativeTokenConf {
+                                        chain_name: chain_name.clone(),
+                                        decimals,
+                                    },
+                                ))
+                            })
+                            .collect();
+
+                        oracle.map(|oracle| GasPaymentEnforcementPolicy::UsdValue {
+                            oracle,
+                            margin,
+                            native_tokens,
+                        })
+                    }
                     Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
                         .take_err(&mut err, || cwp + "type"),
                 }.map(|policy| GasPaymentEnforcementConf {
@@ -249,6 +419,27 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .map(|str| parse_address_list(str, &mut err, || &p.cwp + "address_blacklist"))
             .unwrap_or_default();
 
+        let address_blacklist_source = p
+            .chain(&mut err)
+            .get_opt_key("addressBlacklistSource")
+            .parse_string()
+            .end()
+            .and_then(|str| {
+                str.parse()
+                    .take_err(&mut err, || &p.cwp + "address_blacklist_source")
+            });
+
+        let address_blacklist_refresh_interval = p
+            .chain(&mut err)
+            .get_opt_key("addressBlacklistRefreshInterval")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_ADDRESS_BLACKLIST_REFRESH_INTERVAL);
+        if address_blacklist_refresh_interval.is_zero() {
+            Err::<(), eyre::Report>(eyre!("Address blacklist refresh interval must be positive"))
+                .take_err(&mut err, || &p.cwp + "address_blacklist_refresh_interval");
+        }
+
         let transaction_gas_limit = p
             .chain(&mut err)
             .get_opt_key("transactionGasLimit")
@@ -300,6 +491,7 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
 
         let metric_app_contexts_parser =
             ValueParser::new(raw_metric_app_contexts_path, &raw_metric_app_contexts);
+        let mut app_context_scheduling = HashMap::new();
         let metric_app_contexts = metric_app_contexts_parser
             .into_array_iter()
             .map(|itr| {
@@ -312,7 +504,32 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
                         .and_then(parse_matching_list)
                         .unwrap_or_default();
 
-                    name.map(|name| (matching_list, name.to_owned()))
+                    let weight = policy
+                        .chain(&mut err)
+                        .get_opt_key("weight")
+                        .parse_u32()
+                        .end();
+                    if weight == Some(0) {
+                        Err::<(), eyre::Report>(eyre!("App context weight must be positive"))
+                            .take_err(&mut err, || &policy.cwp + "weight");
+                    }
+                    let max_in_flight = policy
+                        .chain(&mut err)
+                        .get_opt_key("maxInFlight")
+                        .parse_u32()
+                        .end();
+
+                    let name = name?.to_owned();
+                    if weight.is_some() || max_in_flight.is_some() {
+                        app_context_scheduling.insert(
+                            name.clone(),
+                            AppContextSchedulingConf {
+                                weight: weight.unwrap_or(1),
+                                max_in_flight,
+                            },
+                        );
+                    }
+                    Some((matching_list, name))
                 })
                 .collect_vec()
             })
@@ -324,6 +541,81 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .parse_u32()
             .unwrap_or(DEFAULT_MAX_MESSAGE_RETRIES);
 
+        let admin_api_key = p
+            .chain(&mut err)
+            .get_opt_key("adminApiKey")
+            .parse_string()
+            .end()
+            .map(str::to_owned);
+
+        let gas_accounting_interval = p
+            .chain(&mut err)
+            .get_opt_key("gasAccountingInterval")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_GAS_ACCOUNTING_INTERVAL);
+
+        let (raw_submission_limits_path, raw_submission_limits) = p
+            .get_opt_key("submissionLimits")
+            .take_config_err_flat(&mut err)
+            .and_then(parse_json_array)
+            .unwrap_or_else(|| (&p.cwp + "submission_limits", Value::Array(vec![])));
+
+        let submission_limits_parser =
+            ValueParser::new(raw_submission_limits_path, &raw_submission_limits);
+        let submission_limits = submission_limits_parser
+            .into_array_iter()
+            .map(|itr| {
+                itr.filter_map(|limits| {
+                    let destination_name = limits
+                        .chain(&mut err)
+                        .get_key("destination")
+                        .parse_string()
+                        .end();
+                    let destination = destination_name.and_then(|chain| {
+                        base.lookup_domain(chain)
+                            .context("Missing configuration for a chain in `submissionLimits`")
+                            .into_config_result(|| &limits.cwp + "destination")
+                            .take_config_err(&mut err)
+                    });
+                    let spend_budget = limits
+                        .chain(&mut err)
+                        .get_opt_key("spendBudget")
+                        .parse_u256()
+                        .end();
+                    let spend_window = limits
+                        .chain(&mut err)
+                        .get_opt_key("spendWindow")
+                        .parse_u64()
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_SPEND_WINDOW);
+                    let max_tx_per_minute = limits
+                        .chain(&mut err)
+                        .get_opt_key("maxTxPerMinute")
+                        .parse_u32()
+                        .end();
+                    if max_tx_per_minute == Some(0) {
+                        Err::<(), eyre::Report>(eyre!(
+                            "Max transactions per minute must be positive"
+                        ))
+                        .take_err(&mut err, || &limits.cwp + "max_tx_per_minute");
+                    }
+
+                    destination.map(|destination| {
+                        (
+                            destination,
+                            SubmissionLimitsConf {
+                                spend_budget,
+                                spend_window,
+                                max_tx_per_minute,
+                            },
+                        )
+                    })
+                })
+                .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+
         err.into_result(RelayerSettings {
             base,
             db,
@@ -333,11 +625,17 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             whitelist,
             blacklist,
             address_blacklist,
+            address_blacklist_source,
+            address_blacklist_refresh_interval,
             transaction_gas_limit,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers,
             metric_app_contexts,
+            app_context_scheduling,
             max_retries: max_message_retries,
+            admin_api_key,
+            submission_limits,
+            gas_accounting_interval,
         })
     }
 }
@@ -362,6 +660,60 @@ fn parse_json_array(p: ValueParser) -> Option<(ConfigPath, Value)> {
     }
 }
 
+fn parse_token_price_oracle(p: ValueParser) -> ConfigResult<TokenPriceOracleConf> {
+    let mut err = ConfigParsingError::default();
+
+    let oracle_type = p.chain(&mut err).get_key("type").parse_string().end();
+    let oracle = match oracle_type {
+        Some("static") => p
+            .chain(&mut err)
+            .get_key("prices")
+            .end()
+            .and_then(|prices| {
+                parse_usd_prices(prices.val)
+                    .into_config_result(|| prices.cwp.clone())
+                    .take_config_err(&mut err)
+            })
+            .map(|prices| TokenPriceOracleConf::Static { prices }),
+        Some("http") => {
+            let url = p
+                .chain(&mut err)
+                .get_key("url")
+                .parse_from_str("Expected token price oracle url")
+                .end();
+            let cache_ttl = p
+                .chain(&mut err)
+                .get_opt_key("cacheTtl")
+                .parse_u64()
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_TOKEN_PRICE_CACHE_TTL);
+            let max_staleness = p
+                .chain(&mut err)
+                .get_opt_key("maxStaleness")
+                .parse_u64()
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_TOKEN_PRICE_MAX_STALENESS);
+            if max_staleness < cache_ttl {
+                Err::<(), eyre::Report>(eyre!(
+                    "Token price max staleness must be at least the cache TTL"
+                ))
+                .take_err(&mut err, || &p.cwp + "max_staleness");
+            }
+            url.map(|url| TokenPriceOracleConf::Http {
+                url,
+                cache_ttl,
+                max_staleness,
+            })
+        }
+        Some(t) => Err(eyre!("Unknown token price oracle type `{t}`"))
+            .take_err(&mut err, || &p.cwp + "type"),
+        None => None,
+    };
+
+    cfg_unwrap_all!(&p.cwp, err: [oracle]);
+    err.into_result(oracle)
+}
+
 fn parse_matching_list(p: ValueParser) -> ConfigResult<MatchingList> {
     let mut err = ConfigParsingError::default();
 
@@ -382,48 +734,55 @@ fn parse_address_list(
     str: &str,
     err: &mut ConfigParsingError,
     err_path: impl Fn() -> ConfigPath,
-) -> Vec<Vec<u8>> {
+) -> Vec<BlacklistEntry> {
     str.split(',')
-        .filter_map(|s| {
-            let mut s = s.trim().to_owned();
-            if let Some(stripped) = s.strip_prefix("0x") {
-                s = stripped.to_owned();
-            }
-            hex::decode(s).take_err(err, &err_path)
-        })
+        .filter_map(|s| s.parse().take_err(err, &err_path))
         .collect_vec()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use ethers::utils::hex;
     use hyperlane_core::H160;
 
     #[test]
     fn test_parse_address_blacklist() {
         let valid_address1 = b"valid".to_vec();
-        let valid_address2 = H160::random().as_bytes().to_vec();
+        let valid_address2 = H160::random();
 
         // Successful parsing
         let input = format!(
-            "0x{}, {}",
+            "0x{}, evm:{:?}",
             hex::encode(&valid_address1),
-            hex::encode(&valid_address2)
+            valid_address2
         );
         let mut err = ConfigParsingError::default();
         let res = parse_address_list(&input, &mut err, ConfigPath::default);
-        assert_eq!(res, vec![valid_address1.clone(), valid_address2.clone()]);
+        assert_eq!(
+            res,
+            vec![
+                BlacklistEntry::Raw(valid_address1.clone()),
+                BlacklistEntry::Evm(valid_address2)
+            ]
+        );
         assert!(err.is_ok());
 
         // An error in the final address provided
         let input = format!(
-            "0x{}, {}, 0xaazz",
+            "0x{}, evm:{:?}, 0xaazz",
             hex::encode(&valid_address1),
-            hex::encode(&valid_address2)
+            valid_address2
         );
         let mut err = ConfigParsingError::default();
         let res = parse_address_list(&input, &mut err, ConfigPath::default);
-        assert_eq!(res, vec![valid_address1, valid_address2]);
+        assert_eq!(
+            res,
+            vec![
+                BlacklistEntry::Raw(valid_address1),
+                BlacklistEntry::Evm(valid_address2)
+            ]
+        );
         assert!(!err.is_ok());
     }
 }
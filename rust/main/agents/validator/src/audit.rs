@@ -0,0 +1,640 @@
+//! Audits the checkpoints a validator published to its checkpoint storage
+//! against the origin merkle tree hook and the local database.
+
+use std::sync::Arc;
+
+use eyre::{bail, Result};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use hyperlane_base::{db::HyperlaneDb, CheckpointSyncer};
+use hyperlane_core::{
+    accumulator::incremental::IncrementalMerkle, Checkpoint, CheckpointWithMessageId,
+    HyperlaneChain, HyperlaneContract, HyperlaneSigner, HyperlaneSignerExt, MerkleTreeHook,
+    ReorgPeriod, H160, H256,
+};
+use hyperlane_ethereum::SingletonSignerHandle;
+
+/// What to do with the issues found by an audit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointAuditMode {
+    /// Only report the issues
+    Report,
+    /// Report the issues and re-sign missing checkpoints
+    Repair,
+}
+
+impl CheckpointAuditMode {
+    /// Parses the `audit <report|repair>` subcommand from the arguments after
+    /// the executable path. Returns `None` if no subcommand was given.
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Option<Self>> {
+        let mode = match args.next().as_deref() {
+            None => return Ok(None),
+            Some(arg) if arg.starts_with("--") => return Ok(None),
+            Some("audit") => match args.next().as_deref() {
+                Some("report") => Self::Report,
+                Some("repair") => Self::Repair,
+                _ => bail!("Expected an audit mode, usage: validator audit <report|repair>"),
+            },
+            Some(subcommand) => bail!("Unknown subcommand `{subcommand}`, expected audit"),
+        };
+        if let Some(arg) = args.next().filter(|arg| !arg.starts_with("--")) {
+            bail!("Unexpected argument `{arg}`, usage: validator audit <report|repair>");
+        }
+        Ok(Some(mode))
+    }
+}
+
+/// An issue with the published checkpoints
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CheckpointAuditIssue {
+    /// No checkpoints were published for the indices `from..=to`
+    Gap { from: u32, to: u32 },
+    /// The checkpoint could not be read from storage
+    Unreadable { index: u32, error: String },
+    /// The checkpoint was not signed by the validator. `actual` is `None` if
+    /// the signer could not be recovered.
+    WrongSigner {
+        index: u32,
+        expected: H160,
+        actual: Option<H160>,
+    },
+    /// The checkpoint is for another merkle tree hook
+    WrongMerkleTreeHook {
+        index: u32,
+        merkle_tree_hook_address: H256,
+        mailbox_domain: u32,
+    },
+    /// The checkpoint root does not match the tree built from the local database
+    MismatchedRoot {
+        index: u32,
+        expected: H256,
+        actual: H256,
+    },
+    /// The checkpoint message id does not match the local database
+    MismatchedMessageId {
+        index: u32,
+        expected: H256,
+        actual: H256,
+    },
+    /// The local database has no merkle tree insertion to check the
+    /// checkpoints from this index on against
+    Unverifiable { from: u32 },
+    /// The tree built from the local database disagrees with the merkle tree
+    /// hook, so the database is not a reliable reference
+    MismatchedOnchainRoot {
+        index: u32,
+        local: H256,
+        onchain: H256,
+    },
+    /// A reorg was reported to the checkpoint storage
+    ReorgReported {
+        checkpoint_index: u32,
+        local_merkle_root: H256,
+        canonical_merkle_root: H256,
+    },
+    /// The latest index in storage is past the finalized merkle tree
+    LatestIndexAhead {
+        latest_index: u32,
+        finalized_index: u32,
+    },
+}
+
+/// The result of auditing a validator's checkpoint storage
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointAuditReport {
+    /// The validator whose signatures are expected
+    pub validator: H160,
+    /// The latest index according to the checkpoint storage
+    pub latest_index: Option<u32>,
+    /// The index of the latest finalized merkle tree hook checkpoint, i.e.
+    /// the last index that was audited
+    pub finalized_index: Option<u32>,
+    /// The issues found, in index order
+    pub issues: Vec<CheckpointAuditIssue>,
+    /// The indices of the checkpoints that were re-signed
+    pub repaired: Vec<u32>,
+    /// Whether a repair was requested but nothing was signed, because the
+    /// local database could not be trusted
+    pub repair_aborted: bool,
+}
+
+/// Walks a validator's checkpoint storage and checks every checkpoint up to
+/// the finalized merkle tree hook index.
+pub(crate) struct CheckpointAuditor {
+    checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+    merkle_tree_hook: Arc<dyn MerkleTreeHook>,
+    db: Arc<dyn HyperlaneDb>,
+    signer: SingletonSignerHandle,
+    reorg_period: ReorgPeriod,
+}
+
+impl CheckpointAuditor {
+    pub(crate) fn new(
+        checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+        merkle_tree_hook: Arc<dyn MerkleTreeHook>,
+        db: Arc<dyn HyperlaneDb>,
+        signer: SingletonSignerHandle,
+        reorg_period: ReorgPeriod,
+    ) -> Self {
+        Self {
+            checkpoint_syncer,
+            merkle_tree_hook,
+            db,
+            signer,
+            reorg_period,
+        }
+    }
+
+    pub(crate) async fn audit(&self, mode: CheckpointAuditMode) -> Result<CheckpointAuditReport> {
+        let validator = self.signer.eth_address();
+        let mut report = CheckpointAuditReport {
+            validator,
+            latest_index: self.checkpoint_syncer.latest_index().await?,
+            ..Default::default()
+        };
+        if self.merkle_tree_hook.count(&self.reorg_period).await? == 0 {
+            return Ok(report);
+        }
+        let finalized = self
+            .merkle_tree_hook
+            .latest_checkpoint(&self.reorg_period)
+            .await?;
+        report.finalized_index = Some(finalized.index);
+
+        // The checkpoints expected from the local database, as long as it has every leaf
+        let expected = self.expected_checkpoints(finalized.index)?;
+        if expected.len() <= finalized.index as usize {
+            report.issues.push(CheckpointAuditIssue::Unverifiable {
+                from: expected.len() as u32,
+            });
+        }
+        let local_root = expected
+            .get(finalized.index as usize)
+            .map(|checkpoint| checkpoint.root);
+        if let Some(local) = local_root.filter(|&root| root != finalized.root) {
+            report
+                .issues
+                .push(CheckpointAuditIssue::MismatchedOnchainRoot {
+                    index: finalized.index,
+                    local,
+                    onchain: finalized.root,
+                });
+        }
+        let reorg_event = self.checkpoint_syncer.reorg_status().await?;
+        if let Some(reorg_event) = &reorg_event {
+            report.issues.push(CheckpointAuditIssue::ReorgReported {
+                checkpoint_index: reorg_event.checkpoint_index,
+                local_merkle_root: reorg_event.local_merkle_root,
+                canonical_merkle_root: reorg_event.canonical_merkle_root,
+            });
+        }
+        if let Some(latest_index) = report.latest_index.filter(|&i| i > finalized.index) {
+            report.issues.push(CheckpointAuditIssue::LatestIndexAhead {
+                latest_index,
+                finalized_index: finalized.index,
+            });
+        }
+
+        // Only sign checkpoints when the local database provably matches the chain
+        let repair = mode == CheckpointAuditMode::Repair;
+        let can_repair = local_root == Some(finalized.root) && reorg_event.is_none();
+        if repair && !can_repair {
+            warn!(
+                finalized_index = finalized.index,
+                "Not repairing checkpoints, as the local database does not match the merkle tree hook or a reorg was reported"
+            );
+            report.repair_aborted = true;
+        }
+
+        let merkle_tree_hook_address = self.merkle_tree_hook.address();
+        let mailbox_domain = self.merkle_tree_hook.domain().id();
+        let mut gap_start = None;
+        // The highest index up to which every checkpoint is published without issues
+        let mut contiguous_to = None;
+
+        for index in 0..=finalized.index {
+            let expected = expected.get(index as usize);
+            let published = match self.checkpoint_syncer.fetch_checkpoint(index).await {
+                Ok(published) => published,
+                Err(err) => {
+                    report.issues.push(CheckpointAuditIssue::Unreadable {
+                        index,
+                        error: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let Some(published) = published else {
+                match expected.filter(|_| repair && can_repair) {
+                    Some(expected) => {
+                        let signed = self.signer.sign(*expected).await?;
+                        self.checkpoint_syncer.write_checkpoint(&signed).await?;
+                        report.repaired.push(index);
+                        if contiguous_to.map_or(0, |i| i + 1) == index {
+                            contiguous_to = Some(index);
+                        }
+                    }
+                    None => {
+                        gap_start.get_or_insert(index);
+                    }
+                }
+                continue;
+            };
+            if let Some(from) = gap_start.take() {
+                report.issues.push(CheckpointAuditIssue::Gap {
+                    from,
+                    to: index - 1,
+                });
+            }
+            let issues_before = report.issues.len();
+
+            let signer = published.recover().ok();
+            if signer != Some(validator) {
+                report.issues.push(CheckpointAuditIssue::WrongSigner {
+                    index,
+                    expected: validator,
+                    actual: signer,
+                });
+            }
+            if published.value.merkle_tree_hook_address != merkle_tree_hook_address
+                || published.value.mailbox_domain != mailbox_domain
+            {
+                report
+                    .issues
+                    .push(CheckpointAuditIssue::WrongMerkleTreeHook {
+                        index,
+                        merkle_tree_hook_address: published.value.merkle_tree_hook_address,
+                        mailbox_domain: published.value.mailbox_domain,
+                    });
+            }
+            if let Some(expected) = expected {
+                if published.value.root != expected.root {
+                    report.issues.push(CheckpointAuditIssue::MismatchedRoot {
+                        index,
+                        expected: expected.root,
+                        actual: published.value.root,
+                    });
+                }
+                if published.value.message_id != expected.message_id {
+                    report
+                        .issues
+                        .push(CheckpointAuditIssue::MismatchedMessageId {
+                            index,
+                            expected: expected.message_id,
+                            actual: published.value.message_id,
+                        });
+                }
+            }
+            if report.issues.len() == issues_before && contiguous_to.map_or(0, |i| i + 1) == index {
+                contiguous_to = Some(index);
+            }
+        }
+        if let Some(from) = gap_start {
+            report.issues.push(CheckpointAuditIssue::Gap {
+                from,
+                to: finalized.index,
+            });
+        }
+
+        if !report.repaired.is_empty() {
+            if let Some(contiguous_to) = contiguous_to {
+                self.checkpoint_syncer
+                    .update_latest_index(contiguous_to)
+                    .await?;
+            }
+        }
+        if report.issues.is_empty() {
+            info!(finalized_index = ?report.finalized_index, "Checkpoint audit found no issues");
+        } else {
+            warn!(
+                issues = report.issues.len(),
+                repaired = report.repaired.len(),
+                "Checkpoint audit found issues"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Builds the checkpoints up to `to_index` from the merkle tree insertions
+    /// in the local database, stopping at the first missing insertion
+    fn expected_checkpoints(&self, to_index: u32) -> Result<Vec<CheckpointWithMessageId>> {
+        let merkle_tree_hook_address = self.merkle_tree_hook.address();
+        let mailbox_domain = self.merkle_tree_hook.domain().id();
+        let mut tree = IncrementalMerkle::default();
+        let mut checkpoints = vec![];
+        for index in 0..=to_index {
+            let Some(insertion) = self
+                .db
+                .retrieve_merkle_tree_insertion_by_leaf_index(&index)?
+            else {
+                break;
+            };
+            tree.ingest(insertion.message_id());
+            checkpoints.push(CheckpointWithMessageId {
+                checkpoint: Checkpoint {
+                    merkle_tree_hook_address,
+                    mailbox_domain,
+                    root: tree.root(),
+                    index,
+                },
+                message_id: insertion.message_id(),
+            });
+        }
+        Ok(checkpoints)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use ethers::signers::LocalWallet;
+    use hyperlane_base::{
+        db::{test_utils, HyperlaneRocksDB},
+        LocalStorage,
+    };
+    use hyperlane_core::{
+        test_utils::dummy_domain, ChainResult, HyperlaneDomain, HyperlaneProvider,
+        MerkleTreeInsertion, ReorgEvent, SignedCheckpointWithMessageId,
+    };
+    use hyperlane_ethereum::{Signers, SingletonSigner};
+
+    use super::*;
+
+    mockall::mock! {
+        pub MerkleTreeHook {}
+
+        impl Debug for MerkleTreeHook {
+            fn fmt<'a>(&self, f: &mut std::fmt::Formatter<'a>) -> std::fmt::Result;
+        }
+
+        impl HyperlaneChain for MerkleTreeHook {
+            fn domain(&self) -> &HyperlaneDomain;
+            fn provider(&self) -> Box<dyn HyperlaneProvider>;
+        }
+
+        impl HyperlaneContract for MerkleTreeHook {
+            fn address(&self) -> H256;
+        }
+
+        #[async_trait]
+        impl MerkleTreeHook for MerkleTreeHook {
+            async fn tree(&self, reorg_period: &ReorgPeriod) -> ChainResult<IncrementalMerkle>;
+            async fn count(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32>;
+            async fn latest_checkpoint(&self, reorg_period: &ReorgPeriod) -> ChainResult<Checkpoint>;
+        }
+    }
+
+    const VALIDATOR_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const LEAF_COUNT: u32 = 6;
+
+    fn validator_signer(key: &str) -> SingletonSignerHandle {
+        let wallet: LocalWallet = key.parse().unwrap();
+        let (signer, handle) = SingletonSigner::new(Signers::from(wallet));
+        tokio::spawn(signer.run());
+        handle
+    }
+
+    /// Stores `LEAF_COUNT` merkle tree insertions in `db` and mocks a merkle
+    /// tree hook that finalized all of them
+    fn setup_merkle_tree_hook(db: &HyperlaneRocksDB) -> (MockMerkleTreeHook, Vec<Checkpoint>) {
+        let domain = dummy_domain(0, "dummy_domain");
+        let address = H256::from_low_u64_be(1);
+        let mut tree = IncrementalMerkle::default();
+        let mut checkpoints = vec![];
+        for index in 0..LEAF_COUNT {
+            let insertion = MerkleTreeInsertion::new(index, H256::random());
+            db.store_merkle_tree_insertion_by_leaf_index(&index, &insertion)
+                .unwrap();
+            tree.ingest(insertion.message_id());
+            checkpoints.push(Checkpoint {
+                merkle_tree_hook_address: address,
+                mailbox_domain: domain.id(),
+                root: tree.root(),
+                index,
+            });
+        }
+
+        let merkle_tree_hook = mock_merkle_tree_hook(checkpoints[checkpoints.len() - 1]);
+        (merkle_tree_hook, checkpoints)
+    }
+
+    /// Mocks a merkle tree hook that finalized `finalized`
+    fn mock_merkle_tree_hook(finalized: Checkpoint) -> MockMerkleTreeHook {
+        let mut merkle_tree_hook = MockMerkleTreeHook::new();
+        merkle_tree_hook
+            .expect_address()
+            .return_const(finalized.merkle_tree_hook_address);
+        merkle_tree_hook
+            .expect_domain()
+            .return_const(dummy_domain(finalized.mailbox_domain, "dummy_domain"));
+        merkle_tree_hook
+            .expect_count()
+            .returning(move |_| Ok(finalized.index + 1));
+        merkle_tree_hook
+            .expect_latest_checkpoint()
+            .returning(move |_| Ok(finalized));
+        merkle_tree_hook
+    }
+
+    async fn sign(
+        db: &HyperlaneRocksDB,
+        signer: &SingletonSignerHandle,
+        checkpoint: Checkpoint,
+    ) -> SignedCheckpointWithMessageId {
+        let message_id = db
+            .retrieve_merkle_tree_insertion_by_leaf_index(&checkpoint.index)
+            .unwrap()
+            .unwrap()
+            .message_id();
+        signer
+            .sign(CheckpointWithMessageId {
+                checkpoint,
+                message_id,
+            })
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_audit_subcommand() {
+        let parse =
+            |args: &[&str]| CheckpointAuditMode::from_args(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(parse(&[]).unwrap(), None);
+        assert_eq!(parse(&["--db", "/tmp/db"]).unwrap(), None);
+        assert_eq!(
+            parse(&["audit", "report", "--db", "/tmp/db"]).unwrap(),
+            Some(CheckpointAuditMode::Report)
+        );
+        assert_eq!(
+            parse(&["audit", "repair"]).unwrap(),
+            Some(CheckpointAuditMode::Repair)
+        );
+        assert!(parse(&["audit"]).is_err());
+        assert!(parse(&["audit", "fix"]).is_err());
+        assert!(parse(&["audit", "report", "extra"]).is_err());
+        assert!(parse(&["validate"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reports_and_repairs_published_checkpoints() {
+        test_utils::run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&dummy_domain(0, "dummy_domain"), db);
+            let (merkle_tree_hook, checkpoints) = setup_merkle_tree_hook(&db);
+            let signer = validator_signer(VALIDATOR_KEY);
+            let other_signer = validator_signer(
+                "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+            );
+            let storage_dir = tempfile::tempdir().unwrap();
+            let storage = Arc::new(LocalStorage::new(storage_dir.path().to_owned(), None).unwrap());
+
+            // 0 and 1 are fine, 2 and 3 are missing, 4 is signed by someone else
+            // and 5 has the wrong root
+            for checkpoint in &checkpoints[..2] {
+                storage
+                    .write_checkpoint(&sign(&db, &signer, *checkpoint).await)
+                    .await
+                    .unwrap();
+            }
+            storage
+                .write_checkpoint(&sign(&db, &other_signer, checkpoints[4]).await)
+                .await
+                .unwrap();
+            let wrong_root = Checkpoint {
+                root: H256::random(),
+                ..checkpoints[5]
+            };
+            storage
+                .write_checkpoint(&sign(&db, &signer, wrong_root).await)
+                .await
+                .unwrap();
+            storage.write_latest_index(1).await.unwrap();
+
+            let auditor = CheckpointAuditor::new(
+                storage.clone(),
+                Arc::new(merkle_tree_hook),
+                Arc::new(db.clone()),
+                signer.clone(),
+                ReorgPeriod::None,
+            );
+            let report = auditor.audit(CheckpointAuditMode::Report).await.unwrap();
+            assert_eq!(report.latest_index, Some(1));
+            assert_eq!(report.finalized_index, Some(LEAF_COUNT - 1));
+            assert_eq!(
+                report.issues,
+                vec![
+                    CheckpointAuditIssue::Gap { from: 2, to: 3 },
+                    CheckpointAuditIssue::WrongSigner {
+                        index: 4,
+                        expected: signer.eth_address(),
+                        actual: Some(other_signer.eth_address()),
+                    },
+                    CheckpointAuditIssue::MismatchedRoot {
+                        index: 5,
+                        expected: checkpoints[5].root,
+                        actual: wrong_root.root,
+                    },
+                ]
+            );
+            assert!(report.repaired.is_empty());
+
+            let report = auditor.audit(CheckpointAuditMode::Repair).await.unwrap();
+            assert_eq!(report.repaired, vec![2, 3]);
+            assert_eq!(report.issues.len(), 2);
+            // Not advanced past the checkpoints with issues
+            assert_eq!(storage.latest_index().await.unwrap(), Some(3));
+
+            let report = auditor.audit(CheckpointAuditMode::Report).await.unwrap();
+            assert!(!report
+                .issues
+                .iter()
+                .any(|issue| matches!(issue, CheckpointAuditIssue::Gap { .. })));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_repair_is_aborted_when_the_database_does_not_match_the_chain() {
+        test_utils::run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&dummy_domain(0, "dummy_domain"), db);
+            let (_, checkpoints) = setup_merkle_tree_hook(&db);
+            let onchain = Checkpoint {
+                root: H256::random(),
+                ..checkpoints[checkpoints.len() - 1]
+            };
+            let signer = validator_signer(VALIDATOR_KEY);
+            let storage_dir = tempfile::tempdir().unwrap();
+            let storage = Arc::new(LocalStorage::new(storage_dir.path().to_owned(), None).unwrap());
+
+            let auditor = CheckpointAuditor::new(
+                storage.clone(),
+                Arc::new(mock_merkle_tree_hook(onchain)),
+                Arc::new(db.clone()),
+                signer,
+                ReorgPeriod::None,
+            );
+            let report = auditor.audit(CheckpointAuditMode::Repair).await.unwrap();
+            assert!(report.repair_aborted);
+            assert!(report.repaired.is_empty());
+            assert_eq!(
+                report.issues,
+                vec![
+                    CheckpointAuditIssue::MismatchedOnchainRoot {
+                        index: onchain.index,
+                        local: checkpoints[checkpoints.len() - 1].root,
+                        onchain: onchain.root,
+                    },
+                    CheckpointAuditIssue::Gap {
+                        from: 0,
+                        to: onchain.index,
+                    },
+                ]
+            );
+            assert!(storage.fetch_checkpoint(0).await.unwrap().is_none());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_repair_is_aborted_after_a_reported_reorg() {
+        test_utils::run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&dummy_domain(0, "dummy_domain"), db);
+            let (merkle_tree_hook, _) = setup_merkle_tree_hook(&db);
+            let signer = validator_signer(VALIDATOR_KEY);
+            let storage_dir = tempfile::tempdir().unwrap();
+            let storage = Arc::new(LocalStorage::new(storage_dir.path().to_owned(), None).unwrap());
+            let reorg_event = ReorgEvent {
+                checkpoint_index: 3,
+                local_merkle_root: H256::random(),
+                canonical_merkle_root: H256::random(),
+                ..Default::default()
+            };
+            storage.write_reorg_status(&reorg_event).await.unwrap();
+
+            let auditor = CheckpointAuditor::new(
+                storage.clone(),
+                Arc::new(merkle_tree_hook),
+                Arc::new(db.clone()),
+                signer,
+                ReorgPeriod::None,
+            );
+            let report = auditor.audit(CheckpointAuditMode::Repair).await.unwrap();
+            assert!(report.repair_aborted);
+            assert!(report.repaired.is_empty());
+            assert_eq!(
+                report.issues[0],
+                CheckpointAuditIssue::ReorgReported {
+                    checkpoint_index: 3,
+                    local_merkle_root: reorg_event.local_merkle_root,
+                    canonical_merkle_root: reorg_event.canonical_merkle_root,
+                }
+            );
+            assert!(storage.fetch_checkpoint(0).await.unwrap().is_none());
+        })
+        .await;
+    }
+}
@@ -0,0 +1,196 @@
+//! Tracks the signals the validator's health is derived from.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, Ordering};
+
+use hyperlane_base::CheckpointSyncer;
+use prometheus::IntGauge;
+use tracing::warn;
+
+/// Indexer lag, in blocks, above which the indexer is partially healthy
+const DEGRADED_INDEXER_LAG: i64 = 100;
+/// Indexer lag, in blocks, above which the indexer is unhealthy
+const UNHEALTHY_INDEXER_LAG: i64 = 1000;
+/// Seconds without a written checkpoint, while there are checkpoints to sign,
+/// after which the submitter is partially healthy
+const DEGRADED_CHECKPOINT_AGE_SECS: i64 = 5 * 60;
+/// Seconds without a written checkpoint, while there are checkpoints to sign,
+/// after which the submitter is unhealthy
+const UNHEALTHY_CHECKPOINT_AGE_SECS: i64 = 30 * 60;
+/// Consecutive checkpoint syncer write failures after which the submitter is
+/// unhealthy. Any failure makes it partially healthy.
+const UNHEALTHY_WRITE_FAILURES: u32 = 10;
+
+/// How healthy a validator service is, ordered from best to worst
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthStatus {
+    Healthy,
+    PartiallyHealthy,
+    Unhealthy,
+}
+
+impl HealthStatus {
+    fn from_thresholds(value: i64, degraded: i64, unhealthy: i64) -> Self {
+        if value > unhealthy {
+            Self::Unhealthy
+        } else if value > degraded {
+            Self::PartiallyHealthy
+        } else {
+            Self::Healthy
+        }
+    }
+}
+
+/// State shared between the validator tasks and the EigenNodeApi
+#[derive(Debug)]
+pub struct ValidatorHealth {
+    /// The latest block indexed by the merkle tree hook sync
+    indexed_height: IntGauge,
+    /// The latest block of the origin chain
+    chain_height: IntGauge,
+    /// Unix timestamp of the last checkpoint written by the submitter, or 0
+    last_checkpoint_written_at: AtomicI64,
+    /// Checkpoint syncer write failures since the last successful write
+    consecutive_write_failures: AtomicU32,
+    /// Whether the checkpoint storage reports a reorg, as of its last read
+    reorg_reported: AtomicBool,
+}
+
+impl ValidatorHealth {
+    pub fn new(indexed_height: IntGauge, chain_height: IntGauge) -> Self {
+        Self {
+            indexed_height,
+            chain_height,
+            last_checkpoint_written_at: Default::default(),
+            consecutive_write_failures: Default::default(),
+            reorg_reported: Default::default(),
+        }
+    }
+
+    pub fn record_checkpoint_written(&self) {
+        self.last_checkpoint_written_at
+            .store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
+        self.consecutive_write_failures.store(0, Ordering::Relaxed);
+    }
+
+    pub fn record_write_failure(&self) {
+        self.consecutive_write_failures
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reads the reorg status persisted to the checkpoint storage. The last
+    /// known status is kept if it can't be read.
+    pub async fn refresh_reorg_status(&self, checkpoint_syncer: &dyn CheckpointSyncer) {
+        match checkpoint_syncer.reorg_status().await {
+            Ok(reorg_event) => self
+                .reorg_reported
+                .store(reorg_event.is_some(), Ordering::Relaxed),
+            Err(err) => warn!(?err, "Failed to read reorg status"),
+        }
+    }
+
+    /// Blocks between the origin chain tip and the indexer
+    pub fn indexer_lag(&self) -> i64 {
+        (self.chain_height.get() - self.indexed_height.get()).max(0)
+    }
+
+    pub fn indexer_status(&self) -> HealthStatus {
+        HealthStatus::from_thresholds(
+            self.indexer_lag(),
+            DEGRADED_INDEXER_LAG,
+            UNHEALTHY_INDEXER_LAG,
+        )
+    }
+
+    /// `checkpoint_delta` is how many observed checkpoints are yet to be signed
+    pub fn submitter_status(&self, checkpoint_delta: i64) -> HealthStatus {
+        if self.reorg_reported.load(Ordering::Relaxed) {
+            return HealthStatus::Unhealthy;
+        }
+        // Healthy with at most 1 unsigned checkpoint, partially healthy with at most 10
+        let delta_status = HealthStatus::from_thresholds(checkpoint_delta, 1, 10);
+
+        let write_failures = self.consecutive_write_failures.load(Ordering::Relaxed);
+        let write_status = HealthStatus::from_thresholds(
+            write_failures as i64,
+            0,
+            UNHEALTHY_WRITE_FAILURES as i64 - 1,
+        );
+
+        // A submitter that has nothing to sign is not expected to write
+        let last_written_at = self.last_checkpoint_written_at.load(Ordering::Relaxed);
+        let age_status = if checkpoint_delta > 0 && last_written_at > 0 {
+            HealthStatus::from_thresholds(
+                chrono::Utc::now().timestamp() - last_written_at,
+                DEGRADED_CHECKPOINT_AGE_SECS,
+                UNHEALTHY_CHECKPOINT_AGE_SECS,
+            )
+        } else {
+            HealthStatus::Healthy
+        };
+
+        delta_status.max(write_status).max(age_status)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::LocalStorage;
+    use hyperlane_core::ReorgEvent;
+    use prometheus::Opts;
+
+    use super::*;
+
+    fn health() -> ValidatorHealth {
+        ValidatorHealth::new(
+            IntGauge::with_opts(Opts::new("indexed", "indexed")).unwrap(),
+            IntGauge::with_opts(Opts::new("tip", "tip")).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_indexer_status() {
+        let health = health();
+        health.chain_height.set(5000);
+        health.indexed_height.set(4950);
+        assert_eq!(health.indexer_status(), HealthStatus::Healthy);
+        health.indexed_height.set(4500);
+        assert_eq!(health.indexer_status(), HealthStatus::PartiallyHealthy);
+        health.indexed_height.set(0);
+        assert_eq!(health.indexer_status(), HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_submitter_status() {
+        let health = health();
+        assert_eq!(health.submitter_status(0), HealthStatus::Healthy);
+        assert_eq!(health.submitter_status(5), HealthStatus::PartiallyHealthy);
+
+        health.record_write_failure();
+        assert_eq!(health.submitter_status(0), HealthStatus::PartiallyHealthy);
+        for _ in 1..UNHEALTHY_WRITE_FAILURES {
+            health.record_write_failure();
+        }
+        assert_eq!(health.submitter_status(0), HealthStatus::Unhealthy);
+        health.record_checkpoint_written();
+        assert_eq!(health.submitter_status(0), HealthStatus::Healthy);
+
+        health.last_checkpoint_written_at.store(
+            chrono::Utc::now().timestamp() - UNHEALTHY_CHECKPOINT_AGE_SECS - 1,
+            Ordering::Relaxed,
+        );
+        assert_eq!(health.submitter_status(0), HealthStatus::Healthy);
+        assert_eq!(health.submitter_status(1), HealthStatus::Unhealthy);
+
+        health.record_checkpoint_written();
+        let storage_dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(storage_dir.path().to_owned(), None).unwrap();
+        health.refresh_reorg_status(&storage).await;
+        assert_eq!(health.submitter_status(0), HealthStatus::Healthy);
+        storage
+            .write_reorg_status(&ReorgEvent::default())
+            .await
+            .unwrap();
+        health.refresh_reorg_status(&storage).await;
+        assert_eq!(health.submitter_status(0), HealthStatus::Unhealthy);
+    }
+}
@@ -7,8 +7,13 @@ use eyre::Result;
 
 use hyperlane_base::agent_main;
 
-use crate::validator::Validator;
+use crate::{
+    audit::CheckpointAuditMode,
+    validator::{Validator, ValidatorAudit},
+};
 
+mod audit;
+mod health;
 mod server;
 mod settings;
 mod submit;
@@ -19,5 +24,9 @@ async fn main() -> Result<()> {
     // Logging is not initialised at this point, so, using `println!`
     println!("Validator starting up...");
 
+    // `validator audit <report|repair>` audits the published checkpoints instead
+    if CheckpointAuditMode::from_args(std::env::args().skip(1))?.is_some() {
+        return agent_main::<ValidatorAudit>().await;
+    }
     agent_main::<Validator>().await
 }
@@ -12,9 +12,10 @@
 //! - /node/services - List of Services
 //!  eg. response [{"id":"hyperlane-validator-indexer","name":"indexer","description":"indexes the messages from the origin chain mailbox","status":"up"},{"id":"hyperlane-validator-submitter","name":"submitter","description":"signs messages indexed from the indexer","status":"up"}]
 //! - /node/services/:service_id/health - Service Health
-//! eg. response 200 - healthy, 503 - unhealthy  
+//! eg. response 200 - healthy, 206 - partially healthy, 503 - unhealthy, 404 - unknown service
 
 use axum::{
+    extract::{Path, State},
     http::StatusCode,
     response::IntoResponse,
     routing::{get, Router},
@@ -26,8 +27,13 @@ use hyperlane_core::HyperlaneDomain;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::health::{HealthStatus, ValidatorHealth};
+
 const EIGEN_NODE_API_BASE: &str = "/eigen";
 
+const INDEXER_SERVICE_ID: &str = "hyperlane-validator-indexer";
+const SUBMITTER_SERVICE_ID: &str = "hyperlane-validator-submitter";
+
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 enum ServiceStatus {
     Up,
@@ -50,10 +56,22 @@ struct Service {
     status: ServiceStatus,
 }
 
-#[derive(new)]
+#[derive(new, Clone)]
 pub struct EigenNodeApi {
-    origin_chain: HyperlaneDomain,
     core_metrics: Arc<CoreMetrics>,
+    /// The health of every origin chain the validator signs for
+    origins: Vec<(HyperlaneDomain, Arc<ValidatorHealth>)>,
+}
+
+fn status_code(status: HealthStatus) -> StatusCode {
+    match status {
+        // 200 - healthy
+        HealthStatus::Healthy => StatusCode::OK,
+        // 206 - partially healthy
+        HealthStatus::PartiallyHealthy => StatusCode::PARTIAL_CONTENT,
+        // 503 - unhealthy
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    }
 }
 
 impl EigenNodeApi {
@@ -62,24 +80,42 @@ impl EigenNodeApi {
     }
 
     pub fn router(&self) -> Router {
-        let core_metrics_clone = self.core_metrics.clone();
-        let origin_chain = self.origin_chain.clone();
-
         tracing::info!("Serving the EigenNodeAPI routes...");
 
-        let health_route = get(move || {
-            Self::node_health_handler(origin_chain.clone(), core_metrics_clone.clone())
-        });
         let services_route = Router::new()
             .route("/", get(Self::node_services_handler))
             .route("/:service_id/health", get(Self::service_health_handler));
 
         let node_route = Router::new()
-            .route("/health", health_route)
+            .route("/health", get(Self::node_health_handler))
             .nest("/services", services_route)
             .route("/", get(Self::node_info_handler));
 
-        Router::new().nest("/node", node_route)
+        Router::new()
+            .nest("/node", node_route)
+            .with_state(self.clone())
+    }
+
+    /// A service is as healthy as it is on its least healthy origin chain
+    fn indexer_status(&self) -> HealthStatus {
+        self.origins
+            .iter()
+            .map(|(_, health)| health.indexer_status())
+            .max()
+            .unwrap_or(HealthStatus::Healthy)
+    }
+
+    fn submitter_status(&self) -> HealthStatus {
+        self.origins
+            .iter()
+            .map(|(origin_chain, health)| {
+                let checkpoint_delta = self
+                    .core_metrics
+                    .get_latest_checkpoint_validator_delta(origin_chain.clone());
+                health.submitter_status(checkpoint_delta)
+            })
+            .max()
+            .unwrap_or(HealthStatus::Healthy)
     }
 
     pub async fn node_info_handler() -> impl IntoResponse {
@@ -91,54 +127,49 @@ impl EigenNodeApi {
         Json(node_info)
     }
 
-    /// Method to return the NodeInfo data
-    /// if signed_checkpoint - observed_checkpoint <= 1 return 200 - healthy
-    /// else if observed_checkpoint - signed_checkpoint <= 10 return 203 - partially healthy
-    /// else return 503 - unhealthy
-    pub async fn node_health_handler(
-        origin_chain: HyperlaneDomain,
-        core_metrics: Arc<CoreMetrics>,
-    ) -> impl IntoResponse {
-        let checkpoint_delta = core_metrics.get_latest_checkpoint_validator_delta(origin_chain);
-
-        // logic to check if the node is healthy
-        if checkpoint_delta <= 1 {
-            // 200 - healthy
-            StatusCode::OK
-        } else if checkpoint_delta <= 10 {
-            // 206 - partially healthy
-            StatusCode::PARTIAL_CONTENT
-        } else {
-            // 503 - unhealthy
-            StatusCode::SERVICE_UNAVAILABLE
-        }
+    /// Method to return the health of the node, which is the health of its
+    /// least healthy service across all origin chains:
+    /// - the indexer is judged on how far it lags behind the origin chain tip
+    /// - the submitter is judged on how many observed checkpoints are unsigned,
+    ///   how long ago it last wrote a checkpoint, checkpoint syncer write
+    ///   failures and whether a reorg is reported in the checkpoint storage
+    pub async fn node_health_handler(State(api): State<EigenNodeApi>) -> impl IntoResponse {
+        status_code(api.indexer_status().max(api.submitter_status()))
     }
 
-    /// Method to return a list of services
-    /// NB: hardcoded for now
-    pub async fn node_services_handler() -> impl IntoResponse {
+    /// Method to return a list of services, which are down when unhealthy
+    pub async fn node_services_handler(State(api): State<EigenNodeApi>) -> impl IntoResponse {
+        let service_status = |status| match status {
+            HealthStatus::Unhealthy => ServiceStatus::Down,
+            _ => ServiceStatus::Up,
+        };
         let services = vec![
             Service {
-                id: "hyperlane-validator-indexer".to_string(),
+                id: INDEXER_SERVICE_ID.to_string(),
                 name: "indexer".to_string(),
                 description: "indexes the messages from the origin chain mailbox".to_string(),
-                status: ServiceStatus::Up,
+                status: service_status(api.indexer_status()),
             },
             Service {
-                id: "hyperlane-validator-submitter".to_string(),
+                id: SUBMITTER_SERVICE_ID.to_string(),
                 name: "submitter".to_string(),
                 description: "signs messages indexed from the indexer".to_string(),
-                status: ServiceStatus::Up,
+                status: service_status(api.submitter_status()),
             },
         ];
         Json(services)
     }
 
     /// Method to return the health of a service
-    pub async fn service_health_handler(_service_id: String) -> impl IntoResponse {
-        // TODO: implement logic to check if the service is healthy
-        // now just return 200
-        StatusCode::OK
+    pub async fn service_health_handler(
+        State(api): State<EigenNodeApi>,
+        Path(service_id): Path<String>,
+    ) -> impl IntoResponse {
+        match service_id.as_str() {
+            INDEXER_SERVICE_ID => status_code(api.indexer_status()),
+            SUBMITTER_SERVICE_ID => status_code(api.submitter_status()),
+            _ => StatusCode::NOT_FOUND,
+        }
     }
 }
 
@@ -148,12 +179,35 @@ mod tests {
 
     use super::*;
     use axum::http::StatusCode;
-    use prometheus::Registry;
+    use hyperlane_base::{CheckpointSyncer, LocalStorage};
+    use hyperlane_core::ReorgEvent;
+    use prometheus::{IntGauge, Opts, Registry};
 
     const PARTIALLY_HEALTHY_OBSERVED_CHECKPOINT: i64 = 34;
     const HEALTHY_OBSERVED_CHECKPOINT: i64 = 42;
 
+    /// Persists a reorg to a checkpoint storage and refreshes `health` from it
+    async fn report_reorg(health: &ValidatorHealth) {
+        let storage_dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(storage_dir.path().to_owned(), None).unwrap();
+        storage
+            .write_reorg_status(&ReorgEvent::default())
+            .await
+            .unwrap();
+        health.refresh_reorg_status(&storage).await;
+    }
+
     async fn setup_test_server() -> (reqwest::Client, SocketAddr, Arc<CoreMetrics>) {
+        let (client, addr, core_metrics, _) = setup_test_server_with_health().await;
+        (client, addr, core_metrics)
+    }
+
+    async fn setup_test_server_with_health() -> (
+        reqwest::Client,
+        SocketAddr,
+        Arc<CoreMetrics>,
+        Arc<ValidatorHealth>,
+    ) {
         let core_metrics =
             Arc::new(CoreMetrics::new("dummy_validator", 37582, Registry::new()).unwrap());
         // Initialize the Prometheus registry
@@ -162,9 +216,17 @@ mod tests {
             .with_label_values(&["validator_observed", "ethereum"])
             .set(HEALTHY_OBSERVED_CHECKPOINT);
 
+        let health = Arc::new(ValidatorHealth::new(
+            IntGauge::with_opts(Opts::new("indexed_height", "indexed height")).unwrap(),
+            IntGauge::with_opts(Opts::new("chain_height", "chain height")).unwrap(),
+        ));
+
         let node_api = EigenNodeApi::new(
-            HyperlaneDomain::new_test_domain("ethereum"),
             Arc::clone(&core_metrics),
+            vec![(
+                HyperlaneDomain::new_test_domain("ethereum"),
+                Arc::clone(&health),
+            )],
         );
         let app = node_api.router();
 
@@ -177,7 +239,7 @@ mod tests {
         // Create a client
         let client = reqwest::Client::new();
 
-        (client, addr, core_metrics)
+        (client, addr, core_metrics, health)
     }
 
     #[tokio::test]
@@ -239,7 +301,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_eigen_node_services_handler() {
-        let (client, addr, _) = setup_test_server().await;
+        let (client, addr, core_metrics) = setup_test_server().await;
+        core_metrics
+            .latest_checkpoint()
+            .with_label_values(&["validator_processed", "ethereum"])
+            .set(HEALTHY_OBSERVED_CHECKPOINT);
         let res = client
             .get(format!("http://{}/node/services", addr))
             .send()
@@ -280,4 +346,83 @@ mod tests {
         // Check that the response status is OK
         assert_eq!(res.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_service_health_reflects_validator_state() {
+        let (client, addr, core_metrics, health) = setup_test_server_with_health().await;
+        core_metrics
+            .latest_checkpoint()
+            .with_label_values(&["validator_processed", "ethereum"])
+            .set(HEALTHY_OBSERVED_CHECKPOINT);
+        let service_health = |service_id: &'static str| {
+            client
+                .get(format!(
+                    "http://{}/node/services/{}/health",
+                    addr, service_id
+                ))
+                .send()
+        };
+
+        assert_eq!(
+            service_health(SUBMITTER_SERVICE_ID).await.unwrap().status(),
+            StatusCode::OK
+        );
+        report_reorg(&health).await;
+        assert_eq!(
+            service_health(SUBMITTER_SERVICE_ID).await.unwrap().status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        // The indexer is unaffected by the submitter's health
+        assert_eq!(
+            service_health(INDEXER_SERVICE_ID).await.unwrap().status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            service_health("unknown").await.unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let res = client
+            .get(format!("http://{}/node/health", addr))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_node_health_across_origin_chains() {
+        let core_metrics =
+            Arc::new(CoreMetrics::new("dummy_validator", 37582, Registry::new()).unwrap());
+        let origins = ["ethereum", "polygon"]
+            .into_iter()
+            .map(|chain| {
+                core_metrics
+                    .latest_checkpoint()
+                    .with_label_values(&["validator_observed", chain])
+                    .set(HEALTHY_OBSERVED_CHECKPOINT);
+                core_metrics
+                    .latest_checkpoint()
+                    .with_label_values(&["validator_processed", chain])
+                    .set(HEALTHY_OBSERVED_CHECKPOINT);
+                let health = Arc::new(ValidatorHealth::new(
+                    IntGauge::with_opts(Opts::new("indexed_height", "indexed height")).unwrap(),
+                    IntGauge::with_opts(Opts::new("chain_height", "chain height")).unwrap(),
+                ));
+                (HyperlaneDomain::new_test_domain(chain), health)
+            })
+            .collect::<Vec<_>>();
+        let node_api = EigenNodeApi::new(core_metrics.clone(), origins.clone());
+        assert_eq!(node_api.submitter_status(), HealthStatus::Healthy);
+
+        // A single lagging origin chain degrades the whole node
+        core_metrics
+            .latest_checkpoint()
+            .with_label_values(&["validator_processed", "polygon"])
+            .set(PARTIALLY_HEALTHY_OBSERVED_CHECKPOINT);
+        assert_eq!(node_api.submitter_status(), HealthStatus::PartiallyHealthy);
+        report_reorg(&origins[0].1).await;
+        assert_eq!(node_api.submitter_status(), HealthStatus::Unhealthy);
+        assert_eq!(node_api.indexer_status(), HealthStatus::Healthy);
+    }
 }
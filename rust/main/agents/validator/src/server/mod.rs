@@ -7,13 +7,15 @@ pub use eigen_node::EigenNodeApi;
 use hyperlane_base::CoreMetrics;
 use hyperlane_core::HyperlaneDomain;
 
+use crate::health::ValidatorHealth;
+
 /// Returns a vector of validator-specific endpoint routes to be served.
 /// Can be extended with additional routes and feature flags to enable/disable individually.
 pub fn routes(
-    origin_chain: HyperlaneDomain,
     metrics: Arc<CoreMetrics>,
+    origins: Vec<(HyperlaneDomain, Arc<ValidatorHealth>)>,
 ) -> Vec<(&'static str, Router)> {
-    let eigen_node_api = EigenNodeApi::new(origin_chain, metrics);
+    let eigen_node_api = EigenNodeApi::new(metrics, origins);
 
     vec![eigen_node_api.get_route()]
 }
@@ -4,7 +4,7 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, path::PathBuf, time::Duration};
+use std::{collections::HashSet, env, path::PathBuf, time::Duration};
 
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
 use eyre::{eyre, Context};
@@ -14,6 +14,7 @@ use hyperlane_base::{
         parser::{RawAgentConf, RawAgentSignerConf, ValueParser},
         CheckpointSyncerConf, Settings, SignerConf,
     },
+    AZURE_STORAGE_SAS_TOKEN,
 };
 use hyperlane_core::{
     cfg_unwrap_all, config::*, HyperlaneDomain, HyperlaneDomainProtocol, ReorgPeriod,
@@ -30,20 +31,32 @@ pub struct ValidatorSettings {
     #[deref_mut]
     base: Settings,
 
-    /// Database path
-    pub db: PathBuf,
-    /// Chain to validate messages on
-    pub origin_chain: HyperlaneDomain,
+    /// Chains to validate messages on
+    pub origin_chains: Vec<OriginChainSettings>,
     /// The validator attestation signer
     pub validator: SignerConf,
-    /// The checkpoint syncer configuration
-    pub checkpoint_syncer: CheckpointSyncerConf,
-    /// The reorg configuration
-    pub reorg_period: ReorgPeriod,
+    /// How frequently to backfill lagging checkpoint syncers
+    pub checkpoint_syncer_reconcile_interval: Duration,
     /// How frequently to check for new checkpoints
     pub interval: Duration,
 }
 
+/// Settings for validating messages on a single origin chain
+#[derive(Debug)]
+pub struct OriginChainSettings {
+    /// Chain to validate messages on
+    pub origin_chain: HyperlaneDomain,
+    /// Database path. Chains may share a database, which is namespaced by domain.
+    pub db: PathBuf,
+    /// The checkpoint syncers checkpoints are mirrored to. The first one is
+    /// the primary syncer.
+    pub checkpoint_syncers: Vec<CheckpointSyncerConf>,
+    /// How many checkpoint syncers a write must succeed on
+    pub checkpoint_syncer_quorum: usize,
+    /// The reorg configuration
+    pub reorg_period: ReorgPeriod,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(transparent)]
 struct RawValidatorSettings(Value);
@@ -60,13 +73,30 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
 
         let p = ValueParser::new(cwp.clone(), &raw.0);
 
-        let origin_chain_name = p
+        // A comma separated list, so that one process can validate several chains
+        let origin_chain_names: Option<Vec<&str>> = p
             .chain(&mut err)
             .get_key("originChainName")
             .parse_string()
-            .end();
+            .end()
+            .map(|v| v.split(',').map(str::trim).collect());
+        if origin_chain_names
+            .as_ref()
+            .is_some_and(|names| names.iter().any(|name| name.is_empty()))
+        {
+            Err::<(), _>(eyre!("Empty chain in originChainName"))
+                .take_err(&mut err, || cwp + "origin_chain_name");
+        }
 
-        let origin_chain_name_set = origin_chain_name.map(|s| HashSet::from([s]));
+        let origin_chain_name_set: Option<HashSet<&str>> = origin_chain_names
+            .as_ref()
+            .map(|names| names.iter().copied().collect());
+        if let (Some(names), Some(name_set)) = (&origin_chain_names, &origin_chain_name_set) {
+            if names.len() != name_set.len() {
+                Err::<(), _>(eyre!("Duplicate chain in originChainName"))
+                    .take_err(&mut err, || cwp + "origin_chain_name");
+            }
+        }
 
         let base: Option<Settings> = p
             .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
@@ -75,15 +105,6 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             )
             .take_config_err(&mut err);
 
-        let origin_chain = if let (Some(base), Some(origin_chain_name)) = (&base, origin_chain_name)
-        {
-            base.lookup_domain(origin_chain_name)
-                .context("Missing configuration for the origin chain")
-                .take_err(&mut err, || cwp + "origin_chain_name")
-        } else {
-            None
-        };
-
         let validator = p
             .chain(&mut err)
             .get_key("validator")
@@ -93,21 +114,23 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             )
             .end();
 
-        let db = p
+        // With several origin chains, a top level database is shared by the
+        // chains that don't set their own
+        let default_db: Option<PathBuf> = p
             .chain(&mut err)
             .get_opt_key("db")
             .parse_from_str("Expected db file path")
-            .unwrap_or_else(|| {
-                std::env::current_dir()
-                    .unwrap()
-                    .join(format!("validator_db_{}", origin_chain_name.unwrap_or("")))
-            });
+            .end();
+
+        let (default_checkpoint_syncers, default_checkpoint_syncer_quorum) =
+            parse_checkpoint_syncers(&p, &mut err);
 
-        let checkpoint_syncer = p
+        let checkpoint_syncer_reconcile_interval = p
             .chain(&mut err)
-            .get_key("checkpointSyncer")
-            .and_then(parse_checkpoint_syncer)
-            .end();
+            .get_opt_key("checkpointSyncerReconcileInterval")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(60));
 
         let interval = p
             .chain(&mut err)
@@ -116,40 +139,156 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             .map(Duration::from_secs)
             .unwrap_or(Duration::from_secs(5));
 
-        cfg_unwrap_all!(cwp, err: [origin_chain_name]);
+        cfg_unwrap_all!(cwp, err: [origin_chain_names]);
 
-        let reorg_period = p
-            .chain(&mut err)
-            .get_key("chains")
-            .get_key(origin_chain_name)
-            .get_opt_key("blocks")
-            .get_opt_key("reorgPeriod")
-            .parse_value("Invalid reorgPeriod")
-            .unwrap_or(ReorgPeriod::from_blocks(1));
+        let mut origin_chains = vec![];
+        for origin_chain_name in &origin_chain_names {
+            let origin_chain = base.as_ref().and_then(|base| {
+                base.lookup_domain(origin_chain_name)
+                    .context("Missing configuration for the origin chain")
+                    .take_err(&mut err, || cwp + "origin_chain_name")
+            });
+
+            // Each chain keeps its own checkpoint syncers, as they may not be
+            // shared between chains. The top level ones can only be used when
+            // validating a single chain.
+            let overrides = p
+                .chain(&mut err)
+                .get_opt_key("originChains")
+                .get_opt_key(origin_chain_name)
+                .end();
+            let (checkpoint_syncers, checkpoint_syncer_quorum) = match &overrides {
+                Some(overrides) => parse_checkpoint_syncers(overrides, &mut err),
+                None if origin_chain_names.len() == 1 => (
+                    default_checkpoint_syncers.clone(),
+                    default_checkpoint_syncer_quorum,
+                ),
+                None => (vec![], None),
+            };
+            let syncers_cwp = || match &overrides {
+                Some(overrides) => overrides.cwp.clone(),
+                None => cwp.clone(),
+            };
+            if checkpoint_syncers.is_empty() {
+                let msg = if origin_chain_names.len() == 1 {
+                    "Expected at least one of checkpointSyncer or checkpointSyncers"
+                } else {
+                    "Expected each origin chain to set its own checkpoint syncers in originChains"
+                };
+                Err::<(), _>(eyre!(msg)).take_err(&mut err, || syncers_cwp() + "checkpoint_syncer");
+            }
+
+            let checkpoint_syncer_quorum =
+                checkpoint_syncer_quorum.unwrap_or(checkpoint_syncers.len());
+            if !checkpoint_syncers.is_empty()
+                && !(1..=checkpoint_syncers.len()).contains(&checkpoint_syncer_quorum)
+            {
+                Err::<(), _>(eyre!(
+                    "Checkpoint syncer quorum must be between 1 and the number of checkpoint syncers"
+                ))
+                .take_err(&mut err, || syncers_cwp() + "checkpoint_syncer_quorum");
+            }
+
+            // Defaults to the database a validator of only this chain would use
+            let db = overrides
+                .as_ref()
+                .and_then(|overrides| {
+                    overrides
+                        .chain(&mut err)
+                        .get_opt_key("db")
+                        .parse_from_str("Expected db file path")
+                        .end()
+                })
+                .or_else(|| default_db.clone())
+                .unwrap_or_else(|| {
+                    std::env::current_dir()
+                        .unwrap()
+                        .join(format!("validator_db_{origin_chain_name}"))
+                });
+
+            let reorg_period = p
+                .chain(&mut err)
+                .get_key("chains")
+                .get_key(origin_chain_name)
+                .get_opt_key("blocks")
+                .get_opt_key("reorgPeriod")
+                .parse_value("Invalid reorgPeriod")
+                .unwrap_or(ReorgPeriod::from_blocks(1));
 
-        cfg_unwrap_all!(cwp, err: [base, origin_chain, validator, checkpoint_syncer]);
+            if let Some(origin_chain) = origin_chain {
+                origin_chains.push(OriginChainSettings {
+                    origin_chain,
+                    db,
+                    checkpoint_syncers,
+                    checkpoint_syncer_quorum,
+                    reorg_period,
+                });
+            }
+        }
+
+        cfg_unwrap_all!(cwp, err: [base, validator]);
 
         let mut base: Settings = base;
-        // If the origin chain is an EVM chain, then we can use the validator as the signer if needed.
-        if origin_chain.domain_protocol() == HyperlaneDomainProtocol::Ethereum {
-            if let Some(origin) = base.chains.get_mut(origin_chain.name()) {
-                origin.signer.get_or_insert_with(|| validator.clone());
+        // If an origin chain is an EVM chain, then we can use the validator as the signer if needed.
+        for OriginChainSettings { origin_chain, .. } in &origin_chains {
+            if origin_chain.domain_protocol() == HyperlaneDomainProtocol::Ethereum {
+                if let Some(origin) = base.chains.get_mut(origin_chain.name()) {
+                    origin.signer.get_or_insert_with(|| validator.clone());
+                }
             }
         }
 
         err.into_result(Self {
             base,
-            db,
-            origin_chain,
+            origin_chains,
             validator,
-            checkpoint_syncer,
-            reorg_period,
+            checkpoint_syncer_reconcile_interval,
             interval,
         })
     }
 }
 
-/// Expects ValidatorAgentConfig.checkpointSyncer
+/// Expects the checkpointSyncer, checkpointSyncers and checkpointSyncerQuorum
+/// keys of either ValidatorAgentConfig or an entry of
+/// ValidatorAgentConfig.originChains
+fn parse_checkpoint_syncers(
+    p: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> (Vec<CheckpointSyncerConf>, Option<usize>) {
+    // `checkpointSyncer` is kept as the primary syncer, with any
+    // `checkpointSyncers` mirroring it
+    let checkpoint_syncers = p
+        .chain(err)
+        .get_opt_key("checkpointSyncer")
+        .and_then(parse_checkpoint_syncer)
+        .end()
+        .into_iter()
+        .chain(
+            p.chain(err)
+                .get_opt_key("checkpointSyncers")
+                .into_array_iter()
+                .map(|itr| {
+                    itr.filter_map(|syncer| {
+                        syncer.chain(err).and_then(parse_checkpoint_syncer).end()
+                    })
+                    .collect::<Vec<_>>()
+                })
+                .unwrap_or_default(),
+        )
+        .collect::<Vec<_>>();
+
+    let checkpoint_syncer_quorum = p
+        .chain(err)
+        .get_opt_key("checkpointSyncerQuorum")
+        .parse_u64()
+        .end()
+        .map(|quorum| quorum as usize);
+
+    (checkpoint_syncers, checkpoint_syncer_quorum)
+}
+
+/// Expects ValidatorAgentConfig.checkpointSyncer or an entry of
+/// ValidatorAgentConfig.checkpointSyncers
 fn parse_checkpoint_syncer(syncer: ValueParser) -> ConfigResult<CheckpointSyncerConf> {
     let mut err = ConfigParsingError::default();
     let syncer_type = syncer.chain(&mut err).get_key("type").parse_string().end();
@@ -224,6 +363,41 @@ fn parse_checkpoint_syncer(syncer: ValueParser) -> ConfigResult<CheckpointSyncer
                 user_secrets,
             })
         }
+        Some("azure") => {
+            let account = syncer
+                .chain(&mut err)
+                .get_key("account")
+                .parse_string()
+                .end()
+                .map(str::to_owned);
+            let container = syncer
+                .chain(&mut err)
+                .get_key("container")
+                .parse_string()
+                .end()
+                .map(str::to_owned);
+            let folder = syncer
+                .chain(&mut err)
+                .get_opt_key("folder")
+                .parse_string()
+                .end()
+                .map(str::to_owned);
+            let sas_token = syncer
+                .chain(&mut err)
+                .get_opt_key("sasToken")
+                .parse_string()
+                .end()
+                .map(str::to_owned)
+                .or_else(|| env::var(AZURE_STORAGE_SAS_TOKEN).ok());
+
+            cfg_unwrap_all!(&syncer.cwp, err: [account, container]);
+            err.into_result(CheckpointSyncerConf::Azure {
+                account,
+                container,
+                folder,
+                sas_token,
+            })
+        }
         Some(_) => {
             Err(eyre!("Unknown checkpoint syncer type")).into_config_result(|| &syncer.cwp + "type")
         }
@@ -16,6 +16,8 @@ use hyperlane_core::{
 use hyperlane_core::{ChainResult, MerkleTreeHook, ReorgEvent, ReorgPeriod};
 use hyperlane_ethereum::SingletonSignerHandle;
 
+use crate::health::ValidatorHealth;
+
 #[derive(Clone)]
 pub(crate) struct ValidatorSubmitter {
     interval: Duration,
@@ -25,6 +27,7 @@ pub(crate) struct ValidatorSubmitter {
     checkpoint_syncer: Arc<dyn CheckpointSyncer>,
     db: Arc<dyn HyperlaneDb>,
     metrics: ValidatorSubmitterMetrics,
+    health: Arc<ValidatorHealth>,
 }
 
 impl ValidatorSubmitter {
@@ -36,6 +39,7 @@ impl ValidatorSubmitter {
         checkpoint_syncer: Arc<dyn CheckpointSyncer>,
         db: Arc<dyn HyperlaneDb>,
         metrics: ValidatorSubmitterMetrics,
+        health: Arc<ValidatorHealth>,
     ) -> Self {
         Self {
             reorg_period,
@@ -45,6 +49,7 @@ impl ValidatorSubmitter {
             checkpoint_syncer,
             db,
             metrics,
+            health,
         }
     }
 
@@ -231,6 +236,9 @@ impl ValidatorSubmitter {
                     e
                 ));
             }
+            self.health
+                .refresh_reorg_status(&*self.checkpoint_syncer)
+                .await;
             panic!("{panic_message}");
         }
 
@@ -264,7 +272,9 @@ impl ValidatorSubmitter {
         let signed_checkpoint = self.signer.sign(checkpoint).await?;
         self.checkpoint_syncer
             .write_checkpoint(&signed_checkpoint)
-            .await?;
+            .await
+            .inspect_err(|_| self.health.record_write_failure())?;
+        self.health.record_checkpoint_written();
         debug!(index = checkpoint.index, "Signed and submitted checkpoint");
 
         // TODO: move these into S3 implementations
@@ -299,7 +309,8 @@ impl ValidatorSubmitter {
                 self_clone
                     .checkpoint_syncer
                     .update_latest_index(last_checkpoint.index)
-                    .await?;
+                    .await
+                    .inspect_err(|_| self_clone.health.record_write_failure())?;
                 Ok(())
             })
         })
@@ -346,8 +357,9 @@ mod test {
     use hyperlane_core::{
         test_utils::dummy_domain, GasPaymentKey, HyperlaneChain, HyperlaneContract,
         HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, InterchainGasPayment,
-        InterchainGasPaymentMeta, MerkleTreeHook, MerkleTreeInsertion, PendingOperationStatus,
-        ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId, H160, H256,
+        InterchainGasPaymentMeta, MerkleTreeHook, MerkleTreeInsertion, OperationLifecycleLog,
+        PendingOperationStatus, ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId,
+        H160, H256,
     };
     use prometheus::Registry;
     use std::{fmt::Debug, sync::Arc, time::Duration};
@@ -405,6 +417,15 @@ mod test {
                 &self,
                 message_id: &H256,
             ) -> DbResult<Option<PendingOperationStatus>>;
+            fn store_operation_lifecycle_by_message_id(
+                &self,
+                message_id: &H256,
+                log: &OperationLifecycleLog,
+            ) -> DbResult<()>;
+            fn retrieve_operation_lifecycle_by_message_id(
+                &self,
+                message_id: &H256,
+            ) -> DbResult<Option<OperationLifecycleLog>>;
             fn store_interchain_gas_payment_data_by_gas_payment_key(
                 &self,
                 key: &GasPaymentKey,
@@ -619,6 +640,11 @@ mod test {
                 );
                 Ok(())
             });
+        // the health is refreshed from the persisted reorg status
+        mock_checkpoint_syncer
+            .expect_reorg_status()
+            .once()
+            .returning(|| Ok(Some(ReorgEvent::default())));
 
         // instantiate the validator submitter
         let validator_submitter = ValidatorSubmitter::new(
@@ -629,6 +655,10 @@ mod test {
             Arc::new(mock_checkpoint_syncer),
             Arc::new(db),
             dummy_metrics(),
+            Arc::new(ValidatorHealth::new(
+                IntGauge::new("indexed_height", "indexed height").unwrap(),
+                IntGauge::new("chain_height", "chain height").unwrap(),
+            )),
         );
 
         // mock the correctness checkpoint response
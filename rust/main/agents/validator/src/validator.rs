@@ -1,9 +1,14 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::server as validator_server;
 use async_trait::async_trait;
 use derive_more::AsRef;
-use eyre::Result;
+use eyre::{bail, Result};
 
 use futures_util::future::try_join_all;
 use tokio::{task::JoinHandle, time::sleep};
@@ -14,8 +19,8 @@ use hyperlane_base::{
     metrics::AgentMetrics,
     settings::ChainConf,
     AgentMetadata, BaseAgent, ChainMetrics, ChainSpecificMetricsUpdater, CheckpointSyncer,
-    ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore, RuntimeMetrics,
-    SequencedDataContractSync,
+    ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore, MirroredCheckpointSyncer,
+    RuntimeMetrics, SequencedDataContractSync,
 };
 
 use hyperlane_core::{
@@ -26,33 +31,63 @@ use hyperlane_core::{
 use hyperlane_ethereum::{SingletonSigner, SingletonSignerHandle};
 
 use crate::{
+    audit::{CheckpointAuditMode, CheckpointAuditor},
+    health::ValidatorHealth,
     settings::ValidatorSettings,
     submit::{ValidatorSubmitter, ValidatorSubmitterMetrics},
 };
 
+/// The label the merkle tree hook sync reports its metrics under
+const MERKLE_TREE_HOOK_SYNC_LABEL: &str = "merkle_tree_hook";
+
+/// Longest delay between retries of a failed startup step of an origin, such as
+/// announcing or a merkle tree hook count request
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// How often the reorg status persisted to the checkpoint storage is read
+const REORG_STATUS_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
 /// A validator agent
 #[derive(Debug, AsRef)]
 pub struct Validator {
-    origin_chain: HyperlaneDomain,
-    origin_chain_conf: ChainConf,
     #[as_ref]
     core: HyperlaneAgentCore,
+    origins: Vec<OriginValidator>,
+    // temporary holder until `run` is called
+    signer_instance: Option<Box<SingletonSigner>>,
+    core_metrics: Arc<CoreMetrics>,
+    agent_metrics: AgentMetrics,
+    chain_metrics: ChainMetrics,
+    runtime_metrics: RuntimeMetrics,
+}
+
+/// Audits the checkpoints the validator published instead of validating, when
+/// the validator is run as `validator audit <report|repair>`
+#[derive(Debug)]
+pub struct ValidatorAudit {
+    validator: Validator,
+    mode: CheckpointAuditMode,
+}
+
+/// Indexes and signs checkpoints for a single origin chain. The signer is
+/// shared with the other origin chains of the validator.
+#[derive(Debug)]
+struct OriginValidator {
+    origin_chain: HyperlaneDomain,
+    origin_chain_conf: ChainConf,
     db: HyperlaneRocksDB,
     merkle_tree_hook_sync: Arc<SequencedDataContractSync<MerkleTreeInsertion>>,
     mailbox: Arc<dyn Mailbox>,
     merkle_tree_hook: Arc<dyn MerkleTreeHook>,
     validator_announce: Arc<dyn ValidatorAnnounce>,
     signer: SingletonSignerHandle,
-    // temporary holder until `run` is called
-    signer_instance: Option<Box<SingletonSigner>>,
     reorg_period: ReorgPeriod,
     interval: Duration,
-    checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+    checkpoint_syncer: Arc<MirroredCheckpointSyncer>,
+    checkpoint_syncer_reconcile_interval: Duration,
     core_metrics: Arc<CoreMetrics>,
-    agent_metrics: AgentMetrics,
-    chain_metrics: ChainMetrics,
-    runtime_metrics: RuntimeMetrics,
-    agent_metadata: AgentMetadata,
+    agent_metadata: Arc<AgentMetadata>,
+    health: Arc<ValidatorHealth>,
 }
 
 #[async_trait]
@@ -73,71 +108,100 @@ impl BaseAgent for Validator {
     where
         Self: Sized,
     {
-        let db = DB::from_path(&settings.db)?;
-        let msg_db = HyperlaneRocksDB::new(&settings.origin_chain, db);
+        // Origin chains may share a database, which is namespaced by domain
+        let mut dbs: HashMap<PathBuf, DB> = HashMap::new();
 
         // Intentionally using hyperlane_ethereum for the validator's signer
         let (signer_instance, signer) = SingletonSigner::new(settings.validator.build().await?);
 
         let core = settings.build_hyperlane_core(metrics.clone());
-        // Be extra sure to panic checkpoint syncer fails, which indicates
-        // a fatal startup error.
-        let checkpoint_syncer = settings
-            .checkpoint_syncer
-            .build_and_validate(None)
-            .await
-            .expect("Failed to build checkpoint syncer")
-            .into();
-
-        let mailbox = settings
-            .build_mailbox(&settings.origin_chain, &metrics)
-            .await?;
-
-        let merkle_tree_hook = settings
-            .build_merkle_tree_hook(&settings.origin_chain, &metrics)
-            .await?;
-
-        let validator_announce = settings
-            .build_validator_announce(&settings.origin_chain, &metrics)
-            .await?;
-
-        let origin_chain_conf = core
-            .settings
-            .chain_setup(&settings.origin_chain)
-            .unwrap()
-            .clone();
-
+        let agent_metadata = Arc::new(agent_metadata);
         let contract_sync_metrics = Arc::new(ContractSyncMetrics::new(&metrics));
 
-        let merkle_tree_hook_sync = settings
-            .sequenced_contract_sync::<MerkleTreeInsertion, _>(
-                &settings.origin_chain,
-                &metrics,
-                &contract_sync_metrics,
-                msg_db.clone().into(),
-                false,
-            )
-            .await?;
+        let mut origins = vec![];
+        for origin in &settings.origin_chains {
+            let origin_chain = &origin.origin_chain;
+            let db = match dbs.entry(origin.db.clone()) {
+                Entry::Occupied(entry) => entry.get().clone(),
+                Entry::Vacant(entry) => entry.insert(DB::from_path(&origin.db)?).clone(),
+            };
+            let msg_db = HyperlaneRocksDB::new(origin_chain, db);
+
+            // Be extra sure to panic checkpoint syncer fails, which indicates
+            // a fatal startup error.
+            let mut checkpoint_syncers = vec![];
+            for checkpoint_syncer in &origin.checkpoint_syncers {
+                let checkpoint_syncer: Arc<dyn CheckpointSyncer> = checkpoint_syncer
+                    .build_and_validate(None)
+                    .await
+                    .expect("Failed to build checkpoint syncer")
+                    .into();
+                checkpoint_syncers.push(checkpoint_syncer);
+            }
+            let checkpoint_syncer = Arc::new(
+                MirroredCheckpointSyncer::new(checkpoint_syncers, origin.checkpoint_syncer_quorum)
+                    .expect("Failed to build checkpoint syncer"),
+            );
+
+            let mailbox = settings.build_mailbox(origin_chain, &metrics).await?;
+
+            let merkle_tree_hook = settings
+                .build_merkle_tree_hook(origin_chain, &metrics)
+                .await?;
+
+            let validator_announce = settings
+                .build_validator_announce(origin_chain, &metrics)
+                .await?;
+
+            let origin_chain_conf = core.settings.chain_setup(origin_chain).unwrap().clone();
+
+            let health = Arc::new(ValidatorHealth::new(
+                contract_sync_metrics
+                    .indexed_height
+                    .with_label_values(&[MERKLE_TREE_HOOK_SYNC_LABEL, origin_chain.name()]),
+                chain_metrics
+                    .block_height
+                    .with_label_values(&[origin_chain.name()]),
+            ));
+            health.refresh_reorg_status(&*checkpoint_syncer).await;
+
+            let merkle_tree_hook_sync = settings
+                .sequenced_contract_sync::<MerkleTreeInsertion, _>(
+                    origin_chain,
+                    &metrics,
+                    &contract_sync_metrics,
+                    msg_db.clone().into(),
+                    false,
+                )
+                .await?;
+
+            origins.push(OriginValidator {
+                origin_chain: origin_chain.clone(),
+                origin_chain_conf,
+                db: msg_db,
+                merkle_tree_hook_sync,
+                mailbox: mailbox.into(),
+                merkle_tree_hook: merkle_tree_hook.into(),
+                validator_announce: validator_announce.into(),
+                signer: signer.clone(),
+                reorg_period: origin.reorg_period.clone(),
+                interval: settings.interval,
+                checkpoint_syncer,
+                checkpoint_syncer_reconcile_interval: settings.checkpoint_syncer_reconcile_interval,
+                core_metrics: metrics.clone(),
+                agent_metadata: agent_metadata.clone(),
+                health,
+            });
+        }
 
         Ok(Self {
-            origin_chain: settings.origin_chain,
-            origin_chain_conf,
             core,
-            db: msg_db,
-            mailbox: mailbox.into(),
-            merkle_tree_hook: merkle_tree_hook.into(),
-            merkle_tree_hook_sync,
-            validator_announce: validator_announce.into(),
-            signer,
+            origins,
             signer_instance: Some(Box::new(signer_instance)),
-            reorg_period: settings.reorg_period,
-            interval: settings.interval,
-            checkpoint_syncer,
             agent_metrics,
             chain_metrics,
             core_metrics: metrics,
             runtime_metrics,
-            agent_metadata,
         })
     }
 
@@ -146,8 +210,13 @@ impl BaseAgent for Validator {
         let mut tasks = vec![];
 
         // run server
-        let custom_routes =
-            validator_server::routes(self.origin_chain.clone(), self.core.metrics.clone());
+        let custom_routes = validator_server::routes(
+            self.core.metrics.clone(),
+            self.origins
+                .iter()
+                .map(|origin| (origin.origin_chain.clone(), origin.health.clone()))
+                .collect(),
+        );
         let server = self
             .core
             .settings
@@ -168,36 +237,131 @@ impl BaseAgent for Validator {
             );
         }
 
-        let metrics_updater = ChainSpecificMetricsUpdater::new(
-            &self.origin_chain_conf,
-            self.core_metrics.clone(),
-            self.agent_metrics.clone(),
-            self.chain_metrics.clone(),
-            Self::AGENT_NAME.to_string(),
+        for origin in std::mem::take(&mut self.origins) {
+            let metrics_updater = ChainSpecificMetricsUpdater::new(
+                &origin.origin_chain_conf,
+                self.core_metrics.clone(),
+                self.agent_metrics.clone(),
+                self.chain_metrics.clone(),
+                Self::AGENT_NAME.to_string(),
+            )
+            .await
+            .unwrap();
+            tasks.push(
+                tokio::spawn(async move {
+                    metrics_updater.spawn().await.unwrap();
+                })
+                .instrument(info_span!("MetricsUpdater")),
+            );
+
+            let span = info_span!("OriginValidator", origin = %origin.origin_chain);
+            tasks.push(tokio::spawn(origin.run()).instrument(span));
+        }
+        tasks.push(self.runtime_metrics.spawn());
+
+        // Note that this only returns an error if one of the tasks panics
+        if let Err(err) = try_join_all(tasks).await {
+            error!(?err, "One of the validator tasks returned an error");
+        }
+    }
+}
+
+#[async_trait]
+impl BaseAgent for ValidatorAudit {
+    const AGENT_NAME: &'static str = Validator::AGENT_NAME;
+
+    type Settings = ValidatorSettings;
+
+    async fn from_settings(
+        agent_metadata: AgentMetadata,
+        settings: Self::Settings,
+        metrics: Arc<CoreMetrics>,
+        agent_metrics: AgentMetrics,
+        chain_metrics: ChainMetrics,
+        runtime_metrics: RuntimeMetrics,
+        tokio_console_server: console_subscriber::Server,
+    ) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let Some(mode) = CheckpointAuditMode::from_args(std::env::args().skip(1))? else {
+            bail!("The audit must be run as `validator audit <report|repair>`");
+        };
+        let validator = Validator::from_settings(
+            agent_metadata,
+            settings,
+            metrics,
+            agent_metrics,
+            chain_metrics,
+            runtime_metrics,
+            tokio_console_server,
         )
-        .await
-        .unwrap();
-        tasks.push(
-            tokio::spawn(async move {
-                metrics_updater.spawn().await.unwrap();
-            })
-            .instrument(info_span!("MetricsUpdater")),
-        );
+        .await?;
+        Ok(Self { validator, mode })
+    }
 
+    /// Audits the published checkpoints of every origin chain and logs a
+    /// report for each of them
+    #[allow(clippy::async_yields_async)]
+    async fn run(mut self) {
+        if let Some(signer_instance) = self.validator.signer_instance.take() {
+            tokio::spawn(
+                signer_instance
+                    .run()
+                    .instrument(info_span!("SingletonSigner")),
+            );
+        }
+        for origin in &self.validator.origins {
+            let auditor = CheckpointAuditor::new(
+                origin.checkpoint_syncer.clone(),
+                origin.merkle_tree_hook.clone(),
+                Arc::new(origin.db.clone()),
+                origin.signer.clone(),
+                origin.reorg_period.clone(),
+            );
+            match auditor.audit(self.mode).await {
+                Ok(report) => {
+                    let report =
+                        serde_json::to_string(&report).expect("Failed to serialize audit report");
+                    info!(origin = %origin.origin_chain, %report, "Checkpoint audit report");
+                }
+                Err(err) => {
+                    error!(?err, origin = %origin.origin_chain, "Failed to audit checkpoints")
+                }
+            }
+        }
+    }
+}
+
+impl OriginValidator {
+    async fn run(self) {
+        // Failures are retried rather than fatal, so they don't stop the other origins
         // report agent metadata
-        self.metadata()
-            .await
-            .expect("Failed to report agent metadata");
+        let mut retry_delay = self.interval;
+        while let Err(err) = self.metadata().await {
+            error!(?err, origin = %self.origin_chain, ?retry_delay, "Failed to report agent metadata, retrying");
+            sleep(retry_delay).await;
+            retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+        }
 
         // announce the validator after spawning the signer task
-        self.announce().await.expect("Failed to announce validator");
+        let mut retry_delay = self.interval;
+        while let Err(err) = self.announce().await {
+            error!(?err, origin = %self.origin_chain, ?retry_delay, "Failed to announce validator, retrying");
+            sleep(retry_delay).await;
+            retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+        }
+
+        let mut tasks = vec![self.run_reorg_status_poller()];
 
         // Ensure that the merkle tree hook has count > 0 before we begin indexing
         // messages or submitting checkpoints.
+        let mut count_retry_delay = self.interval;
         loop {
             match self.merkle_tree_hook.count(&self.reorg_period).await {
                 Ok(0) => {
-                    info!("Waiting for first message in merkle tree hook");
+                    info!(origin = %self.origin_chain, "Waiting for first message in merkle tree hook");
+                    count_retry_delay = self.interval;
                     sleep(self.interval).await;
                 }
                 Ok(_) => {
@@ -207,25 +371,25 @@ impl BaseAgent for Validator {
                     }
                     break;
                 }
-                _ => {
-                    // Future that immediately resolves
-                    return;
+                Err(err) => {
+                    error!(?err, origin = %self.origin_chain, ?count_retry_delay, "Failed to get merkle tree hook count, retrying");
+                    sleep(count_retry_delay).await;
+                    count_retry_delay = (count_retry_delay * 2).min(MAX_RETRY_DELAY);
                 }
             }
         }
-        tasks.push(self.runtime_metrics.spawn());
+        if self.checkpoint_syncer.syncers().len() > 1 {
+            tasks.push(self.run_checkpoint_syncer_reconciler());
+        }
 
         // Note that this only returns an error if one of the tasks panics
         if let Err(err) = try_join_all(tasks).await {
-            error!(?err, "One of the validator tasks returned an error");
+            error!(?err, origin = %self.origin_chain, "One of the validator tasks returned an error");
         }
     }
-}
 
-impl Validator {
     async fn run_merkle_tree_hook_sync(&self) -> Instrumented<JoinHandle<()>> {
-        let index_settings =
-            self.as_ref().settings.chains[self.origin_chain.name()].index_settings();
+        let index_settings = self.origin_chain_conf.index_settings();
         let contract_sync = self.merkle_tree_hook_sync.clone();
         let cursor = contract_sync
             .cursor(index_settings)
@@ -238,7 +402,7 @@ impl Validator {
             });
         let origin = self.origin_chain.name().to_string();
         tokio::spawn(async move {
-            let label = "merkle_tree_hook";
+            let label = MERKLE_TREE_HOOK_SYNC_LABEL;
             contract_sync.clone().sync(label, cursor.into()).await;
             info!(chain = origin, label, "contract sync task exit");
         })
@@ -253,7 +417,8 @@ impl Validator {
             self.signer.clone(),
             self.checkpoint_syncer.clone(),
             Arc::new(self.db.clone()) as Arc<dyn HyperlaneDb>,
-            ValidatorSubmitterMetrics::new(&self.core.metrics, &self.origin_chain),
+            ValidatorSubmitterMetrics::new(&self.core_metrics, &self.origin_chain),
+            self.health.clone(),
         );
 
         let tip_tree = self
@@ -287,6 +452,36 @@ impl Validator {
         tasks
     }
 
+    /// Periodically reads the reorg status persisted to the checkpoint
+    /// storage, which the validator's health is derived from
+    fn run_reorg_status_poller(&self) -> Instrumented<JoinHandle<()>> {
+        let checkpoint_syncer = self.checkpoint_syncer.clone();
+        let health = self.health.clone();
+        tokio::spawn(async move {
+            loop {
+                sleep(REORG_STATUS_POLL_INTERVAL).await;
+                health.refresh_reorg_status(&*checkpoint_syncer).await;
+            }
+        })
+        .instrument(info_span!("ReorgStatusPoller"))
+    }
+
+    /// Periodically backfills checkpoint syncer mirrors that lag behind the
+    /// others, e.g. after an outage.
+    fn run_checkpoint_syncer_reconciler(&self) -> Instrumented<JoinHandle<()>> {
+        let checkpoint_syncer = self.checkpoint_syncer.clone();
+        let interval = self.checkpoint_syncer_reconcile_interval;
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = checkpoint_syncer.reconcile().await {
+                    warn!(?err, "Failed to reconcile checkpoint syncer mirrors");
+                }
+                sleep(interval).await;
+            }
+        })
+        .instrument(info_span!("CheckpointSyncerReconciler"))
+    }
+
     fn log_on_announce_failure(result: ChainResult<TxOutcome>, chain_signer: &String) {
         match result {
             Ok(outcome) => {
@@ -326,19 +521,35 @@ impl Validator {
 
     async fn announce(&self) -> Result<()> {
         let address = self.signer.eth_address();
-        let announcement_location = self.checkpoint_syncer.announcement_location();
-
-        // Sign and post the validator announcement
-        let announcement = Announcement {
-            validator: address,
-            mailbox_address: self.mailbox.address(),
-            mailbox_domain: self.mailbox.domain().id(),
-            storage_location: announcement_location.clone(),
-        };
-        let signed_announcement = self.signer.sign(announcement.clone()).await?;
-        self.checkpoint_syncer
-            .write_announcement(&signed_announcement)
-            .await?;
+
+        // Sign and post an announcement for the location of every mirror
+        let mut signed_announcements = vec![];
+        let mut written = 0;
+        for syncer in self.checkpoint_syncer.syncers() {
+            let announcement = Announcement {
+                validator: address,
+                mailbox_address: self.mailbox.address(),
+                mailbox_domain: self.mailbox.domain().id(),
+                storage_location: syncer.announcement_location(),
+            };
+            let signed_announcement = self.signer.sign(announcement).await?;
+            match syncer.write_announcement(&signed_announcement).await {
+                Ok(()) => written += 1,
+                Err(err) => warn!(
+                    ?err,
+                    location = %signed_announcement.value.storage_location,
+                    "Failed to write announcement to checkpoint syncer"
+                ),
+            }
+            signed_announcements.push(signed_announcement);
+        }
+        if written < self.checkpoint_syncer.quorum() {
+            bail!(
+                "Failed to write announcement: only {written} of {} checkpoint syncers succeeded, quorum is {}",
+                signed_announcements.len(),
+                self.checkpoint_syncer.quorum()
+            );
+        }
 
         // Ensure that the validator has announced themselves before we enter
         // the main validator submit loop. This is to avoid a situation in
@@ -353,38 +564,44 @@ impl Validator {
                 .await?
                 .first()
             {
-                if locations.contains(&announcement_location) {
+                let unannounced = signed_announcements
+                    .iter()
+                    .filter(|signed| !locations.contains(&signed.value.storage_location))
+                    .collect::<Vec<_>>();
+                if unannounced.is_empty() {
                     info!(
                         ?locations,
-                        ?announcement_location,
-                        "Validator has announced signature storage location"
+                        announcement_locations=?self.checkpoint_syncer.announcement_locations(),
+                        "Validator has announced signature storage locations"
                     );
                     break;
                 }
                 info!(
                     announced_locations=?locations,
-                    "Validator has not announced signature storage location"
+                    unannounced_locations=?unannounced.iter().map(|signed| &signed.value.storage_location).collect::<Vec<_>>(),
+                    "Validator has not announced all signature storage locations"
                 );
 
-                if let Some(chain_signer) = self.core.settings.chains[self.origin_chain.name()]
-                    .chain_signer()
-                    .await?
-                {
+                if let Some(chain_signer) = self.origin_chain_conf.chain_signer().await? {
                     let chain_signer = chain_signer.address_string();
-                    info!(eth_validator_address=?announcement.validator, ?chain_signer, "Attempting self announce");
-                    let balance_delta = self
-                        .validator_announce
-                        .announce_tokens_needed(signed_announcement.clone())
-                        .await
-                        .unwrap_or_default();
-                    if balance_delta > U256::zero() {
-                        warn!(
-                            tokens_needed=%balance_delta,
-                            eth_validator_address=?announcement.validator,
-                            ?chain_signer,
-                            "Please send tokens to your chain signer address to announce",
-                        );
-                    } else {
+                    // Relayers read from the most recently announced location,
+                    // so the primary is announced last
+                    for signed_announcement in unannounced.into_iter().rev() {
+                        info!(eth_validator_address=?address, location=%signed_announcement.value.storage_location, ?chain_signer, "Attempting self announce");
+                        let balance_delta = self
+                            .validator_announce
+                            .announce_tokens_needed(signed_announcement.clone())
+                            .await
+                            .unwrap_or_default();
+                        if balance_delta > U256::zero() {
+                            warn!(
+                                tokens_needed=%balance_delta,
+                                eth_validator_address=?address,
+                                ?chain_signer,
+                                "Please send tokens to your chain signer address to announce",
+                            );
+                            break;
+                        }
                         let result = self
                             .validator_announce
                             .announce(signed_announcement.clone())
@@ -0,0 +1,3 @@
+pub use operation_verifier::TonApplicationOperationVerifier;
+
+mod operation_verifier;
@@ -0,0 +1,76 @@
+use std::io::Cursor;
+
+use async_trait::async_trait;
+use derive_new::new;
+use tracing::trace;
+
+use hyperlane_core::{Decode, HyperlaneMessage, H256, U256};
+use hyperlane_operation_verifier::{
+    ApplicationOperationVerifier, ApplicationOperationVerifierReport,
+};
+use hyperlane_warp_route::TokenMessage;
+
+const WARP_ROUTE_MARKER: &str = "/";
+
+/// Application operation verifier for TON
+#[derive(new)]
+pub struct TonApplicationOperationVerifier {}
+
+#[async_trait]
+impl ApplicationOperationVerifier for TonApplicationOperationVerifier {
+    async fn verify(
+        &self,
+        app_context: &Option<String>,
+        message: &HyperlaneMessage,
+    ) -> Option<ApplicationOperationVerifierReport> {
+        trace!(?app_context, ?message, "TON application operation verifier");
+
+        Self::verify_message(app_context, message)
+    }
+}
+
+impl TonApplicationOperationVerifier {
+    fn verify_message(
+        app_context: &Option<String>,
+        message: &HyperlaneMessage,
+    ) -> Option<ApplicationOperationVerifierReport> {
+        use ApplicationOperationVerifierReport::{MalformedMessage, ZeroAmount};
+
+        let context = match app_context {
+            Some(c) => c,
+            None => return None,
+        };
+
+        if !context.contains(WARP_ROUTE_MARKER) {
+            return None;
+        }
+
+        // Starting from this point we assume that we are in a warp route context
+
+        let mut reader = Cursor::new(message.body.as_slice());
+        let token_message = match TokenMessage::read_from(&mut reader) {
+            Ok(m) => m,
+            Err(_) => return Some(MalformedMessage(message.clone())),
+        };
+
+        if !Self::is_valid_recipient(&token_message.recipient()) {
+            return Some(MalformedMessage(message.clone()));
+        }
+
+        if token_message.amount() == U256::zero() {
+            return Some(ZeroAmount);
+        }
+
+        None
+    }
+
+    /// Token message recipient carries only the 256-bit account id, which is a
+    /// valid basechain (0) and masterchain (-1) address, except for the zero
+    /// account that TON contracts treat as an absent address.
+    fn is_valid_recipient(recipient: &H256) -> bool {
+        !recipient.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -1 +1,2 @@
 pub mod provider;
+pub mod tonlib;
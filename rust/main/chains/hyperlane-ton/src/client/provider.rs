@@ -1,8 +1,7 @@
 use std::ops::RangeInclusive;
-use std::{cmp::max, str::FromStr};
+use std::{cmp::max, future::Future, str::FromStr, time::Instant};
 
 use async_trait::async_trait;
-use derive_new::new;
 use reqwest::{Client, Method, Response};
 use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
@@ -11,23 +10,28 @@ use tracing::{error, info, warn};
 use url::Url;
 
 use hyperlane_core::{
-    h512_to_bytes, BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, FixedPointNumber,
-    HyperlaneChain, HyperlaneDomain, HyperlaneProvider, TxOutcome, TxnInfo, H256, H512, U256,
+    h512_to_bytes,
+    rpc_clients::{BlockNumberGetter, FallbackProvider},
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, FixedPointNumber, HyperlaneChain,
+    HyperlaneDomain, HyperlaneProvider, TxOutcome, TxnInfo, H256, H512, U256,
 };
+use hyperlane_metric::prometheus_metric::{PrometheusClientMetrics, PrometheusConfig};
 
 use crate::constants::{
-    ACCOUNT_STATES_ENDPOINT, BLOCKS_ENDPOINT, JETTON_WALLETS_ENDPOINT, MESSAGES_ENDPOINT,
-    TRANSACTIONS_BY_MESSAGE_ENDPOINT, TRANSACTIONS_ENDPOINT, WALLET_INFORMATION_ENDPOINT,
-    WALLET_STATE_ENDPOINT, WORKCHAIN_MASTERCHAIN,
+    ACCOUNT_STATES_ENDPOINT, BLOCKS_ENDPOINT, CONFIG_PARAM_ENDPOINT, JETTON_WALLETS_ENDPOINT,
+    MESSAGES_ENDPOINT, TRANSACTIONS_BY_MESSAGE_ENDPOINT, TRANSACTIONS_ENDPOINT,
+    WALLET_INFORMATION_ENDPOINT, WALLET_STATE_ENDPOINT, WORKCHAIN_MASTERCHAIN,
 };
 use crate::{
+    client::tonlib::TonlibClient,
     error::HyperlaneTonError,
     run_get_method::StackItem,
-    trait_builder::TonConnectionConf,
+    trait_builder::{TonApiBackend, TonConnectionConf, TonEndpoint},
     traits::ton_api_center::TonApiCenter,
     types::{
         account_state::AccountStateResponse,
         block_response::BlockResponse,
+        config_param::ConfigParamResponse,
         jetton_wallet_response::GetJettonWalletsResponse,
         message::{MessageResponse, SendMessageResponse},
         run_get_method::RunGetMethodResponse,
@@ -37,14 +41,157 @@ use crate::{
     utils::conversion::ConversionUtils,
 };
 
-#[derive(Clone, new)]
+/// Client of a single TON API endpoint
+#[derive(Clone)]
+pub struct TonApiClient {
+    pub http_client: Client,
+    pub endpoint: TonEndpoint,
+    pub api_backend: TonApiBackend,
+    metrics: PrometheusClientMetrics,
+    metrics_config: PrometheusConfig,
+}
+
+/// TON provider sending its requests to the configured API endpoints in order
+/// of priority. Endpoints that fail or whose masterchain stops advancing are
+/// deprioritized.
+#[derive(Clone)]
 pub struct TonProvider {
     pub http_client: Client,
     pub connection_conf: TonConnectionConf,
     pub domain: HyperlaneDomain,
+    fallback_provider: FallbackProvider<TonApiClient, TonApiClient>,
 }
 
 impl TonProvider {
+    /// Create a new TON provider without request metrics
+    pub fn new(
+        http_client: Client,
+        connection_conf: TonConnectionConf,
+        domain: HyperlaneDomain,
+    ) -> Self {
+        Self::with_metrics(
+            http_client,
+            connection_conf,
+            domain,
+            PrometheusClientMetrics::default(),
+            None,
+        )
+    }
+
+    /// Create a new TON provider recording metrics of the requests sent to
+    /// each endpoint
+    pub fn with_metrics(
+        http_client: Client,
+        connection_conf: TonConnectionConf,
+        domain: HyperlaneDomain,
+        metrics: PrometheusClientMetrics,
+        chain: Option<hyperlane_metric::prometheus_metric::ChainInfo>,
+    ) -> Self {
+        let clients = connection_conf.endpoints.iter().map(|endpoint| {
+            TonApiClient::new(
+                http_client.clone(),
+                endpoint.clone(),
+                connection_conf.api_backend.clone(),
+                metrics.clone(),
+                PrometheusConfig::from_url(&endpoint.url, chain.clone()),
+            )
+        });
+        let fallback_provider = FallbackProvider::new(clients);
+
+        Self {
+            http_client,
+            connection_conf,
+            domain,
+            fallback_provider,
+        }
+    }
+
+    /// Send a request to the endpoints in order of priority until one succeeds
+    async fn call<V, F, Fut>(&self, method: &'static str, request: F) -> ChainResult<V>
+    where
+        F: Fn(TonApiClient) -> Fut + Send + Sync,
+        Fut: Future<Output = ChainResult<V>> + Send + 'static,
+        V: Send + 'static,
+    {
+        if self.fallback_provider.inner.providers.is_empty() {
+            return Err(HyperlaneTonError::InvalidConfiguration(
+                "No TON API endpoints configured".to_string(),
+            )
+            .into());
+        }
+
+        self.fallback_provider
+            .call(|client| {
+                let request = request(client.clone());
+                Box::pin(async move { client.track_metric_call(method, || request).await })
+            })
+            .await
+    }
+}
+
+impl TonApiClient {
+    pub fn new(
+        http_client: Client,
+        endpoint: TonEndpoint,
+        api_backend: TonApiBackend,
+        metrics: PrometheusClientMetrics,
+        metrics_config: PrometheusConfig,
+    ) -> Self {
+        Self {
+            http_client,
+            endpoint,
+            api_backend,
+            metrics,
+            metrics_config,
+        }
+    }
+
+    async fn track_metric_call<F, Fut, T>(&self, method: &str, request: F) -> ChainResult<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ChainResult<T>>,
+    {
+        let start = Instant::now();
+        let res = request().await;
+
+        self.metrics
+            .increment_metrics(&self.metrics_config, method, start, res.is_ok());
+        res
+    }
+
+    /// Seqno of the latest masterchain block known to this endpoint
+    pub async fn get_finalized_block(&self) -> ChainResult<u32> {
+        let response = self
+            .get_blocks(
+                WORKCHAIN_MASTERCHAIN, // masterchain
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(1), // Limit: 1
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| {
+                HyperlaneTonError::ApiRequestFailed(format!(
+                    "Failed to fetch latest block: {:?}",
+                    e
+                ))
+            })?;
+
+        let block = response
+            .blocks
+            .first()
+            .ok_or(HyperlaneTonError::NoBlocksFound)?;
+
+        info!("Latest block found: {:?}", block.seqno);
+        Ok(block.seqno as u32)
+    }
+
     pub async fn request_and_parse<T: DeserializeOwned>(
         &self,
         method: Method,
@@ -53,7 +200,7 @@ impl TonProvider {
         body: Option<&Value>,
     ) -> ChainResult<T> {
         let url = self
-            .connection_conf
+            .endpoint
             .url
             .join(endpoint)
             .map_err(|e| HyperlaneTonError::UrlConstructionError(e.to_string()))?;
@@ -99,7 +246,7 @@ impl TonProvider {
             .post(url)
             .header("accept", "application/json")
             .header("Content-Type", "application/json")
-            .header("X-API-Key", self.connection_conf.api_key.clone())
+            .header("X-API-Key", self.endpoint.api_key.clone())
             .json(params)
             .send()
             .await
@@ -110,6 +257,42 @@ impl TonProvider {
         result
     }
 
+    /// Client for the requests served by tonlib, if it is the configured backend.
+    fn tonlib(&self) -> Option<TonlibClient> {
+        match self.api_backend {
+            TonApiBackend::TonCenter => None,
+            TonApiBackend::Tonlib { .. } => Some(TonlibClient::new(
+                self.http_client.clone(),
+                self.endpoint.url.clone(),
+                self.endpoint.api_key.clone(),
+            )),
+        }
+    }
+
+    /// Provider for the message and transaction searches tonlib cannot serve,
+    /// if they are not handled by this client itself.
+    fn indexer(&self) -> ChainResult<Option<TonApiClient>> {
+        match &self.api_backend {
+            TonApiBackend::TonCenter => Ok(None),
+            TonApiBackend::Tonlib {
+                indexer_url: Some(indexer_url),
+            } => Ok(Some(TonApiClient {
+                endpoint: TonEndpoint {
+                    url: indexer_url.clone(),
+                    ..self.endpoint.clone()
+                },
+                api_backend: TonApiBackend::TonCenter,
+                ..self.clone()
+            })),
+            TonApiBackend::Tonlib { indexer_url: None } => {
+                Err(HyperlaneTonError::InvalidConfiguration(
+                    "The tonlib backend requires an indexer url to search messages".to_string(),
+                )
+                .into())
+            }
+        }
+    }
+
     async fn query_request<T: serde::Serialize + ?Sized>(
         &self,
         url: Url,
@@ -120,11 +303,27 @@ impl TonProvider {
             .query(params)
             .header("accept", "application/json")
             .header("Content-Type", "application/json")
-            .header("X-API-Key", self.connection_conf.api_key.clone())
+            .header("X-API-Key", self.endpoint.api_key.clone())
             .send()
             .await
     }
 }
+impl std::fmt::Debug for TonApiClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TonApiClient")
+            .field("endpoint", &self.endpoint)
+            .field("api_backend", &self.api_backend)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BlockNumberGetter for TonApiClient {
+    async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
+        self.get_finalized_block().await.map(u64::from)
+    }
+}
+
 impl std::fmt::Debug for TonProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TonProvider")
@@ -195,15 +394,29 @@ impl HyperlaneProvider for TonProvider {
     }
 
     async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
-        let bytes = &h512_to_bytes(hash);
-        let tx_hash = hex::encode(bytes);
+        let bytes = h512_to_bytes(hash);
+        // Hashes of sent messages are padded at the end, see `ConversionUtils::base64_to_h512`
+        let tx_hash = match bytes.split_at(32) {
+            (hash, padding) if padding.iter().all(|b| *b == 0) => hex::encode(hash),
+            _ => hex::encode(&bytes),
+        };
 
-        let query_params = vec![("hash", tx_hash.clone())];
-        let response: TransactionResponse = self
-            .request_and_parse(
-                Method::GET,
-                TRANSACTIONS_ENDPOINT,
-                Some(&query_params),
+        let response = self
+            .get_transactions(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(tx_hash),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
                 None,
             )
             .await
@@ -303,7 +516,7 @@ impl HyperlaneProvider for TonProvider {
 }
 
 #[async_trait]
-impl TonApiCenter for TonProvider {
+impl TonApiCenter for TonApiClient {
     /// Implements a method to retrieve messages from the TON network based on specified filters.
     /// Parameters include message hashes, source and destination addresses,
     /// and time or other filters for querying messages.
@@ -324,6 +537,26 @@ impl TonApiCenter for TonProvider {
         offset: Option<u32>,
         sort: Option<String>,
     ) -> ChainResult<MessageResponse> {
+        if let Some(indexer) = self.indexer()? {
+            return indexer
+                .get_messages(
+                    msg_hash,
+                    body_hash,
+                    source,
+                    destination,
+                    opcode,
+                    start_utime,
+                    end_utime,
+                    start_lt,
+                    end_lt,
+                    direction,
+                    limit,
+                    offset,
+                    sort,
+                )
+                .await;
+        }
+
         let params: Vec<(&str, String)> = vec![
             ("msg_hash", msg_hash.map(|v| v.join(","))),
             ("body_hash", body_hash),
@@ -374,6 +607,28 @@ impl TonApiCenter for TonProvider {
         offset: Option<u32>,
         sort: Option<String>,
     ) -> ChainResult<TransactionResponse> {
+        if let Some(indexer) = self.indexer()? {
+            return indexer
+                .get_transactions(
+                    workchain,
+                    shard,
+                    seqno,
+                    mc_seqno,
+                    account,
+                    exclude_account,
+                    hash,
+                    lt,
+                    start_utime,
+                    end_utime,
+                    start_lt,
+                    end_lt,
+                    limit,
+                    offset,
+                    sort,
+                )
+                .await;
+        }
+
         let query_params: Vec<(&str, String)> = vec![
             ("workchain", workchain.map(|v| v.to_string())),
             ("shard", shard),
@@ -415,6 +670,10 @@ impl TonApiCenter for TonProvider {
         address: String,
         include_boc: bool,
     ) -> ChainResult<AccountStateResponse> {
+        if let Some(tonlib) = self.tonlib() {
+            return tonlib.get_account_state(address, include_boc).await;
+        }
+
         let query_params: Vec<(&str, String)> = vec![
             ("address", address),
             ("include_boc", include_boc.to_string()),
@@ -440,6 +699,10 @@ impl TonApiCenter for TonProvider {
         address: &str,
         use_v2: bool,
     ) -> ChainResult<WalletInformation> {
+        if let Some(tonlib) = self.tonlib() {
+            return tonlib.get_wallet_information(address, use_v2).await;
+        }
+
         let query_params: Vec<(&str, String)> = vec![
             ("address", address.to_string()),
             ("use_v2", use_v2.to_string()),
@@ -466,19 +729,19 @@ impl TonApiCenter for TonProvider {
         method: &str,
         stack: Option<Vec<StackItem>>,
     ) -> ChainResult<RunGetMethodResponse> {
+        if let Some(tonlib) = self.tonlib() {
+            return tonlib.run_get_method(address, method, stack).await;
+        }
+
         info!(
             "Calling get method for address: {:?}, method: {:?}, stack: {:?}",
             address, method, stack
         );
 
-        let url = self
-            .connection_conf
-            .url
-            .join("v3/runGetMethod")
-            .map_err(|e| {
-                warn!("Failed to construct account state URL: {:?}", e);
-                HyperlaneTonError::UrlConstructionError(e.to_string())
-            })?;
+        let url = self.endpoint.url.join("v3/runGetMethod").map_err(|e| {
+            warn!("Failed to construct account state URL: {:?}", e);
+            HyperlaneTonError::UrlConstructionError(e.to_string())
+        })?;
 
         let stack_data = stack.unwrap_or_else(|| vec![]);
 
@@ -541,7 +804,11 @@ impl TonApiCenter for TonProvider {
     }
 
     async fn send_message(&self, boc: String) -> ChainResult<SendMessageResponse> {
-        let url = self.connection_conf.url.join("v3/message").map_err(|e| {
+        if let Some(tonlib) = self.tonlib() {
+            return tonlib.send_message(boc).await;
+        }
+
+        let url = self.endpoint.url.join("v3/message").map_err(|e| {
             warn!("Failed to construct message URL: {:?}", e);
             HyperlaneTonError::UrlConstructionError(e.to_string())
         })?;
@@ -615,6 +882,12 @@ impl TonApiCenter for TonProvider {
         body_hash: Option<String>,
         opcode: Option<String>,
     ) -> ChainResult<TransactionResponse> {
+        if let Some(indexer) = self.indexer()? {
+            return indexer
+                .get_transaction_by_message(msg_hash, body_hash, opcode)
+                .await;
+        }
+
         let query_params: Vec<(&str, String)> = vec![
             ("msg_hash", msg_hash),
             ("body_hash", body_hash.unwrap_or_default()),
@@ -653,6 +926,24 @@ impl TonApiCenter for TonProvider {
         offset: Option<u32>,
         sort: Option<String>,
     ) -> ChainResult<BlockResponse> {
+        if let Some(tonlib) = self.tonlib() {
+            return tonlib
+                .get_blocks(
+                    workchain,
+                    shard,
+                    seqno,
+                    mc_seqno,
+                    start_utime,
+                    end_utime,
+                    start_lt,
+                    end_lt,
+                    limit,
+                    offset,
+                    sort,
+                )
+                .await;
+        }
+
         let query_params: Vec<(&str, String)> = vec![
             ("workchain", workchain.to_string()),
             ("shard", shard.unwrap_or_default()),
@@ -693,6 +984,254 @@ impl TonApiCenter for TonProvider {
             })?;
         Ok(parsed_response)
     }
+
+    async fn get_config_param(&self, config_id: u32) -> ChainResult<ConfigParamResponse> {
+        if let Some(tonlib) = self.tonlib() {
+            return tonlib.get_config_param(config_id).await;
+        }
+
+        let query_params = [("config_id", config_id.to_string())];
+
+        let parsed_response: ConfigParamResponse = self
+            .request_and_parse(
+                Method::GET,
+                CONFIG_PARAM_ENDPOINT,
+                Some(&query_params),
+                None,
+            )
+            .await
+            .map_err(|e| {
+                warn!("Failed to get config param {}: {:?}", config_id, e);
+                e
+            })?;
+
+        if !parsed_response.ok {
+            return Err(HyperlaneTonError::ApiInvalidResponse(format!(
+                "Config param {} request was not successful",
+                config_id
+            ))
+            .into());
+        }
+        Ok(parsed_response)
+    }
+}
+
+#[async_trait]
+impl TonApiCenter for TonProvider {
+    async fn get_messages(
+        &self,
+        msg_hash: Option<Vec<String>>,
+        body_hash: Option<String>,
+        source: Option<String>,
+        destination: Option<String>,
+        opcode: Option<String>,
+        start_utime: Option<i64>,
+        end_utime: Option<i64>,
+        start_lt: Option<i64>,
+        end_lt: Option<i64>,
+        direction: Option<String>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        sort: Option<String>,
+    ) -> ChainResult<MessageResponse> {
+        self.call("get_messages", |client| {
+            let (msg_hash, body_hash, source, destination, opcode, direction, sort) = (
+                msg_hash.clone(),
+                body_hash.clone(),
+                source.clone(),
+                destination.clone(),
+                opcode.clone(),
+                direction.clone(),
+                sort.clone(),
+            );
+            async move {
+                client
+                    .get_messages(
+                        msg_hash,
+                        body_hash,
+                        source,
+                        destination,
+                        opcode,
+                        start_utime,
+                        end_utime,
+                        start_lt,
+                        end_lt,
+                        direction,
+                        limit,
+                        offset,
+                        sort,
+                    )
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn get_transactions(
+        &self,
+        workchain: Option<i32>,
+        shard: Option<String>,
+        seqno: Option<i32>,
+        mc_seqno: Option<i32>,
+        account: Option<Vec<String>>,
+        exclude_account: Option<Vec<String>>,
+        hash: Option<String>,
+        lt: Option<i64>,
+        start_utime: Option<i64>,
+        end_utime: Option<i64>,
+        start_lt: Option<i64>,
+        end_lt: Option<i64>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        sort: Option<String>,
+    ) -> ChainResult<TransactionResponse> {
+        self.call("get_transactions", |client| {
+            let (shard, account, exclude_account, hash, sort) = (
+                shard.clone(),
+                account.clone(),
+                exclude_account.clone(),
+                hash.clone(),
+                sort.clone(),
+            );
+            async move {
+                client
+                    .get_transactions(
+                        workchain,
+                        shard,
+                        seqno,
+                        mc_seqno,
+                        account,
+                        exclude_account,
+                        hash,
+                        lt,
+                        start_utime,
+                        end_utime,
+                        start_lt,
+                        end_lt,
+                        limit,
+                        offset,
+                        sort,
+                    )
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn get_account_state(
+        &self,
+        address: String,
+        include_boc: bool,
+    ) -> ChainResult<AccountStateResponse> {
+        self.call("get_account_state", |client| {
+            let address = address.clone();
+            async move { client.get_account_state(address, include_boc).await }
+        })
+        .await
+    }
+
+    async fn get_wallet_information(
+        &self,
+        address: &str,
+        use_v2: bool,
+    ) -> ChainResult<WalletInformation> {
+        self.call("get_wallet_information", |client| {
+            let address = address.to_string();
+            async move { client.get_wallet_information(&address, use_v2).await }
+        })
+        .await
+    }
+
+    async fn run_get_method(
+        &self,
+        address: &str,
+        method: &str,
+        stack: Option<Vec<StackItem>>,
+    ) -> ChainResult<RunGetMethodResponse> {
+        self.call("run_get_method", |client| {
+            let (address, method, stack) = (address.to_string(), method.to_string(), stack.clone());
+            async move { client.run_get_method(&address, &method, stack).await }
+        })
+        .await
+    }
+
+    async fn send_message(&self, boc: String) -> ChainResult<SendMessageResponse> {
+        self.call("send_message", |client| {
+            let boc = boc.clone();
+            async move { client.send_message(boc).await }
+        })
+        .await
+    }
+
+    async fn get_wallet_states(&self, account: String) -> ChainResult<WalletStatesResponse> {
+        self.call("get_wallet_states", |client| {
+            let account = account.clone();
+            async move { client.get_wallet_states(account).await }
+        })
+        .await
+    }
+
+    async fn get_transaction_by_message(
+        &self,
+        msg_hash: String,
+        body_hash: Option<String>,
+        opcode: Option<String>,
+    ) -> ChainResult<TransactionResponse> {
+        self.call("get_transaction_by_message", |client| {
+            let (msg_hash, body_hash, opcode) =
+                (msg_hash.clone(), body_hash.clone(), opcode.clone());
+            async move {
+                client
+                    .get_transaction_by_message(msg_hash, body_hash, opcode)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn get_blocks(
+        &self,
+        workchain: i32,
+        shard: Option<String>,
+        seqno: Option<u64>,
+        mc_seqno: Option<u32>,
+        start_utime: Option<i64>,
+        end_utime: Option<i64>,
+        start_lt: Option<i64>,
+        end_lt: Option<i64>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        sort: Option<String>,
+    ) -> ChainResult<BlockResponse> {
+        self.call("get_blocks", |client| {
+            let (shard, sort) = (shard.clone(), sort.clone());
+            async move {
+                client
+                    .get_blocks(
+                        workchain,
+                        shard,
+                        seqno,
+                        mc_seqno,
+                        start_utime,
+                        end_utime,
+                        start_lt,
+                        end_lt,
+                        limit,
+                        offset,
+                        sort,
+                    )
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn get_config_param(&self, config_id: u32) -> ChainResult<ConfigParamResponse> {
+        self.call("get_config_param", |client| async move {
+            client.get_config_param(config_id).await
+        })
+        .await
+    }
 }
 
 impl TonProvider {
@@ -796,35 +1335,10 @@ impl TonProvider {
         })
     }
     pub async fn get_finalized_block(&self) -> ChainResult<u32> {
-        let response = self
-            .get_blocks(
-                WORKCHAIN_MASTERCHAIN, // masterchain
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                Some(1), // Limit: 1
-                None,
-                None,
-            )
-            .await
-            .map_err(|e| {
-                HyperlaneTonError::ApiRequestFailed(format!(
-                    "Failed to fetch latest block: {:?}",
-                    e
-                ))
-            })?;
-
-        let block = response
-            .blocks
-            .first()
-            .ok_or(HyperlaneTonError::NoBlocksFound)?;
-
-        info!("Latest block found: {:?}", block.seqno);
-        Ok(block.seqno as u32)
+        self.call("get_finalized_block", |client| async move {
+            client.get_finalized_block().await
+        })
+        .await
     }
 
     pub async fn get_logs(
@@ -923,6 +1437,44 @@ use crate::ton_api_center::TonApiCenterTestUtils;
 #[cfg(feature = "test-utils")]
 #[async_trait]
 impl TonApiCenterTestUtils for TonProvider {
+    async fn get_jetton_wallets(
+        &self,
+        address: Option<Vec<String>>,
+        owner_address: Option<Vec<String>>,
+        jetton_address: Option<Vec<String>>,
+        exclude_zero_balance: Option<bool>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        sort: Option<String>,
+    ) -> ChainResult<GetJettonWalletsResponse> {
+        self.call("get_jetton_wallets", |client| {
+            let (address, owner_address, jetton_address, sort) = (
+                address.clone(),
+                owner_address.clone(),
+                jetton_address.clone(),
+                sort.clone(),
+            );
+            async move {
+                client
+                    .get_jetton_wallets(
+                        address,
+                        owner_address,
+                        jetton_address,
+                        exclude_zero_balance,
+                        limit,
+                        offset,
+                        sort,
+                    )
+                    .await
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "test-utils")]
+#[async_trait]
+impl TonApiCenterTestUtils for TonApiClient {
     async fn get_jetton_wallets(
         &self,
         address: Option<Vec<String>>,
@@ -981,6 +1533,8 @@ mod tests {
     use tonlib_core::TonAddress;
     use url::Url;
 
+    use crate::wrappers::recorded_server::RecordedServer;
+
     fn create_test_provider() -> TonProvider {
         let _ = tracing_subscriber::fmt()
             .with_max_level(tracing::Level::INFO)
@@ -1002,6 +1556,27 @@ mod tests {
         provider
     }
 
+    const TONCENTER_RECORDINGS: &str = include_str!("../../tests/recordings/toncenter.json");
+    const TONLIB_RECORDINGS: &str = include_str!("../../tests/recordings/tonlib.json");
+
+    async fn start_recorded_server(recordings: &str) -> RecordedServer {
+        RecordedServer::from_json(recordings)
+            .await
+            .expect("Failed to start recorded server")
+    }
+
+    fn create_recorded_provider(url: Url, api_backend: TonApiBackend) -> TonProvider {
+        let config = TonConnectionConf {
+            api_backend,
+            ..TonConnectionConf::new(url, String::new(), 1)
+        };
+        TonProvider::new(
+            Client::new(),
+            config,
+            HyperlaneDomain::Known(hyperlane_core::KnownHyperlaneDomain::TonTest1),
+        )
+    }
+
     fn h256_to_h512(h: H256) -> H512 {
         let mut bytes = [0u8; 64];
         bytes[32..].copy_from_slice(h.as_bytes());
@@ -1227,4 +1802,82 @@ mod tests {
             Err(e) => panic!("Error fetching jetton wallet info: {:?}", e),
         }
     }
+
+    #[tokio::test]
+    async fn test_get_finalized_block_recorded_toncenter() {
+        let toncenter = start_recorded_server(TONCENTER_RECORDINGS).await;
+        let provider = create_recorded_provider(
+            toncenter.url().join("api/").unwrap(),
+            TonApiBackend::TonCenter,
+        );
+
+        let block = provider.get_finalized_block().await.unwrap();
+
+        assert_eq!(block, 27294362);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_next_endpoint() {
+        let failing = RecordedServer::start(vec![])
+            .await
+            .expect("Failed to start recorded server");
+        let toncenter = start_recorded_server(TONCENTER_RECORDINGS).await;
+        let config = TonConnectionConf::with_endpoints(
+            vec![
+                TonEndpoint {
+                    url: failing.url().join("api/").unwrap(),
+                    api_key: String::new(),
+                },
+                TonEndpoint {
+                    url: toncenter.url().join("api/").unwrap(),
+                    api_key: String::new(),
+                },
+            ],
+            1,
+        );
+        let provider = TonProvider::new(
+            Client::new(),
+            config,
+            HyperlaneDomain::Known(hyperlane_core::KnownHyperlaneDomain::TonTest1),
+        );
+
+        let block = provider.get_finalized_block().await.unwrap();
+
+        assert_eq!(block, 27294362);
+    }
+
+    #[tokio::test]
+    async fn test_tonlib_backend_searches_messages_in_indexer() {
+        let tonlib = start_recorded_server(TONLIB_RECORDINGS).await;
+        let toncenter = start_recorded_server(TONCENTER_RECORDINGS).await;
+        let provider = create_recorded_provider(
+            tonlib.url(),
+            TonApiBackend::Tonlib {
+                indexer_url: Some(toncenter.url().join("api/").unwrap()),
+            },
+        );
+
+        let block = provider.get_finalized_block().await.unwrap();
+        let logs = provider
+            .get_logs("0:AFB01EB4", 1733403600, 1733403610, 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(block, 27294361);
+        assert_eq!(logs.messages.len(), 1);
+        assert_eq!(logs.messages[0].created_lt, "29431320000002");
+    }
+
+    #[tokio::test]
+    async fn test_tonlib_backend_without_indexer() {
+        let tonlib = start_recorded_server(TONLIB_RECORDINGS).await;
+        let provider =
+            create_recorded_provider(tonlib.url(), TonApiBackend::Tonlib { indexer_url: None });
+
+        let result = provider
+            .get_logs("0:AFB01EB4", 1733403600, 1733403610, 10, 0)
+            .await;
+
+        assert!(result.is_err());
+    }
 }
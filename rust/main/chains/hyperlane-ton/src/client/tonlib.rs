@@ -0,0 +1,598 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use derive_new::new;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tracing::{info, warn};
+use url::Url;
+
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+
+use crate::constants::{MASTERCHAIN_SHARD, TONLIB_JSON_RPC_ENDPOINT, WORKCHAIN_MASTERCHAIN};
+use crate::{
+    error::HyperlaneTonError,
+    run_get_method::{StackItem, StackValue},
+    traits::ton_api_center::TonApiCenter,
+    types::{
+        account_state::{Account, AccountStateResponse},
+        block_response::{Block, BlockResponse, MasterChainBlockRef, PrevBlock},
+        config_param::{ConfigInfo, ConfigParamResponse},
+        message::{MessageResponse, SendMessageResponse},
+        run_get_method::RunGetMethodResponse,
+        tonlib::{
+            TonlibAddressInformation, TonlibBlockHeader, TonlibExtMessageInfo,
+            TonlibMasterchainInfo, TonlibResponse, TonlibRunGetMethodResult,
+            TonlibWalletInformation,
+        },
+        transaction::TransactionResponse,
+        wallet_state::{WalletInformation, WalletState, WalletStatesResponse},
+    },
+};
+
+/// Client of the tonlib JSON-RPC interface, as served by `ton-http-api` on top
+/// of a set of liteservers.
+#[derive(Clone, new)]
+pub struct TonlibClient {
+    pub http_client: Client,
+    pub url: Url,
+    pub api_key: String,
+}
+
+impl TonlibClient {
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> ChainResult<T> {
+        let url = self
+            .url
+            .join(TONLIB_JSON_RPC_ENDPOINT)
+            .map_err(|e| HyperlaneTonError::UrlConstructionError(e.to_string()))?;
+
+        let body = json!({
+            "id": "1",
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        });
+
+        let mut request = self
+            .http_client
+            .post(url)
+            .header("accept", "application/json")
+            .json(&body);
+        if !self.api_key.is_empty() {
+            request = request.header("X-API-Key", self.api_key.clone());
+        }
+
+        let response = request.send().await.map_err(|e| {
+            warn!("Error sending tonlib request {}: {:?}", method, e);
+            HyperlaneTonError::ApiConnectionError(format!("{:?}", e))
+        })?;
+
+        // tonlib reports failures in the body, so it is parsed regardless of the status
+        let status = response.status();
+        let response_text = response.text().await.map_err(|e| {
+            HyperlaneTonError::ApiInvalidResponse(format!("Failed to get response text: {:?}", e))
+        })?;
+
+        let parsed: TonlibResponse<T> = serde_json::from_str(&response_text).map_err(|e| {
+            HyperlaneTonError::ParsingError(format!(
+                "Failed to parse {} response with status {}: {:?}",
+                method, status, e
+            ))
+        })?;
+
+        match parsed {
+            TonlibResponse {
+                ok: true,
+                result: Some(result),
+                ..
+            } => Ok(result),
+            TonlibResponse { code, error, .. } => {
+                Err(HyperlaneTonError::ApiInvalidResponse(format!(
+                    "tonlib {} failed with code {:?}: {}",
+                    method,
+                    code,
+                    error.unwrap_or_default()
+                ))
+                .into())
+            }
+        }
+    }
+
+    async fn get_masterchain_block_header(&self, seqno: i32) -> ChainResult<TonlibBlockHeader> {
+        self.call(
+            "getBlockHeader",
+            json!({
+                "workchain": WORKCHAIN_MASTERCHAIN,
+                "shard": MASTERCHAIN_SHARD,
+                "seqno": seqno,
+            }),
+        )
+        .await
+    }
+
+    fn unsupported(request: &str) -> ChainCommunicationError {
+        HyperlaneTonError::UnsupportedByBackend(format!("tonlib ({})", request)).into()
+    }
+
+    fn to_tonlib_stack(stack: Vec<StackItem>) -> ChainResult<Vec<(String, Value)>> {
+        stack
+            .into_iter()
+            .map(|item| {
+                let StackValue::String(value) = item.value else {
+                    return Err(HyperlaneTonError::ParsingError(format!(
+                        "Unsupported stack value for tonlib: {:?}",
+                        item.value
+                    ))
+                    .into());
+                };
+                let r#type = match item.r#type.as_str() {
+                    "num" => "num",
+                    "cell" => "tvm.Cell",
+                    "slice" => "tvm.Slice",
+                    other => {
+                        return Err(HyperlaneTonError::ParsingError(format!(
+                            "Unsupported stack item type for tonlib: {}",
+                            other
+                        ))
+                        .into())
+                    }
+                };
+                Ok((r#type.to_string(), Value::String(value)))
+            })
+            .collect()
+    }
+
+    fn from_tonlib_stack(stack: Vec<(String, Value)>) -> ChainResult<Vec<StackItem>> {
+        stack
+            .into_iter()
+            .map(|(r#type, value)| {
+                let value = match r#type.as_str() {
+                    "num" => value.as_str(),
+                    "cell" | "slice" => value.get("bytes").and_then(Value::as_str),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    HyperlaneTonError::ParsingError(format!(
+                        "Unsupported tonlib stack entry: [{}, {}]",
+                        r#type, value
+                    ))
+                })?;
+                Ok(StackItem::new(
+                    r#type,
+                    StackValue::String(value.to_string()),
+                ))
+            })
+            .collect()
+    }
+
+    /// tonlib reports uninitialized accounts as `uninitialized`, toncenter v3 as `uninit`.
+    fn account_status(state: String) -> String {
+        match state.as_str() {
+            "uninitialized" => "uninit".to_string(),
+            _ => state,
+        }
+    }
+
+    fn to_block(header: TonlibBlockHeader) -> Block {
+        let id = header.id;
+        Block {
+            after_merge: header.after_merge,
+            after_split: header.after_split,
+            before_split: header.before_split,
+            created_by: String::new(),
+            end_lt: header.end_lt,
+            file_hash: id.file_hash,
+            flags: 0,
+            gen_catchain_seqno: header.catchain_seqno,
+            gen_utime: header.gen_utime.to_string(),
+            global_id: header.global_id,
+            key_block: header.is_key_block,
+            master_ref_seqno: id.seqno,
+            masterchain_block_ref: MasterChainBlockRef {
+                seqno: id.seqno,
+                shard: id.shard.clone(),
+                workchain: id.workchain,
+            },
+            min_ref_mc_seqno: header.min_ref_mc_seqno,
+            prev_blocks: header
+                .prev_blocks
+                .into_iter()
+                .map(|prev| PrevBlock {
+                    seqno: prev.seqno,
+                    shard: prev.shard,
+                    workchain: prev.workchain,
+                })
+                .collect(),
+            prev_key_block_seqno: header.prev_key_block_seqno,
+            rand_seed: String::new(),
+            root_hash: id.root_hash,
+            seqno: id.seqno,
+            shard: id.shard,
+            start_lt: header.start_lt,
+            tx_count: 0,
+            validator_list_hash_short: header.validator_list_hash_short,
+            version: header.version,
+            vert_seqno: header.vert_seqno,
+            vert_seqno_incr: false,
+            want_merge: header.want_merge,
+            want_split: header.want_split,
+            workchain: id.workchain,
+        }
+    }
+}
+
+impl std::fmt::Debug for TonlibClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TonlibClient")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+/// Tonlib only exposes liteserver queries, so the message and transaction
+/// searches of toncenter v3 are not available.
+#[async_trait]
+impl TonApiCenter for TonlibClient {
+    async fn get_messages(
+        &self,
+        _msg_hash: Option<Vec<String>>,
+        _body_hash: Option<String>,
+        _source: Option<String>,
+        _destination: Option<String>,
+        _opcode: Option<String>,
+        _start_utime: Option<i64>,
+        _end_utime: Option<i64>,
+        _start_lt: Option<i64>,
+        _end_lt: Option<i64>,
+        _direction: Option<String>,
+        _limit: Option<u32>,
+        _offset: Option<u32>,
+        _sort: Option<String>,
+    ) -> ChainResult<MessageResponse> {
+        Err(Self::unsupported("get_messages"))
+    }
+
+    async fn get_transactions(
+        &self,
+        _workchain: Option<i32>,
+        _shard: Option<String>,
+        _seqno: Option<i32>,
+        _mc_seqno: Option<i32>,
+        _account: Option<Vec<String>>,
+        _exclude_account: Option<Vec<String>>,
+        _hash: Option<String>,
+        _lt: Option<i64>,
+        _start_utime: Option<i64>,
+        _end_utime: Option<i64>,
+        _start_lt: Option<i64>,
+        _end_lt: Option<i64>,
+        _limit: Option<u32>,
+        _offset: Option<u32>,
+        _sort: Option<String>,
+    ) -> ChainResult<TransactionResponse> {
+        Err(Self::unsupported("get_transactions"))
+    }
+
+    async fn get_account_state(
+        &self,
+        address: String,
+        include_boc: bool,
+    ) -> ChainResult<AccountStateResponse> {
+        let info: TonlibAddressInformation = self
+            .call("getAddressInformation", json!({ "address": address }))
+            .await?;
+
+        let include = |boc: String| (include_boc && !boc.is_empty()).then_some(boc);
+        let account = Account {
+            account_state_hash: None,
+            address: Some(address),
+            balance: Some(info.balance),
+            code_boc: include(info.code),
+            code_hash: None,
+            data_boc: include(info.data),
+            data_hash: None,
+            frozen_hash: info.frozen_hash.filter(|hash| !hash.is_empty()),
+            last_transaction_hash: Some(info.last_transaction_id.hash),
+            last_transaction_lt: Some(info.last_transaction_id.lt),
+            status: Some(Self::account_status(info.state)),
+        };
+
+        Ok(AccountStateResponse {
+            accounts: vec![account],
+            address_book: HashMap::new(),
+        })
+    }
+
+    async fn get_wallet_information(
+        &self,
+        address: &str,
+        _use_v2: bool,
+    ) -> ChainResult<WalletInformation> {
+        let info: TonlibWalletInformation = self
+            .call("getWalletInformation", json!({ "address": address }))
+            .await?;
+
+        Ok(WalletInformation {
+            balance: info.balance,
+            wallet_type: info.wallet_type.unwrap_or_default(),
+            seqno: info.seqno.unwrap_or_default() as usize,
+            wallet_id: info.wallet_id.unwrap_or_default() as usize,
+            last_transaction_lt: info.last_transaction_id.lt,
+            last_transaction_hash: info.last_transaction_id.hash,
+            status: Self::account_status(info.account_state),
+        })
+    }
+
+    async fn run_get_method(
+        &self,
+        address: &str,
+        method: &str,
+        stack: Option<Vec<StackItem>>,
+    ) -> ChainResult<RunGetMethodResponse> {
+        info!(
+            "Calling tonlib get method for address: {:?}, method: {:?}",
+            address, method
+        );
+
+        let stack = Self::to_tonlib_stack(stack.unwrap_or_default())?;
+        let result: TonlibRunGetMethodResult = self
+            .call(
+                "runGetMethod",
+                json!({
+                    "address": address,
+                    "method": method,
+                    "stack": stack,
+                }),
+            )
+            .await?;
+
+        Ok(RunGetMethodResponse {
+            gas_used: result.gas_used,
+            exit_code: result.exit_code,
+            stack: Self::from_tonlib_stack(result.stack)?,
+        })
+    }
+
+    async fn send_message(&self, boc: String) -> ChainResult<SendMessageResponse> {
+        let info: TonlibExtMessageInfo = self
+            .call("sendBocReturnHash", json!({ "boc": boc }))
+            .await?;
+
+        Ok(SendMessageResponse {
+            message_hash: info.hash,
+        })
+    }
+
+    async fn get_wallet_states(&self, account: String) -> ChainResult<WalletStatesResponse> {
+        let info: TonlibWalletInformation = self
+            .call("getWalletInformation", json!({ "address": account }))
+            .await?;
+
+        let wallet = WalletState {
+            address: account,
+            is_wallet: info.wallet,
+            wallet_type: info.wallet_type.unwrap_or_default(),
+            seqno: info.seqno.unwrap_or_default(),
+            wallet_id: info.wallet_id.unwrap_or_default(),
+            balance: info.balance,
+            status: Self::account_status(info.account_state),
+            code_hash: String::new(),
+            last_transaction_hash: info.last_transaction_id.hash,
+            last_transaction_lt: info.last_transaction_id.lt,
+        };
+
+        Ok(WalletStatesResponse {
+            wallets: vec![wallet],
+            address_book: HashMap::new(),
+        })
+    }
+
+    async fn get_transaction_by_message(
+        &self,
+        _msg_hash: String,
+        _body_hash: Option<String>,
+        _opcode: Option<String>,
+    ) -> ChainResult<TransactionResponse> {
+        Err(Self::unsupported("get_transaction_by_message"))
+    }
+
+    /// Only masterchain blocks can be looked up, either by seqno or, when none
+    /// is given, the latest one.
+    async fn get_blocks(
+        &self,
+        workchain: i32,
+        shard: Option<String>,
+        seqno: Option<u64>,
+        mc_seqno: Option<u32>,
+        start_utime: Option<i64>,
+        end_utime: Option<i64>,
+        start_lt: Option<i64>,
+        end_lt: Option<i64>,
+        _limit: Option<u32>,
+        _offset: Option<u32>,
+        _sort: Option<String>,
+    ) -> ChainResult<BlockResponse> {
+        let has_range =
+            start_utime.is_some() || end_utime.is_some() || start_lt.is_some() || end_lt.is_some();
+        if workchain != WORKCHAIN_MASTERCHAIN || shard.is_some() || has_range {
+            return Err(Self::unsupported("get_blocks outside of masterchain seqno"));
+        }
+
+        let seqno = match seqno.map(u32::try_from).transpose() {
+            Ok(seqno) => seqno.or(mc_seqno),
+            Err(e) => {
+                return Err(HyperlaneTonError::ParsingError(format!(
+                    "Block seqno is out of range: {:?}",
+                    e
+                ))
+                .into())
+            }
+        };
+        let seqno = match seqno {
+            Some(seqno) => seqno as i32,
+            None => {
+                let info: TonlibMasterchainInfo =
+                    self.call("getMasterchainInfo", json!({})).await?;
+                info.last.seqno
+            }
+        };
+
+        let header = self.get_masterchain_block_header(seqno).await?;
+
+        Ok(BlockResponse {
+            blocks: vec![Self::to_block(header)],
+        })
+    }
+
+    async fn get_config_param(&self, config_id: u32) -> ChainResult<ConfigParamResponse> {
+        let result: ConfigInfo = self
+            .call("getConfigParam", json!({ "config_id": config_id }))
+            .await?;
+
+        Ok(ConfigParamResponse { ok: true, result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+
+    use super::TonlibClient;
+    use crate::{
+        run_get_method::{StackItem, StackValue},
+        traits::ton_api_center::TonApiCenter,
+        wrappers::recorded_server::RecordedServer,
+    };
+
+    const RECORDINGS: &str = include_str!("../../tests/recordings/tonlib.json");
+
+    async fn client() -> (RecordedServer, TonlibClient) {
+        let server = RecordedServer::from_json(RECORDINGS)
+            .await
+            .expect("Failed to start recorded server");
+        let client = TonlibClient::new(Client::new(), server.url(), String::new());
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_run_get_method() {
+        let (_server, client) = client().await;
+
+        let response = client
+            .run_get_method(
+                "0:AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF",
+                "get_nonce",
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.exit_code, 0);
+        assert_eq!(response.stack.len(), 2);
+        assert_eq!(response.stack[0].r#type, "num");
+        assert!(matches!(&response.stack[0].value, StackValue::String(v) if v == "0x2a"));
+        assert_eq!(response.stack[1].r#type, "cell");
+        assert!(response.stack[1].as_cell().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_blocks_latest() {
+        let (_server, client) = client().await;
+
+        let response = client
+            .get_blocks(
+                -1, None, None, None, None, None, None, None, None, None, None,
+            )
+            .await
+            .unwrap();
+
+        let block = &response.blocks[0];
+        assert_eq!(block.seqno, 27294361);
+        assert_eq!(block.gen_utime, "1733403601");
+        assert_eq!(block.workchain, -1);
+    }
+
+    #[tokio::test]
+    async fn test_get_blocks_basechain_unsupported() {
+        let (_server, client) = client().await;
+
+        let result = client
+            .get_blocks(
+                0, None, None, None, None, None, None, None, None, None, None,
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_account_state_uninitialized() {
+        let (_server, client) = client().await;
+
+        let response = client
+            .get_account_state(
+                "0:0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+                true,
+            )
+            .await
+            .unwrap();
+
+        let account = &response.accounts[0];
+        assert_eq!(account.status.as_deref(), Some("uninit"));
+        assert!(account.code_boc.is_none());
+        assert_eq!(account.balance.as_deref(), Some("0"));
+    }
+
+    #[tokio::test]
+    async fn test_get_wallet_information() {
+        let (_server, client) = client().await;
+
+        let info = client
+            .get_wallet_information(
+                "0:AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF",
+                true,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(info.seqno, 17);
+        assert_eq!(info.status, "active");
+        assert_eq!(info.balance, "1500000000");
+    }
+
+    #[tokio::test]
+    async fn test_send_message() {
+        let (_server, client) = client().await;
+
+        let response = client.send_message("te6cc".to_string()).await.unwrap();
+
+        assert_eq!(
+            response.message_hash,
+            "r7Aetrxh7mVlEB8G6k5v1B1j4Fo7gzA4l0+4mJKZeXk="
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_response() {
+        let (_server, client) = client().await;
+
+        let result = client.get_config_param(20).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_tonlib_stack() {
+        let stack = vec![
+            StackItem::new("num".to_string(), StackValue::String("0x1".to_string())),
+            StackItem::new("cell".to_string(), StackValue::String("te6cc".to_string())),
+        ];
+
+        let converted = TonlibClient::to_tonlib_stack(stack).unwrap();
+
+        assert_eq!(converted[0].0, "num");
+        assert_eq!(converted[1].0, "tvm.Cell");
+        assert_eq!(converted[1].1, "te6cc");
+    }
+}
@@ -1,12 +1,16 @@
 use async_trait::async_trait;
 use tonlib_core::TonAddress;
+use tracing::debug;
 
 use hyperlane_core::{
-    AggregationIsm, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneMessage, HyperlaneProvider, H256,
+    AggregationIsm, ChainCommunicationError, ChainResult, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, H256,
 };
 
-use crate::{client::provider::TonProvider, utils::conversion::ConversionUtils};
+use crate::{
+    client::provider::TonProvider, error::HyperlaneTonError, run_get_method::RunGetMethodResponse,
+    traits::ton_api_center::TonApiCenter, utils::conversion::ConversionUtils,
+};
 
 #[derive(Debug)]
 pub struct TonAggregationIsm {
@@ -18,6 +22,45 @@ impl TonAggregationIsm {
     pub fn new(provider: TonProvider, address: TonAddress) -> ChainResult<Self> {
         Ok(Self { provider, address })
     }
+
+    /// Extracts the threshold and the linked-cell list of module addresses
+    /// returned by the `get_modules_and_threshold` get-method.
+    fn parse_modules_and_threshold_response(
+        response: &RunGetMethodResponse,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        if response.exit_code != 0 {
+            return Err(ChainCommunicationError::from(
+                HyperlaneTonError::ApiInvalidResponse(format!(
+                    "get_modules_and_threshold failed with exit code {}",
+                    response.exit_code
+                )),
+            ));
+        }
+
+        let threshold = ConversionUtils::parse_stack_item_u32(&response.stack, 0, "threshold")?;
+        let threshold = u8::try_from(threshold).map_err(|_| {
+            ChainCommunicationError::from(HyperlaneTonError::ParsingError(format!(
+                "Threshold value is too large for u8: {}",
+                threshold
+            )))
+        })?;
+
+        let modules_stack_item = response.stack.get(1).ok_or_else(|| {
+            ChainCommunicationError::from(HyperlaneTonError::ApiInvalidResponse(
+                "No modules stack item in response".to_string(),
+            ))
+        })?;
+
+        let modules_cell = modules_stack_item.as_cell()?;
+        let modules = ConversionUtils::parse_address_linked_cells(&modules_cell).map_err(|e| {
+            ChainCommunicationError::from(HyperlaneTonError::ParsingError(format!(
+                "Failed to parse module addresses: {:?}",
+                e
+            )))
+        })?;
+
+        Ok((modules, threshold))
+    }
 }
 
 impl HyperlaneContract for TonAggregationIsm {
@@ -40,8 +83,95 @@ impl HyperlaneChain for TonAggregationIsm {
 impl AggregationIsm for TonAggregationIsm {
     async fn modules_and_threshold(
         &self,
-        _message: &HyperlaneMessage,
+        message: &HyperlaneMessage,
     ) -> ChainResult<(Vec<H256>, u8)> {
-        unimplemented!("TON AggregationIsm::modules_and_threshold is not implemented yet.")
+        let stack = ConversionUtils::build_message_stack(message)?;
+
+        let aggregation_ism_hex = self.address.to_hex();
+        let response = self
+            .provider
+            .run_get_method(&aggregation_ism_hex, "get_modules_and_threshold", stack)
+            .await
+            .map_err(|e| {
+                ChainCommunicationError::from(HyperlaneTonError::ApiRequestFailed(format!(
+                    "Failed to run get_modules_and_threshold method: {:?}",
+                    e
+                )))
+            })?;
+
+        let (modules, threshold) = Self::parse_modules_and_threshold_response(&response)?;
+        debug!("modules:{:?} threshold:{:?}", modules, threshold);
+
+        Ok((modules, threshold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose, Engine};
+    use hyperlane_core::H256;
+    use tonlib_core::cell::BagOfCells;
+
+    use super::TonAggregationIsm;
+    use crate::{run_get_method::RunGetMethodResponse, utils::conversion::ConversionUtils};
+
+    fn modules_boc(modules: &[H256]) -> String {
+        let cell = ConversionUtils::create_address_linked_cells(modules)
+            .expect("Failed to create linked cells");
+        let boc = BagOfCells::from_root(cell)
+            .serialize(true)
+            .expect("Failed to serialize BOC");
+        general_purpose::STANDARD.encode(boc)
+    }
+
+    fn response_from_json(json: &str) -> RunGetMethodResponse {
+        serde_json::from_str(json).expect("Failed to parse RunGetMethodResponse fixture")
+    }
+
+    #[test]
+    fn test_parse_modules_and_threshold_response() {
+        let modules: Vec<H256> = (1..=4u8).map(|i| H256::from_slice(&[i; 32])).collect();
+        let response = response_from_json(&format!(
+            r#"{{"gas_used": 4210, "exit_code": 0, "stack": [{{"type": "num", "value": "0x2"}}, {{"type": "cell", "value": "{}"}}]}}"#,
+            modules_boc(&modules)
+        ));
+
+        let (parsed, threshold) =
+            TonAggregationIsm::parse_modules_and_threshold_response(&response).unwrap();
+
+        assert_eq!(parsed, modules);
+        assert_eq!(threshold, 2);
+    }
+
+    #[test]
+    fn test_parse_modules_and_threshold_response_non_zero_exit_code() {
+        let response = response_from_json(r#"{"gas_used": 1520, "exit_code": 9, "stack": []}"#);
+
+        let result = TonAggregationIsm::parse_modules_and_threshold_response(&response);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_modules_and_threshold_response_missing_modules() {
+        let response = response_from_json(
+            r#"{"gas_used": 1520, "exit_code": 0, "stack": [{"type": "num", "value": "0x1"}]}"#,
+        );
+
+        let result = TonAggregationIsm::parse_modules_and_threshold_response(&response);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_modules_and_threshold_response_threshold_overflow() {
+        let response = response_from_json(&format!(
+            r#"{{"gas_used": 1520, "exit_code": 0, "stack": [{{"type": "num", "value": "0x100"}}, {{"type": "cell", "value": "{}"}}]}}"#,
+            modules_boc(&[H256::zero()])
+        ));
+
+        let result = TonAggregationIsm::parse_modules_and_threshold_response(&response);
+
+        assert!(result.is_err());
     }
 }
@@ -24,7 +24,7 @@ use hyperlane_core::{
 use crate::{
     client::provider::TonProvider, error::HyperlaneTonError, run_get_method::StackValue,
     signer::signer::TonSigner, traits::ton_api_center::TonApiCenter,
-    utils::conversion::ConversionUtils, TonConnectionConf,
+    utils::conversion::ConversionUtils,
 };
 
 pub struct TonInterchainSecurityModule {
@@ -38,15 +38,15 @@ pub struct TonInterchainSecurityModule {
 }
 impl TonInterchainSecurityModule {
     const VERIFY: u32 = 0x7b00ad2c;
-    pub fn new(locator: ContractLocator, conf: TonConnectionConf, signer: TonSigner) -> Self {
-        let ism_address = ConversionUtils::h256_to_ton_address(&locator.address, 0);
-        let provider = TonProvider::new(reqwest::Client::new(), conf, locator.domain.clone());
+    pub fn new(locator: ContractLocator, provider: TonProvider, signer: TonSigner) -> Self {
+        let workchain = provider.connection_conf.workchains.ism;
+        let ism_address = ConversionUtils::h256_to_ton_address(&locator.address, workchain);
 
         Self {
             ism_address,
             provider,
             signer,
-            workchain: 0,
+            workchain,
         }
     }
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     fmt::{Debug, Formatter},
     ops::RangeInclusive,
     sync::Arc,
@@ -11,19 +12,24 @@ use num_bigint::BigUint;
 use tonlib_core::{
     cell::{ArcCell, BagOfCells, Cell, CellBuilder, StateInit, TonCellError},
     message::{CommonMsgInfo, InternalMessage, TonMessage, TransferMessage},
-    TonAddress,
+    TonAddress, TonHash,
 };
 use tracing::{error, info, instrument, warn};
 
 use hyperlane_core::{
-    ChainCommunicationError, ChainResult, FixedPointNumber, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox,
-    ReorgPeriod, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256, U256,
+    BatchItem, BatchResult, ChainCommunicationError, ChainResult, FixedPointNumber, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer,
+    LogMeta, Mailbox, QueueOperation, ReorgPeriod, SequenceAwareIndexer, TxCostEstimate, TxOutcome,
+    H256, U256,
 };
 
 use crate::{
     client::provider::TonProvider,
-    constants::LIMIT,
+    config_param::GasPrices,
+    constants::{
+        BASECHAIN_GAS_PRICES_CONFIG_ID, LIMIT, MASTERCHAIN_GAS_PRICES_CONFIG_ID,
+        WORKCHAIN_MASTERCHAIN,
+    },
     error::HyperlaneTonError,
     message::Message,
     signer::signer::TonSigner,
@@ -38,22 +44,35 @@ pub struct TonMailbox {
     pub provider: TonProvider,
     pub signer: TonSigner,
     pub workchain: i32, // -1 or 0 now
+    /// Workchain of message recipients and of the delivery accounts deployed
+    /// for their messages, which may differ from the mailbox's own
+    pub recipient_workchain: i32,
 }
 impl TonMailbox {
     pub fn new(
         mailbox_address: TonAddress,
         provider: TonProvider,
         workchain: i32,
+        recipient_workchain: i32,
         signer: TonSigner,
     ) -> Self {
         Self {
             mailbox_address,
             provider,
             workchain,
+            recipient_workchain,
             signer,
         }
     }
 
+    fn recipient_address(&self, recipient: &H256) -> TonAddress {
+        ConversionUtils::h256_to_ton_address(recipient, self.recipient_workchain)
+    }
+
+    fn delivery_address(&self, account_id: &TonHash) -> TonAddress {
+        TonAddress::new(self.recipient_workchain, account_id)
+    }
+
     async fn get_delivery_code(&self) -> ChainResult<ArcCell> {
         let mailbox_hex = self.mailbox_address.to_hex();
         let err_mapper =
@@ -127,6 +146,65 @@ impl Debug for TonMailbox {
 }
 impl TonMailbox {
     const PROCESS_OPCODE: u32 = 0x658A3AF3;
+    /// Query id of `process` messages sent one at a time, it is not used by the contract
+    const PROCESS_QUERY_ID: u64 = 1;
+    /// Internal messages a highload wallet can send with one external message
+    const MAX_HIGHLOAD_BATCH_SIZE: usize = 254;
+
+    /// Indexes of the batched messages the wallet did not send. Messages are
+    /// matched by the query id of the `process` messages found among the
+    /// wallet transaction's outgoing messages. Whether the mailbox executed the
+    /// sent messages is left to the confirmation of the operations, so this
+    /// does not wait on any mailbox transaction.
+    async fn unsent_batch_indexes(
+        &self,
+        external_message_hash: String,
+        mut query_ids: HashMap<u64, usize>,
+    ) -> ChainResult<Vec<usize>> {
+        let wallet_transaction = self
+            .provider
+            .get_transaction_by_message(external_message_hash, None, None)
+            .await?
+            .transactions
+            .into_iter()
+            .next()
+            .ok_or(HyperlaneTonError::TransactionNotFound)?;
+
+        for out_msg in wallet_transaction.out_msgs {
+            if let Some(query_id) = parse_process_query_id(&out_msg.message_content.body) {
+                query_ids.remove(&query_id);
+            }
+        }
+
+        Ok(query_ids.into_values().collect())
+    }
+
+    /// Fetches the current gas prices of the mailbox workchain from the config params.
+    async fn get_gas_prices(&self) -> ChainResult<GasPrices> {
+        let config_id = if self.workchain == WORKCHAIN_MASTERCHAIN {
+            MASTERCHAIN_GAS_PRICES_CONFIG_ID
+        } else {
+            BASECHAIN_GAS_PRICES_CONFIG_ID
+        };
+
+        let response = self
+            .provider
+            .get_config_param(config_id)
+            .await
+            .map_err(|e| {
+                ChainCommunicationError::from(HyperlaneTonError::ApiRequestFailed(format!(
+                    "Failed to get config param {}: {:?}",
+                    config_id, e
+                )))
+            })?;
+
+        GasPrices::from_boc(&response.result.config.bytes).map_err(|e| {
+            ChainCommunicationError::from(HyperlaneTonError::ParsingError(format!(
+                "Failed to parse gas prices from config param {}: {:?}",
+                config_id, e
+            )))
+        })
+    }
 }
 #[async_trait]
 impl Mailbox for TonMailbox {
@@ -158,7 +236,7 @@ impl Mailbox for TonMailbox {
             &self.get_delivery_data(&id)?,
         )
         .map_err(|e| ChainCommunicationError::from_other(HyperlaneTonError::TonCellError(e)))?;
-        let delivery_address = TonAddress::new(0, &state_hash);
+        let delivery_address = self.delivery_address(&state_hash);
         let accounts = self
             .provider
             .get_account_state(delivery_address.to_string(), false)
@@ -205,7 +283,7 @@ impl Mailbox for TonMailbox {
 
     #[instrument(level = "debug", err, ret, skip(self))]
     async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
-        let recipient_address = ConversionUtils::h256_to_ton_address(&recipient, self.workchain);
+        let recipient_address = self.recipient_address(&recipient);
         let recipient_hex = recipient_address.to_hex();
         let recipient_response = self
             .provider
@@ -255,59 +333,13 @@ impl Mailbox for TonMailbox {
     ) -> ChainResult<TxOutcome> {
         info!("HyperlaneMessage in process:{:?}", message);
         info!("metadata in process:{:?}", metadata);
-        let message_cell = ConversionUtils::build_hyperlane_message_cell(message).map_err(|e| {
-            ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
-                "Failed to build HyperlaneMessage to Ton Cell: {:?}",
-                e
-            )))
-        })?;
-
-        let metadata_cell = ConversionUtils::metadata_to_cell(metadata).map_err(|e| {
-            ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
-                "Failed to build metadata to Ton Cell: {:?}",
-                e
-            )))
-        })?;
-
-        let query_id = 1; // it is not currently used in the contract
-
-        let msg = build_message(
-            TonMailbox::PROCESS_OPCODE,
-            ArcCell::new(message_cell),
-            ArcCell::new(metadata_cell),
-            query_id,
-        )
-        .map_err(|e| {
-            ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
-                "Failed to build message: {:?}",
-                e
-            )))
-        })?;
-
-        let common_msg_info = CommonMsgInfo::InternalMessage(InternalMessage {
-            ihr_disabled: false,
-            bounce: false,
-            bounced: false,
-            src: self.signer.address.clone(),
-            dest: self.mailbox_address.clone(),
-            value: BigUint::from(100000000u32),
-            ihr_fee: Default::default(),
-            fwd_fee: Default::default(),
-            created_lt: 0,
-            created_at: 0,
-        });
-        let transfer_message = TransferMessage {
-            common_msg_info,
-            state_init: None,
-            data: Some(ArcCell::new(msg.clone())),
-        }
-        .build()
-        .map_err(|e| {
-            ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
-                "Failed to create transfer message in process: {:?}",
-                e
-            )))
-        })?;
+        let transfer_message = build_process_transfer_message(
+            &self.signer.address,
+            &self.mailbox_address,
+            message,
+            metadata,
+            TonMailbox::PROCESS_QUERY_ID,
+        )?;
 
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
@@ -371,20 +403,173 @@ impl Mailbox for TonMailbox {
         self.provider.wait_for_transaction(tx.message_hash).await
     }
 
+    /// Delivers the messages with a single external message of a highload wallet.
+    /// Every `process` internal message carries its own query id, used to find
+    /// the batched messages the wallet did not send.
+    #[instrument(skip(self, messages), fields(size=%messages.len()))]
+    async fn process_batch(
+        &self,
+        messages: &[BatchItem<HyperlaneMessage>],
+    ) -> ChainResult<BatchResult> {
+        if !self.signer.is_highload() {
+            return Err(ChainCommunicationError::BatchingFailed);
+        }
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|e| {
+                ChainCommunicationError::from(HyperlaneTonError::ApiInvalidResponse(format!(
+                    "Failed to get current time: {:?}",
+                    e
+                )))
+            })?;
+        let expire_at = now.as_secs() as u32 + 60;
+        // Only has to be unique among the queries the wallet has not forgotten yet
+        let nonce = now.subsec_nanos();
+        let batch_query_id = highload_query_id(expire_at, nonce);
+
+        let mut failed_indexes = vec![];
+        let mut query_ids = HashMap::new();
+        let mut transfer_messages = vec![];
+        for (index, item) in messages.iter().enumerate() {
+            if transfer_messages.len() == TonMailbox::MAX_HIGHLOAD_BATCH_SIZE {
+                failed_indexes.push(index);
+                continue;
+            }
+            let query_id = batch_query_id + index as u64;
+            match build_process_transfer_message(
+                &self.signer.address,
+                &self.mailbox_address,
+                &item.data,
+                &item.submission_data.metadata,
+                query_id,
+            ) {
+                Ok(transfer_message) => {
+                    query_ids.insert(query_id, index);
+                    transfer_messages.push(ArcCell::new(transfer_message));
+                }
+                Err(e) => {
+                    warn!(message_id=?item.data.id(), error=?e, "Failed to build process message for batch");
+                    failed_indexes.push(index);
+                }
+            }
+        }
+        if transfer_messages.is_empty() {
+            return Ok(BatchResult::failed(messages.len()));
+        }
+
+        let message = self
+            .signer
+            .wallet
+            .create_external_message(expire_at, nonce, transfer_messages, false)
+            .map_err(|e| {
+                ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
+                    "Failed to create highload external message: {:?}",
+                    e
+                )))
+            })?;
+        let boc = BagOfCells::from_root(message)
+            .serialize(true)
+            .map_err(|e| {
+                ChainCommunicationError::from(HyperlaneTonError::ParsingError(format!(
+                    "Failed to serialize BOC: {:?}",
+                    e
+                )))
+            })?;
+
+        let tx = self
+            .provider
+            .send_message(general_purpose::STANDARD.encode(&boc))
+            .await?;
+        info!(tx_hash=?tx.message_hash, batch_query_id, "Sent highload batch");
+
+        // The batch is out from here on, so errors must not fail it or the
+        // relayer would resubmit the sent messages one at a time. Whatever is
+        // not resolved here is left to the confirmation of the operations.
+        let outcome = match self
+            .provider
+            .wait_for_transaction(tx.message_hash.clone())
+            .await
+        {
+            // The wallet did not send any of the messages
+            Ok(outcome) if !outcome.executed => return Ok(BatchResult::failed(messages.len())),
+            Ok(outcome) => {
+                match self.unsent_batch_indexes(tx.message_hash, query_ids).await {
+                    Ok(unsent_indexes) => failed_indexes.extend(unsent_indexes),
+                    Err(e) => {
+                        warn!(error=?e, "Failed to look up the messages sent by the highload batch")
+                    }
+                }
+                outcome
+            }
+            Err(e) => {
+                warn!(error=?e, "Highload batch transaction not found, leaving it to confirmation");
+                pending_batch_outcome(&tx.message_hash)
+            }
+        };
+        failed_indexes.sort_unstable();
+
+        Ok(BatchResult {
+            outcome: Some(outcome),
+            failed_indexes,
+        })
+    }
+
+    #[instrument(skip(self, ops), fields(size=%ops.len()))]
+    async fn try_process_batch<'a>(
+        &self,
+        ops: Vec<&'a QueueOperation>,
+    ) -> ChainResult<BatchResult> {
+        let messages = ops
+            .iter()
+            .map(|op| op.try_batch())
+            .collect::<ChainResult<Vec<BatchItem<HyperlaneMessage>>>>()?;
+        self.process_batch(&messages).await
+    }
+
     async fn process_estimate_costs(
         &self,
-        _message: &HyperlaneMessage,
+        message: &HyperlaneMessage,
         _metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        Ok(TxCostEstimate {
-            gas_limit: U256::zero(),
-            gas_price: FixedPointNumber::zero(),
-            l2_gas_limit: None,
-        })
+        let gas_limit = self
+            .provider
+            .connection_conf
+            .fee_schedule
+            .gas_for_opcode(TonMailbox::PROCESS_OPCODE);
+        let gas_prices = self.get_gas_prices().await?;
+
+        let estimate = estimate_costs(gas_limit, &gas_prices);
+        info!(
+            "Estimated process costs for message {:?}: {:?}",
+            message.id(),
+            estimate
+        );
+        Ok(estimate)
     }
 
-    fn process_calldata(&self, _message: &HyperlaneMessage, _metadata: &[u8]) -> Vec<u8> {
-        todo!()
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        build_process_transfer_message(
+            &self.signer.address,
+            &self.mailbox_address,
+            message,
+            metadata,
+            TonMailbox::PROCESS_QUERY_ID,
+        )
+        .and_then(|transfer_message| {
+            BagOfCells::from_root(transfer_message)
+                .serialize(true)
+                .map_err(|e| {
+                    ChainCommunicationError::from(HyperlaneTonError::ParsingError(format!(
+                        "Failed to serialize BOC: {:?}",
+                        e
+                    )))
+                })
+        })
+        .unwrap_or_else(|e| {
+            warn!("Failed to build process calldata: {:?}", e);
+            vec![]
+        })
     }
 }
 
@@ -509,6 +694,84 @@ impl SequenceAwareIndexer<H256> for TonMailboxIndexer {
     }
 }
 
+/// Builds a nanoton-denominated estimate, spreading the flat gas price
+/// over the whole gas limit so that `gas_limit * gas_price` equals the fee.
+fn estimate_costs(gas_limit: u64, gas_prices: &GasPrices) -> TxCostEstimate {
+    let fee = gas_prices.compute_fee(gas_limit);
+    let gas_price = if gas_limit == 0 {
+        FixedPointNumber::zero()
+    } else {
+        FixedPointNumber::from(fee) / gas_limit
+    };
+
+    TxCostEstimate {
+        gas_limit: U256::from(gas_limit),
+        gas_price,
+        l2_gas_limit: None,
+    }
+}
+
+/// Builds the internal `process` message sent from the relayer wallet to the mailbox.
+pub(crate) fn build_process_transfer_message(
+    src: &TonAddress,
+    mailbox_address: &TonAddress,
+    message: &HyperlaneMessage,
+    metadata: &[u8],
+    query_id: u64,
+) -> ChainResult<Cell> {
+    let message_cell = ConversionUtils::build_hyperlane_message_cell(message).map_err(|e| {
+        ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
+            "Failed to build HyperlaneMessage to Ton Cell: {:?}",
+            e
+        )))
+    })?;
+
+    let metadata_cell = ConversionUtils::metadata_to_cell(metadata).map_err(|e| {
+        ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
+            "Failed to build metadata to Ton Cell: {:?}",
+            e
+        )))
+    })?;
+
+    let msg = build_message(
+        TonMailbox::PROCESS_OPCODE,
+        ArcCell::new(message_cell),
+        ArcCell::new(metadata_cell),
+        query_id,
+    )
+    .map_err(|e| {
+        ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
+            "Failed to build message: {:?}",
+            e
+        )))
+    })?;
+
+    let common_msg_info = CommonMsgInfo::InternalMessage(InternalMessage {
+        ihr_disabled: false,
+        bounce: false,
+        bounced: false,
+        src: src.clone(),
+        dest: mailbox_address.clone(),
+        value: BigUint::from(100000000u32),
+        ihr_fee: Default::default(),
+        fwd_fee: Default::default(),
+        created_lt: 0,
+        created_at: 0,
+    });
+    TransferMessage {
+        common_msg_info,
+        state_init: None,
+        data: Some(ArcCell::new(msg)),
+    }
+    .build()
+    .map_err(|e| {
+        ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
+            "Failed to create transfer message in process: {:?}",
+            e
+        )))
+    })
+}
+
 pub(crate) fn build_message(
     opcode: u32,
     message_cell: ArcCell,
@@ -524,6 +787,33 @@ pub(crate) fn build_message(
         .map_err(|e| ChainCommunicationError::from_other(HyperlaneTonError::TonCellError(e)))
 }
 
+/// Query id of a highload wallet external message, the expiry time in the
+/// upper half makes the wallet reject the message once it has expired.
+fn highload_query_id(expire_at: u32, nonce: u32) -> u64 {
+    ((expire_at as u64) << 32) + nonce as u64
+}
+
+/// Outcome of a highload batch whose wallet transaction was not found yet.
+/// The batch is assumed sent, its delivery is checked when confirming.
+fn pending_batch_outcome(external_message_hash: &str) -> TxOutcome {
+    TxOutcome {
+        transaction_id: ConversionUtils::base64_to_h512(external_message_hash).unwrap_or_default(),
+        executed: true,
+        gas_used: U256::zero(),
+        gas_price: FixedPointNumber::from(0),
+    }
+}
+
+/// Query id of a `process` message body, `None` for other messages.
+fn parse_process_query_id(body: &str) -> Option<u64> {
+    let cell = ConversionUtils::parse_root_cell_from_boc(body).ok()?;
+    let mut parser = cell.parser();
+    if parser.load_u32(32).ok()? != TonMailbox::PROCESS_OPCODE {
+        return None;
+    }
+    parser.load_u64(64).ok()
+}
+
 pub fn parse_message(boc: &str) -> Result<HyperlaneMessage, TonCellError> {
     let cell = ConversionUtils::parse_root_cell_from_boc(boc).map_err(|e| {
         error!("Failed to parse root cell from BOC: {:?}", e);
@@ -609,6 +899,7 @@ mod tests {
     use std::ops::RangeInclusive;
     use std::str::FromStr;
     use tokio;
+    use tonlib_core::{mnemonic::KeyPair, wallet::WalletVersion};
 
     fn create_indexer() -> TonMailboxIndexer {
         let mailbox_address =
@@ -645,12 +936,160 @@ mod tests {
             mailbox_address: mailbox_address.clone(),
             signer,
             workchain: 0,
+            recipient_workchain: 0,
         };
 
         let indexer = TonMailboxIndexer { mailbox };
         indexer
     }
 
+    #[test]
+    fn test_recipients_use_their_own_workchain() {
+        let provider = TonProvider::new(
+            Client::new(),
+            TonConnectionConf::new(
+                Url::from_str("https://toncenter.com/api/").unwrap(),
+                String::new(),
+                10,
+            ),
+            HyperlaneDomain::Known(hyperlane_core::KnownHyperlaneDomain::TonTest1),
+        );
+        let key_pair = KeyPair {
+            public_key: vec![0x01; 32],
+            secret_key: vec![0x02; 64],
+        };
+        let signer = TonSigner::new(key_pair, WalletVersion::V4R2).unwrap();
+        let mailbox_id = H256::from_slice(&[0x02; 32]);
+        let mailbox = TonMailbox::new(
+            ConversionUtils::h256_to_ton_address(&mailbox_id, -1),
+            provider,
+            -1,
+            0,
+            signer,
+        );
+
+        let recipient = H256::from_slice(&[0x03; 32]);
+        let recipient_address = mailbox.recipient_address(&recipient);
+        assert_eq!(recipient_address.workchain, 0);
+        assert_eq!(
+            ConversionUtils::ton_address_to_h256(&recipient_address),
+            recipient
+        );
+        assert_eq!(mailbox.delivery_address(&[0x04; 32].into()).workchain, 0);
+        assert_eq!(mailbox.mailbox_address.workchain, -1);
+    }
+
+    fn process_metadata() -> Vec<u8> {
+        let mut metadata = vec![0x11; 32];
+        metadata.extend_from_slice(&[0x22; 32]);
+        metadata.extend_from_slice(&7u32.to_be_bytes());
+        metadata.extend_from_slice(&[0x33; 65]);
+        metadata
+    }
+
+    #[test]
+    fn test_build_process_transfer_message() {
+        let src = ConversionUtils::h256_to_ton_address(&H256::from_slice(&[0x01; 32]), 0);
+        let mailbox = ConversionUtils::h256_to_ton_address(&H256::from_slice(&[0x02; 32]), 0);
+        let message = HyperlaneMessage::default();
+
+        let transfer_message =
+            build_process_transfer_message(&src, &mailbox, &message, &process_metadata(), 1)
+                .unwrap();
+
+        let boc = BagOfCells::from_root(transfer_message)
+            .serialize(true)
+            .unwrap();
+        let root = BagOfCells::parse(&boc)
+            .unwrap()
+            .single_root()
+            .unwrap()
+            .clone();
+        let body = root.references().last().unwrap();
+        let mut parser = body.parser();
+        assert_eq!(parser.load_u32(32).unwrap(), TonMailbox::PROCESS_OPCODE);
+        assert_eq!(parser.load_u64(64).unwrap(), 1);
+
+        let message_cell = body.references().first().unwrap();
+        let expected_message_cell =
+            ConversionUtils::build_hyperlane_message_cell(&message).unwrap();
+        assert_eq!(**message_cell, expected_message_cell);
+    }
+
+    #[test]
+    fn test_build_process_transfer_message_invalid_metadata() {
+        let src = ConversionUtils::h256_to_ton_address(&H256::from_slice(&[0x01; 32]), 0);
+        let mailbox = ConversionUtils::h256_to_ton_address(&H256::from_slice(&[0x02; 32]), 0);
+
+        let result = build_process_transfer_message(
+            &src,
+            &mailbox,
+            &HyperlaneMessage::default(),
+            &[0u8; 10],
+            1,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_process_query_id() {
+        let message_cell =
+            ConversionUtils::build_hyperlane_message_cell(&HyperlaneMessage::default()).unwrap();
+        let metadata_cell = ConversionUtils::metadata_to_cell(&process_metadata()).unwrap();
+        let query_id = highload_query_id(1733403660, 42) + 3;
+        let to_boc = |cell| {
+            let boc = BagOfCells::from_root(cell).serialize(true).unwrap();
+            general_purpose::STANDARD.encode(boc)
+        };
+
+        let process_body = build_message(
+            TonMailbox::PROCESS_OPCODE,
+            ArcCell::new(message_cell.clone()),
+            ArcCell::new(metadata_cell.clone()),
+            query_id,
+        )
+        .unwrap();
+        let other_body = build_message(
+            0x1,
+            ArcCell::new(message_cell),
+            ArcCell::new(metadata_cell),
+            query_id,
+        )
+        .unwrap();
+
+        assert_eq!(query_id >> 32, 1733403660);
+        assert_eq!(
+            parse_process_query_id(&to_boc(process_body)),
+            Some(query_id)
+        );
+        assert_eq!(parse_process_query_id(&to_boc(other_body)), None);
+        assert_eq!(parse_process_query_id("not a boc"), None);
+    }
+
+    #[test]
+    fn test_estimate_costs() {
+        let gas_prices = GasPrices {
+            flat_gas_limit: 100,
+            flat_gas_price: 40_000,
+            gas_price: 26_214_400,
+        };
+
+        let estimate = estimate_costs(150_000, &gas_prices);
+
+        // 40_000 flat + 149_900 gas * 400 nanoton = 60_000_000 nanoton
+        assert_eq!(estimate.gas_limit, U256::from(150_000));
+        assert_eq!(estimate.gas_price, FixedPointNumber::from(400u64));
+    }
+
+    #[test]
+    fn test_estimate_costs_zero_gas() {
+        let estimate = estimate_costs(0, &GasPrices::default());
+
+        assert_eq!(estimate.gas_limit, U256::zero());
+        assert_eq!(estimate.gas_price, FixedPointNumber::zero());
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_fetch_logs_in_range() {
@@ -1,12 +1,16 @@
 use async_trait::async_trait;
 use tonlib_core::TonAddress;
+use tracing::debug;
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProvider, RoutingIsm, H256,
+    ChainCommunicationError, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, RoutingIsm, H256,
 };
 
-use crate::{client::provider::TonProvider, utils::conversion::ConversionUtils};
+use crate::{
+    client::provider::TonProvider, error::HyperlaneTonError, run_get_method::RunGetMethodResponse,
+    traits::ton_api_center::TonApiCenter, utils::conversion::ConversionUtils,
+};
 
 #[derive(Debug)]
 /// A reference to a RoutingIsm contract on some TON chain
@@ -20,6 +24,33 @@ impl TonRoutingIsm {
     pub fn new(provider: TonProvider, address: TonAddress) -> ChainResult<Self> {
         Ok(Self { provider, address })
     }
+
+    /// Extracts the sub-module address returned by the `route` get-method.
+    fn parse_route_response(response: &RunGetMethodResponse) -> ChainResult<H256> {
+        if response.exit_code != 0 {
+            return Err(ChainCommunicationError::from(
+                HyperlaneTonError::ApiInvalidResponse(format!(
+                    "route get-method failed with exit code {}",
+                    response.exit_code
+                )),
+            ));
+        }
+
+        let stack_item = response.stack.first().ok_or_else(|| {
+            ChainCommunicationError::from(HyperlaneTonError::ApiInvalidResponse(
+                "No data in stack".to_string(),
+            ))
+        })?;
+
+        let module_address = stack_item.as_cell()?.parser().load_address().map_err(|e| {
+            ChainCommunicationError::from(HyperlaneTonError::ParsingError(format!(
+                "Failed to parse module address from BOC: {:?}",
+                e
+            )))
+        })?;
+
+        Ok(ConversionUtils::ton_address_to_h256(&module_address))
+    }
 }
 
 impl HyperlaneContract for TonRoutingIsm {
@@ -34,14 +65,94 @@ impl HyperlaneChain for TonRoutingIsm {
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        unimplemented!("TON provider is not implemented yet.")
+        self.provider.provider()
     }
 }
 
 #[async_trait]
 impl RoutingIsm for TonRoutingIsm {
     /// Determine the route for the given message
-    async fn route(&self, _message: &HyperlaneMessage) -> ChainResult<H256> {
-        unimplemented!("TON RoutingIsm::route is not implemented yet.")
+    async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
+        let stack = ConversionUtils::build_message_stack(message)?;
+
+        let routing_ism_hex = self.address.to_hex();
+        let response = self
+            .provider
+            .run_get_method(&routing_ism_hex, "route", stack)
+            .await
+            .map_err(|e| {
+                ChainCommunicationError::from(HyperlaneTonError::ApiRequestFailed(format!(
+                    "Failed to run route method: {:?}",
+                    e
+                )))
+            })?;
+
+        let module = Self::parse_route_response(&response)?;
+        debug!(
+            "Routing ISM route for message {:?}: {:?}",
+            message.id(),
+            module
+        );
+
+        Ok(module)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonlib_core::TonAddress;
+
+    use super::TonRoutingIsm;
+    use crate::{run_get_method::RunGetMethodResponse, utils::conversion::ConversionUtils};
+
+    const MODULE_ADDRESS_BOC: &str =
+        "te6cckEBAQEAJAAAQ4AK6ETsEZndZnPkJ4gUxnX2otydTPtek+fiTAQfLC3C0JAaOf4x";
+
+    fn response_from_json(json: &str) -> RunGetMethodResponse {
+        serde_json::from_str(json).expect("Failed to parse RunGetMethodResponse fixture")
+    }
+
+    #[test]
+    fn test_parse_route_response() {
+        let response = response_from_json(&format!(
+            r#"{{"gas_used": 3122, "exit_code": 0, "stack": [{{"type": "cell", "value": "{}"}}]}}"#,
+            MODULE_ADDRESS_BOC
+        ));
+
+        let module = TonRoutingIsm::parse_route_response(&response).unwrap();
+
+        let expected =
+            TonAddress::from_base64_url("EQBXQidgjM7rM58hPECmM6+1FuTqZ9r0nz8SYCD5YW4WhHCM")
+                .unwrap();
+        assert_eq!(module, ConversionUtils::ton_address_to_h256(&expected));
+    }
+
+    #[test]
+    fn test_parse_route_response_non_zero_exit_code() {
+        let response = response_from_json(r#"{"gas_used": 1520, "exit_code": 101, "stack": []}"#);
+
+        let result = TonRoutingIsm::parse_route_response(&response);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_route_response_empty_stack() {
+        let response = response_from_json(r#"{"gas_used": 1520, "exit_code": 0, "stack": []}"#);
+
+        let result = TonRoutingIsm::parse_route_response(&response);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_route_response_unexpected_type() {
+        let response = response_from_json(
+            r#"{"gas_used": 1520, "exit_code": 0, "stack": [{"type": "num", "value": "0x1"}]}"#,
+        );
+
+        let result = TonRoutingIsm::parse_route_response(&response);
+
+        assert!(result.is_err());
     }
 }
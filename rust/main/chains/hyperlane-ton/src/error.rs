@@ -46,6 +46,9 @@ pub enum HyperlaneTonError {
     UnknownModuleType(u32),
     #[error("No transaction found for the provided hash")]
     TransactionNotFound,
+    /// Request that the configured API backend cannot serve
+    #[error("Request is not supported by the {0} backend")]
+    UnsupportedByBackend(String),
     /// Invalid configuration
     #[error("Invalid configuration: {0}")]
     InvalidConfiguration(String),
@@ -1,4 +1,5 @@
 //! Implementation of hyperlane for Ton.
+pub mod application;
 mod client;
 mod contracts;
 mod error;
@@ -10,7 +11,7 @@ mod utils;
 pub mod wrappers;
 
 pub use self::{
-    client::provider::*,
+    client::{provider::*, tonlib::*},
     contracts::{
         aggregation_ism::*, interchain_gas::*, interchain_security_module::*, mailbox::*,
         merkle_tree_hook::*, multisig_ism::*, routing_ism::*, validator_announce::*,
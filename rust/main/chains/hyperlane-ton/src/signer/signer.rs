@@ -40,6 +40,16 @@ impl TonSigner {
 
         Self::new(key_pair, wallet_version)
     }
+
+    /// Whether the wallet is a highload wallet, which sends many internal
+    /// messages per external message and tracks query ids instead of a seqno.
+    pub fn is_highload(&self) -> bool {
+        matches!(
+            self.wallet.version,
+            WalletVersion::HighloadV2 | WalletVersion::HighloadV2R1 | WalletVersion::HighloadV2R2
+        )
+    }
+
     pub async fn sign_message(&self, body: &Cell) -> Result<Vec<u8>, Error> {
         let signature = self
             .wallet
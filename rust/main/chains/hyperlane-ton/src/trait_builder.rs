@@ -1,13 +1,96 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
+use hyperlane_core::config::OperationBatchConfig;
 use url::Url;
 
 #[derive(Clone, Debug)]
 pub struct TonConnectionConf {
-    pub url: Url,
-    pub api_key: String,
+    /// API endpoints in order of priority. Requests fall back to the next
+    /// endpoint when one fails or stops following the masterchain.
+    pub endpoints: Vec<TonEndpoint>,
     pub max_attempts: u8,
     pub timeout: Duration,
+    pub fee_schedule: TonFeeSchedule,
+    pub workchains: TonContractWorkchains,
+    pub api_backend: TonApiBackend,
+    /// Batching of message deliveries, only used with highload wallets
+    pub operation_batch: OperationBatchConfig,
+}
+
+/// A single API endpoint together with the key used to authenticate at it.
+#[derive(Clone)]
+pub struct TonEndpoint {
+    pub url: Url,
+    pub api_key: String,
+}
+
+impl std::fmt::Debug for TonEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TonEndpoint")
+            .field("url", &self.url.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+/// API serving the requests of `TonApiCenter`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TonApiBackend {
+    /// toncenter v3 HTTP API at `url`
+    #[default]
+    TonCenter,
+    /// tonlib JSON-RPC interface at `url`, e.g. a self-hosted `ton-http-api`
+    /// talking to our own liteservers. Tonlib cannot search messages and
+    /// transactions, so those queries are sent to a toncenter v3 compatible
+    /// indexer at `indexer_url`.
+    Tonlib { indexer_url: Option<Url> },
+}
+
+/// Workchains the core contracts are deployed to. Contract addresses in the
+/// chain config only carry the 256-bit account id, so the workchain has to be
+/// provided separately for contracts living outside of the basechain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TonContractWorkchains {
+    pub mailbox: i32,
+    pub interchain_gas_paymaster: i32,
+    pub merkle_tree_hook: i32,
+    pub validator_announce: i32,
+    /// Workchain of the ISMs the mailbox delivers through
+    pub ism: i32,
+    /// Workchain of message recipients and of the delivery accounts deployed
+    /// for their messages
+    pub recipient: i32,
+}
+
+/// Amount of gas assumed to be consumed by the internal messages the relayer sends,
+/// used to estimate transaction costs without emulating them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TonFeeSchedule {
+    /// Gas units consumed per opcode
+    pub opcode_gas: HashMap<u32, u64>,
+    /// Gas units assumed for opcodes missing from `opcode_gas`
+    pub default_gas: u64,
+}
+
+impl TonFeeSchedule {
+    /// Default gas for delivering a message: mailbox `process`, ISM verification
+    /// and recipient handling together.
+    pub const DEFAULT_GAS: u64 = 150_000;
+
+    pub fn gas_for_opcode(&self, opcode: u32) -> u64 {
+        self.opcode_gas
+            .get(&opcode)
+            .copied()
+            .unwrap_or(self.default_gas)
+    }
+}
+
+impl Default for TonFeeSchedule {
+    fn default() -> Self {
+        Self {
+            opcode_gas: HashMap::new(),
+            default_gas: Self::DEFAULT_GAS,
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -25,11 +108,18 @@ pub enum TonConnectionConfError {
 
 impl TonConnectionConf {
     pub fn new(url: Url, api_key: String, max_attempts: u8) -> Self {
+        Self::with_endpoints(vec![TonEndpoint { url, api_key }], max_attempts)
+    }
+
+    pub fn with_endpoints(endpoints: Vec<TonEndpoint>, max_attempts: u8) -> Self {
         Self {
-            url,
-            api_key,
+            endpoints,
             max_attempts,
             timeout: Duration::from_secs(5),
+            fee_schedule: TonFeeSchedule::default(),
+            workchains: TonContractWorkchains::default(),
+            api_backend: TonApiBackend::default(),
+            operation_batch: OperationBatchConfig::default(),
         }
     }
 }
@@ -7,6 +7,7 @@ use crate::{
     types::{
         account_state::AccountStateResponse,
         block_response::BlockResponse,
+        config_param::ConfigParamResponse,
         jetton_wallet_response::GetJettonWalletsResponse,
         message::{MessageResponse, SendMessageResponse},
         run_get_method::RunGetMethodResponse,
@@ -99,6 +100,8 @@ pub trait TonApiCenter {
         offset: Option<u32>,
         sort: Option<String>,
     ) -> ChainResult<BlockResponse>;
+
+    async fn get_config_param(&self, config_id: u32) -> ChainResult<ConfigParamResponse>;
 }
 
 #[cfg(feature = "test-utils")]
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use tonlib_core::cell::{CellParser, TonCellError};
+
+use crate::ConversionUtils;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigParamResponse {
+    pub ok: bool,
+    pub result: ConfigInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigInfo {
+    pub config: TvmCell,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TvmCell {
+    /// base64-encoded boc
+    pub bytes: String,
+}
+
+/// Gas prices of a workchain as published in config params 20 (masterchain) and 21 (basechain).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasPrices {
+    pub flat_gas_limit: u64,
+    pub flat_gas_price: u64,
+    /// Price of a gas unit in nanotons, multiplied by 2^16.
+    pub gas_price: u64,
+}
+
+impl GasPrices {
+    const GAS_PRICES_TAG: u8 = 0xdd;
+    const GAS_PRICES_EXT_TAG: u8 = 0xde;
+    const GAS_FLAT_PFX_TAG: u8 = 0xd1;
+
+    /// Parses a `GasLimitsPrices` cell encoded as a base64 boc.
+    pub fn from_boc(boc: &str) -> Result<Self, TonCellError> {
+        let cell = ConversionUtils::parse_root_cell_from_boc(boc)?;
+        let mut parser = cell.parser();
+        Self::parse(&mut parser, 0, 0)
+    }
+
+    fn parse(
+        parser: &mut CellParser,
+        flat_gas_limit: u64,
+        flat_gas_price: u64,
+    ) -> Result<Self, TonCellError> {
+        match parser.load_u8(8)? {
+            Self::GAS_FLAT_PFX_TAG => {
+                let flat_gas_limit = parser.load_u64(64)?;
+                let flat_gas_price = parser.load_u64(64)?;
+                Self::parse(parser, flat_gas_limit, flat_gas_price)
+            }
+            Self::GAS_PRICES_TAG | Self::GAS_PRICES_EXT_TAG => Ok(Self {
+                flat_gas_limit,
+                flat_gas_price,
+                gas_price: parser.load_u64(64)?,
+            }),
+            tag => Err(TonCellError::CellParserError(format!(
+                "Unknown GasLimitsPrices tag: {:#x}",
+                tag
+            ))),
+        }
+    }
+
+    /// Computes the fee in nanotons for the given amount of gas, rounding up.
+    pub fn compute_fee(&self, gas: u64) -> u64 {
+        if gas <= self.flat_gas_limit {
+            return self.flat_gas_price;
+        }
+        let variable_gas = (gas - self.flat_gas_limit) as u128;
+        let variable_fee = (variable_gas * self.gas_price as u128 + 0xffff) >> 16;
+        self.flat_gas_price
+            .saturating_add(u64::try_from(variable_fee).unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose, Engine};
+    use tonlib_core::cell::{BagOfCells, Cell, CellBuilder};
+
+    use super::GasPrices;
+
+    fn gas_prices_ext_builder(gas_price: u64) -> CellBuilder {
+        let mut builder = CellBuilder::new();
+        builder.store_u8(8, 0xde).unwrap();
+        builder.store_u64(64, gas_price).unwrap();
+        // gas_limit, special_gas_limit, gas_credit, block_gas_limit, freeze_due_limit, delete_due_limit
+        for value in [
+            1_000_000u64,
+            70_000_000,
+            10_000,
+            10_000_000,
+            100_000_000,
+            1_000_000_000,
+        ] {
+            builder.store_u64(64, value).unwrap();
+        }
+        builder
+    }
+
+    fn to_boc(cell: Cell) -> String {
+        let boc = BagOfCells::from_root(cell).serialize(true).unwrap();
+        general_purpose::STANDARD.encode(boc)
+    }
+
+    #[test]
+    fn test_parse_gas_prices_ext() {
+        let boc = to_boc(gas_prices_ext_builder(26_214_400).build().unwrap());
+
+        let prices = GasPrices::from_boc(&boc).unwrap();
+
+        assert_eq!(
+            prices,
+            GasPrices {
+                flat_gas_limit: 0,
+                flat_gas_price: 0,
+                gas_price: 26_214_400,
+            }
+        );
+        assert_eq!(prices.compute_fee(10_000), 4_000_000);
+    }
+
+    #[test]
+    fn test_parse_gas_flat_prefix() {
+        let inner = gas_prices_ext_builder(26_214_400);
+        let mut builder = CellBuilder::new();
+        builder.store_u8(8, 0xd1).unwrap();
+        builder.store_u64(64, 100).unwrap();
+        builder.store_u64(64, 40_000).unwrap();
+        builder.store_cell_data(&inner.build().unwrap()).unwrap();
+        let boc = to_boc(builder.build().unwrap());
+
+        let prices = GasPrices::from_boc(&boc).unwrap();
+
+        assert_eq!(prices.flat_gas_limit, 100);
+        assert_eq!(prices.flat_gas_price, 40_000);
+        assert_eq!(prices.gas_price, 26_214_400);
+        assert_eq!(prices.compute_fee(50), 40_000);
+        assert_eq!(prices.compute_fee(10_100), 4_040_000);
+    }
+
+    #[test]
+    fn test_parse_gas_prices_unknown_tag() {
+        let mut builder = CellBuilder::new();
+        builder.store_u8(8, 0xaa).unwrap();
+        let boc = to_boc(builder.build().unwrap());
+
+        assert!(GasPrices::from_boc(&boc).is_err());
+    }
+}
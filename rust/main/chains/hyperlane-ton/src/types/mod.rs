@@ -1,8 +1,10 @@
 pub mod account_state;
 pub mod block_response;
+pub mod config_param;
 pub mod jetton_wallet_response;
 pub mod message;
 pub mod run_get_method;
 pub mod t_metadata;
+pub mod tonlib;
 pub mod transaction;
 pub mod wallet_state;
@@ -0,0 +1,89 @@
+//! Responses of the tonlib JSON-RPC interface. Tonlib encodes 64-bit integers as strings.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+pub struct TonlibResponse<T> {
+    pub ok: bool,
+    pub result: Option<T>,
+    pub error: Option<String>,
+    pub code: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TonlibTransactionId {
+    pub lt: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TonlibAddressInformation {
+    pub balance: String,
+    pub code: String,
+    pub data: String,
+    pub last_transaction_id: TonlibTransactionId,
+    pub frozen_hash: Option<String>,
+    /// `active`, `uninitialized` or `frozen`
+    pub state: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TonlibWalletInformation {
+    pub wallet: bool,
+    pub balance: String,
+    pub account_state: String,
+    pub wallet_type: Option<String>,
+    pub seqno: Option<u64>,
+    pub wallet_id: Option<u64>,
+    pub last_transaction_id: TonlibTransactionId,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TonlibBlockIdExt {
+    pub workchain: i32,
+    pub shard: String,
+    pub seqno: i32,
+    pub root_hash: String,
+    pub file_hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TonlibMasterchainInfo {
+    pub last: TonlibBlockIdExt,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TonlibBlockHeader {
+    pub id: TonlibBlockIdExt,
+    pub global_id: i32,
+    pub version: i32,
+    pub after_merge: bool,
+    pub after_split: bool,
+    pub before_split: bool,
+    pub want_merge: bool,
+    pub want_split: bool,
+    pub validator_list_hash_short: i32,
+    pub catchain_seqno: i32,
+    pub min_ref_mc_seqno: i32,
+    pub is_key_block: bool,
+    pub prev_key_block_seqno: i32,
+    pub start_lt: String,
+    pub end_lt: String,
+    pub gen_utime: i64,
+    pub vert_seqno: i32,
+    #[serde(default)]
+    pub prev_blocks: Vec<TonlibBlockIdExt>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TonlibRunGetMethodResult {
+    pub gas_used: u64,
+    pub exit_code: i32,
+    /// Entries of the form `[type, value]`
+    pub stack: Vec<(String, Value)>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TonlibExtMessageInfo {
+    pub hash: String,
+}
@@ -1,4 +1,5 @@
 pub const WORKCHAIN_MASTERCHAIN: i32 = -1;
+pub const WORKCHAIN_BASECHAIN: i32 = 0;
 pub const LIMIT: usize = 1000;
 
 pub const TRANSACTIONS_ENDPOINT: &str = "v3/transactions";
@@ -10,3 +11,11 @@ pub const RUN_GET_METHOD_ENDPOINT: &str = "v3/runGetMethod";
 pub const TRANSACTIONS_BY_MESSAGE_ENDPOINT: &str = "v3/transactionsByMessage";
 pub const BLOCKS_ENDPOINT: &str = "v3/blocks";
 pub const JETTON_WALLETS_ENDPOINT: &str = "v3/jetton/wallets";
+pub const CONFIG_PARAM_ENDPOINT: &str = "v2/getConfigParam";
+pub const TONLIB_JSON_RPC_ENDPOINT: &str = "jsonRPC";
+
+/// Shard id of the masterchain, which consists of a single shard.
+pub const MASTERCHAIN_SHARD: &str = "-9223372036854775808";
+
+pub const MASTERCHAIN_GAS_PRICES_CONFIG_ID: u32 = 20;
+pub const BASECHAIN_GAS_PRICES_CONFIG_ID: u32 = 21;
@@ -10,7 +10,7 @@ use tonlib_core::{
     },
     TonAddress, TonHash,
 };
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, HyperlaneMessage, TxnInfo, TxnReceiptInfo, H160, H256,
@@ -51,7 +51,9 @@ impl ConversionUtils {
     }
 
     pub fn metadata_to_cell(metadata: &[u8]) -> Result<Cell, TonCellError> {
-        let tmetadata = TMetadata::from_bytes(metadata).unwrap();
+        let tmetadata = TMetadata::from_bytes(metadata).map_err(|e| {
+            TonCellError::CellBuilderError(format!("Failed to parse metadata: {}", e))
+        })?;
         let mut writer = CellBuilder::new();
         writer
             .store_slice(&tmetadata.origin_merkle_hook)
@@ -163,6 +165,31 @@ impl ConversionUtils {
         }
     }
 
+    /// Parses a linked list of cells produced by `create_address_linked_cells`.
+    /// Each cell holds up to 3 addresses of 256 bits, the next segment is stored in the first reference.
+    pub fn parse_address_linked_cells(root_cell: &ArcCell) -> Result<Vec<H256>, TonCellError> {
+        let mut addresses = Vec::new();
+        let mut current_cell = root_cell.clone();
+
+        loop {
+            let addresses_in_cell = current_cell.bit_len() / 256;
+            let mut parser = current_cell.parser();
+
+            for _ in 0..addresses_in_cell {
+                let mut address = H256::zero();
+                parser.load_slice(&mut address.0)?;
+                addresses.push(address);
+            }
+
+            match current_cell.references().first() {
+                Some(next_cell) => current_cell = next_cell.clone(),
+                None => break,
+            }
+        }
+        debug!("Parsed {} addresses from linked cells", addresses.len());
+        Ok(addresses)
+    }
+
     /// Parses the root `root_cell` and extracts a dictionary of addresses with their storage locations.
     /// Uses a nested dictionary to store strings in the `BigUint -> Vec<String>` format.
     pub fn parse_address_storage_locations(
@@ -350,6 +377,30 @@ impl ConversionUtils {
             .build()
             .map_err(|e| TonCellError::CellBuilderError(format!("Failed to build cell: {:?}", e)))
     }
+
+    /// Builds the get-method stack passing `message` as a single cell argument
+    pub fn build_message_stack(message: &HyperlaneMessage) -> ChainResult<Option<Vec<StackItem>>> {
+        let message_cell = Self::build_hyperlane_message_cell(message).map_err(|e| {
+            ChainCommunicationError::from(HyperlaneTonError::FailedBuildingCell(format!(
+                "Failed to build HyperlaneMessage to Ton Cell: {:?}",
+                e
+            )))
+        })?;
+
+        let boc = BagOfCells::from_root(message_cell)
+            .serialize(true)
+            .map_err(|e| {
+                ChainCommunicationError::from(HyperlaneTonError::ParsingError(format!(
+                    "Failed to serialize message BOC: {:?}",
+                    e
+                )))
+            })?;
+
+        Ok(Some(vec![StackItem {
+            r#type: "cell".to_string(),
+            value: StackValue::String(general_purpose::STANDARD.encode(&boc)),
+        }]))
+    }
     pub fn parse_stack_item_biguint(
         stack: &[StackItem],
         index: usize,
@@ -394,6 +445,41 @@ impl ConversionUtils {
         })
     }
 
+    /// Parses a raw `workchain:hex` address, as returned by toncenter, into its account id.
+    pub fn parse_raw_address_to_h256(address: &str) -> Result<H256, HyperlaneTonError> {
+        Self::parse_raw_address(address).map(|(_, account_id)| account_id)
+    }
+
+    /// Parses a raw `workchain:hex` address into its workchain and account id.
+    pub fn parse_raw_address(address: &str) -> Result<(i32, H256), HyperlaneTonError> {
+        let (workchain, account_id) = address.split_once(':').ok_or_else(|| {
+            HyperlaneTonError::ParsingError(format!("Address is not in raw form: {}", address))
+        })?;
+
+        let workchain = workchain.parse::<i32>().map_err(|e| {
+            HyperlaneTonError::ParsingError(format!(
+                "Invalid workchain in address {}: {:?}",
+                address, e
+            ))
+        })?;
+
+        let bytes = hex::decode(account_id).map_err(|e| {
+            HyperlaneTonError::ParsingError(format!(
+                "Failed to decode account id of {}: {:?}",
+                address, e
+            ))
+        })?;
+        if bytes.len() != 32 {
+            return Err(HyperlaneTonError::ParsingError(format!(
+                "Account id of {} has unexpected length {}",
+                address,
+                bytes.len()
+            )));
+        }
+
+        Ok((workchain, H256::from_slice(&bytes)))
+    }
+
     pub fn parse_transaction(transaction: &Transaction) -> Result<TxnInfo, HyperlaneTonError> {
         let decoded_hash = general_purpose::STANDARD
             .decode(&transaction.hash)
@@ -428,45 +514,43 @@ impl ConversionUtils {
 
         let nonce = transaction.lt.parse::<u64>().unwrap_or(0);
 
-        let sender_hex = transaction.account.strip_prefix("0:").ok_or_else(|| {
-            warn!(
-                "Sender address does not start with '0:': {:?}",
-                transaction.account
-            );
-            HyperlaneTonError::ParsingError("Sender address is invalid".to_string())
+        let account = Self::parse_raw_address_to_h256(&transaction.account).map_err(|e| {
+            warn!("Account address is invalid: {:?}", transaction.account);
+            e
         })?;
 
-        let sender =
-            H256::from_slice(&hex::decode(sender_hex).map_err(|_| {
-                HyperlaneTonError::ParsingError("Failed to decode sender".to_string())
-            })?);
-
-        let recipient = transaction.in_msg.as_ref().and_then(|msg| {
-            if let Some(dest) = msg.destination.strip_prefix("0:") {
-                match hex::decode(dest) {
-                    Ok(decoded) if decoded.len() == 32 => Some(H256::from_slice(&decoded)),
-                    Ok(_) => {
-                        warn!("Recipient address has unexpected length: {:?}", dest);
-                        None
-                    }
-                    Err(e) => {
-                        warn!("Failed to decode recipient address '{}': {:?}", dest, e);
-                        None
-                    }
+        // External messages have no source, in which case the account itself initiated the transaction
+        let sender = transaction
+            .in_msg
+            .as_ref()
+            .and_then(|msg| msg.source.as_deref())
+            .and_then(|source| match Self::parse_raw_address_to_h256(source) {
+                Ok(source) => Some(source),
+                Err(e) => {
+                    warn!("Failed to parse source address '{}': {:?}", source, e);
+                    None
                 }
-            } else {
-                warn!("Recipient address format is invalid: {:?}", msg.destination);
-                None
-            }
-        });
+            })
+            .unwrap_or(account);
 
         let gas_used =
             U256::from_dec_str(&transaction.description.compute_ph.gas_used).unwrap_or_default();
+        let gas_fees =
+            U256::from_dec_str(&transaction.description.compute_ph.gas_fees).unwrap_or_default();
+        let effective_gas_price = gas_fees.checked_div(gas_used);
+
+        let raw_input_data = transaction.in_msg.as_ref().and_then(|msg| {
+            general_purpose::STANDARD
+                .decode(&msg.message_content.body)
+                .map_err(|e| warn!("Failed to decode in_msg body: {:?}", e))
+                .ok()
+        });
 
         let receipt = Some(TxnReceiptInfo {
             gas_used,
-            cumulative_gas_used: U256::zero(),
-            effective_gas_price: None,
+            // TON transactions are executed per account, so there is no block-wide accumulator
+            cumulative_gas_used: gas_used,
+            effective_gas_price,
         });
 
         let txn_info = TxnInfo {
@@ -474,12 +558,12 @@ impl ConversionUtils {
             gas_limit,
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
-            gas_price: None,
+            gas_price: effective_gas_price,
             nonce,
             sender,
-            recipient,
+            recipient: Some(account),
             receipt,
-            raw_input_data: None,
+            raw_input_data,
         };
 
         Ok(txn_info)
@@ -488,13 +572,18 @@ impl ConversionUtils {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use hyperlane_core::{H160, H256, H512, U256};
     use num_bigint::BigUint;
     use num_traits::Zero;
     use tonlib_core::TonAddress;
 
     use super::ConversionUtils;
-    use crate::run_get_method::{StackItem, StackValue};
+    use crate::{
+        run_get_method::{StackItem, StackValue},
+        transaction::Transaction,
+    };
 
     #[test]
     fn test_parse_address_from_boc() {
@@ -531,6 +620,26 @@ mod tests {
         assert!(result.is_err(), "Expected an error for invalid input");
     }
 
+    #[test]
+    fn test_parse_address_linked_cells_roundtrip() {
+        let addresses: Vec<H256> = (1..=7u8).map(|i| H256::from_slice(&[i; 32])).collect();
+
+        let cell = ConversionUtils::create_address_linked_cells(&addresses)
+            .expect("Failed to create linked cells");
+        let parsed = ConversionUtils::parse_address_linked_cells(&Arc::new(cell))
+            .expect("Failed to parse linked cells");
+
+        assert_eq!(parsed, addresses);
+    }
+    #[test]
+    fn test_parse_address_linked_cells_empty() {
+        let cell = ConversionUtils::create_address_linked_cells(&[])
+            .expect("Failed to create linked cells");
+        let parsed = ConversionUtils::parse_address_linked_cells(&Arc::new(cell))
+            .expect("Failed to parse linked cells");
+
+        assert!(parsed.is_empty());
+    }
     #[test]
     fn test_parse_root_cell_from_boc() {
         let boc_base64 = "te6cckEBAgEANwABQ6AAAAAAAAAAAAAAAAABcqZ6QdO0UVZJKOpooNx6WOrpGnABACBzdG9yYWdlIGxvY2F0aW9u3GbBUg==";
@@ -687,6 +796,127 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), BigUint::from(0x123abc_u32));
     }
+    const TRANSACTION_FIXTURE: &str = r#"{
+        "account": "0:AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF",
+        "hash": "emUQnddCZvrUNaMmy0eYGzRtHAVsdniV0x7EBpK6ON4=",
+        "lt": "28039020000003",
+        "now": 1733244911,
+        "orig_status": "active",
+        "end_status": "active",
+        "total_fees": "1635609",
+        "prev_trans_hash": "gnNFKLUFlUJhHd4YvRvbzH7w0ObYHgyfGVufDkI3avE=",
+        "prev_trans_lt": "28038997000001",
+        "description": {
+            "type": "ord",
+            "action": {
+                "valid": true,
+                "success": true,
+                "no_funds": false,
+                "result_code": 0,
+                "tot_actions": 1,
+                "msgs_created": 1,
+                "spec_actions": 0,
+                "tot_msg_size": {"bits": "1261", "cells": "3"},
+                "status_change": "unchanged",
+                "total_fwd_fees": "533200",
+                "skipped_actions": 0,
+                "action_list_hash": "6o1jd9z8ZNN5ZdH7jDmJYhrgYo0G6GNHmrPpq1fcK5s=",
+                "total_action_fees": "177734"
+            },
+            "aborted": false,
+            "destroyed": false,
+            "compute_ph": {
+                "mode": 0,
+                "type": "vm",
+                "success": true,
+                "gas_fees": "1234000",
+                "gas_used": "3085",
+                "vm_steps": 68,
+                "exit_code": 0,
+                "gas_limit": "0",
+                "gas_credit": "10000",
+                "msg_state_used": false,
+                "account_activated": false,
+                "vm_init_state_hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+                "vm_final_state_hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+            },
+            "storage_ph": {"status_change": "unchanged", "storage_fees_collected": "27"},
+            "credit_first": true
+        },
+        "block_ref": {"workchain": 0, "shard": "2000000000000000", "seqno": 26347112},
+        "in_msg": {
+            "hash": "PmMtgHFkzQ1Ae5BqWb1IjPGiwvWdMTpzW8e56tw/Oq0=",
+            "source": null,
+            "destination": "0:AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF",
+            "message_content": {
+                "hash": "a3Yv8rWvLyhMf3DQ0oW47Rd7AMkNlaVsL6s+Ysg8b5c=",
+                "body": "te6cckEBAQEAJAAAQ4AK6ETsEZndZnPkJ4gUxnX2otydTPtek+fiTAQfLC3C0JAaOf4x"
+            }
+        },
+        "out_msgs": [],
+        "account_state_before": {"hash": "k5ZL0ajb6iFgfCcrsjS8Bk0LF5TBLXF4u3rLfN9I6kY="},
+        "account_state_after": {"hash": "TMhZ3dChYDeLNWgnK6I5Jx3kyiPmBvhRHqnyffU6tOs="},
+        "mc_block_seqno": 28039020
+    }"#;
+
+    #[test]
+    fn test_parse_transaction() {
+        let transaction: Transaction = serde_json::from_str(TRANSACTION_FIXTURE).unwrap();
+
+        let txn_info = ConversionUtils::parse_transaction(&transaction).unwrap();
+
+        let account =
+            TonAddress::from_base64_url("UQCvsB60DElBwHpHOj26K9NfxGJgzes_5pzwV48QGxHar2F3")
+                .unwrap();
+        let account = ConversionUtils::ton_address_to_h256(&account);
+        assert_eq!(
+            &txn_info.hash.as_bytes()[32..],
+            ConversionUtils::base64_to_h256(&transaction.hash)
+                .unwrap()
+                .as_bytes()
+        );
+        assert_eq!(txn_info.nonce, 28039020000003);
+        // External message: the account is both sender and recipient
+        assert_eq!(txn_info.sender, account);
+        assert_eq!(txn_info.recipient, Some(account));
+        assert_eq!(txn_info.gas_price, Some(U256::from(400)));
+        let receipt = txn_info.receipt.unwrap();
+        assert_eq!(receipt.gas_used, U256::from(3085));
+        assert_eq!(receipt.effective_gas_price, Some(U256::from(400)));
+        assert!(txn_info.raw_input_data.is_some());
+    }
+
+    #[test]
+    fn test_parse_raw_address_to_h256_masterchain() {
+        let raw = "-1:AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF";
+
+        let result = ConversionUtils::parse_raw_address_to_h256(raw).unwrap();
+
+        assert_eq!(
+            result,
+            H256::from_slice(
+                &hex::decode("AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF")
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_raw_address_workchain() {
+        let raw = "-1:AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF";
+
+        let (workchain, _) = ConversionUtils::parse_raw_address(raw).unwrap();
+
+        assert_eq!(workchain, -1);
+    }
+
+    #[test]
+    fn test_parse_raw_address_to_h256_invalid() {
+        assert!(ConversionUtils::parse_raw_address_to_h256("AFB01EB4").is_err());
+        assert!(ConversionUtils::parse_raw_address_to_h256("x:AFB01EB4").is_err());
+        assert!(ConversionUtils::parse_raw_address_to_h256("0:AFB01EB4").is_err());
+    }
+
     #[test]
     fn test_parse_stack_item_biguint_invalid_index() {
         let stack: Vec<StackItem> = vec![];
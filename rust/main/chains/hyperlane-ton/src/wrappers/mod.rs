@@ -1 +1,2 @@
+pub mod recorded_server;
 pub mod test_context;
@@ -0,0 +1,141 @@
+//! Stand-in HTTP server replaying recorded API responses, used to exercise the
+//! toncenter and tonlib backends without network access.
+use std::net::SocketAddr;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tracing::warn;
+use url::Url;
+
+/// A recorded response, matched by request path and, for JSON-RPC requests,
+/// by the `method` field of the request body.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordedResponse {
+    pub path: String,
+    #[serde(default)]
+    pub rpc_method: Option<String>,
+    #[serde(default = "default_status")]
+    pub status: u16,
+    pub response: Value,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+pub struct RecordedServer {
+    address: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl RecordedServer {
+    /// Starts serving the recordings on a random local port.
+    pub async fn start(recordings: Vec<RecordedResponse>) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+
+        let handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let recordings = recordings.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = Self::serve(stream, &recordings).await {
+                        warn!("Recorded server failed to serve request: {:?}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { address, handle })
+    }
+
+    /// Starts serving recordings given as a JSON array of `RecordedResponse`.
+    pub async fn from_json(recordings: &str) -> std::io::Result<Self> {
+        let recordings = serde_json::from_str(recordings)?;
+        Self::start(recordings).await
+    }
+
+    /// Base url of the server, ending with a slash so endpoints can be joined to it.
+    pub fn url(&self) -> Url {
+        Url::parse(&format!("http://{}/", self.address)).expect("Invalid server url")
+    }
+
+    async fn serve(mut stream: TcpStream, recordings: &[RecordedResponse]) -> std::io::Result<()> {
+        let (path, body) = Self::read_request(&mut stream).await?;
+        let rpc_method = serde_json::from_slice::<Value>(&body)
+            .ok()
+            .and_then(|body| body.get("method")?.as_str().map(str::to_string));
+
+        let recording = recordings
+            .iter()
+            .find(|r| r.path == path && (r.rpc_method.is_none() || r.rpc_method == rpc_method));
+        let (status, response) = match recording {
+            Some(r) => (r.status, r.response.to_string()),
+            None => (
+                404,
+                json!({ "error": format!("No recording for {} {:?}", path, rpc_method) })
+                    .to_string(),
+            ),
+        };
+
+        let reply = format!(
+            "HTTP/1.1 {} Recorded\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            response.len(),
+            response
+        );
+        stream.write_all(reply.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    /// Reads a single HTTP/1.1 request, returning its path without the query and its body.
+    async fn read_request(stream: &mut TcpStream) -> std::io::Result<(String, Vec<u8>)> {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+
+        let headers_end = loop {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(std::io::ErrorKind::UnexpectedEof.into());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..headers_end]).to_string();
+        let path = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|target| target.split('?').next())
+            .unwrap_or_default()
+            .to_string();
+        let content_length = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        while buffer.len() < headers_end + content_length {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+
+        Ok((path, buffer.split_off(headers_end)))
+    }
+}
+
+impl Drop for RecordedServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
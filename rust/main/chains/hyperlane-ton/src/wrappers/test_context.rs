@@ -55,6 +55,7 @@ impl TestContext {
 
         let mailbox = TonMailbox {
             workchain: 0,
+            recipient_workchain: 0,
             mailbox_address: mailbox_address.parse()?,
             provider: provider.clone(),
             signer: signer.clone(),
@@ -1,7 +1,8 @@
 pub use error::*;
 use hyperlane_core::{
     GasPaymentKey, HyperlaneDomain, HyperlaneMessage, InterchainGasPayment,
-    InterchainGasPaymentMeta, MerkleTreeInsertion, PendingOperationStatus, H256,
+    InterchainGasPaymentMeta, MerkleTreeInsertion, OperationLifecycleLog, PendingOperationStatus,
+    H256,
 };
 pub use rocks::*;
 
@@ -80,6 +81,19 @@ pub trait HyperlaneDb: Send + Sync {
         message_id: &H256,
     ) -> DbResult<Option<PendingOperationStatus>>;
 
+    /// Store the lifecycle log of an operation by its message id
+    fn store_operation_lifecycle_by_message_id(
+        &self,
+        message_id: &H256,
+        log: &OperationLifecycleLog,
+    ) -> DbResult<()>;
+
+    /// Retrieve the lifecycle log of an operation by its message id
+    fn retrieve_operation_lifecycle_by_message_id(
+        &self,
+        message_id: &H256,
+    ) -> DbResult<Option<OperationLifecycleLog>>;
+
     fn store_interchain_gas_payment_data_by_gas_payment_key(
         &self,
         key: &GasPaymentKey,
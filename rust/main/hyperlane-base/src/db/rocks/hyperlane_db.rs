@@ -6,7 +6,7 @@ use hyperlane_core::{
     Decode, Encode, GasPaymentKey, HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage,
     HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexed,
     InterchainGasExpenditure, InterchainGasPayment, InterchainGasPaymentMeta, LogMeta,
-    MerkleTreeInsertion, PendingOperationStatus, H256,
+    MerkleTreeInsertion, OperationLifecycleLog, PendingOperationStatus, H256,
 };
 
 use super::{DbError, TypedDB, DB};
@@ -29,6 +29,7 @@ const GAS_PAYMENT_FOR_MESSAGE_ID: &str = "gas_payment_sequence_for_message_id_v2
 const GAS_PAYMENT_META_PROCESSED: &str = "gas_payment_meta_processed_v3_";
 const GAS_EXPENDITURE_FOR_MESSAGE_ID: &str = "gas_expenditure_for_message_id_v2_";
 const STATUS_BY_MESSAGE_ID: &str = "status_by_message_id_";
+const OPERATION_LIFECYCLE_BY_MESSAGE_ID: &str = "operation_lifecycle_by_message_id_";
 const PENDING_MESSAGE_RETRY_COUNT_FOR_MESSAGE_ID: &str =
     "pending_message_retry_count_for_message_id_";
 const MERKLE_TREE_INSERTION: &str = "merkle_tree_insertion_";
@@ -535,6 +536,23 @@ impl HyperlaneDb for HyperlaneRocksDB {
         self.retrieve_value_by_key(STATUS_BY_MESSAGE_ID, message_id)
     }
 
+    /// Store the lifecycle log of an operation by its message id
+    fn store_operation_lifecycle_by_message_id(
+        &self,
+        message_id: &H256,
+        log: &OperationLifecycleLog,
+    ) -> DbResult<()> {
+        self.store_value_by_key(OPERATION_LIFECYCLE_BY_MESSAGE_ID, message_id, log)
+    }
+
+    /// Retrieve the lifecycle log of an operation by its message id
+    fn retrieve_operation_lifecycle_by_message_id(
+        &self,
+        message_id: &H256,
+    ) -> DbResult<Option<OperationLifecycleLog>> {
+        self.retrieve_value_by_key(OPERATION_LIFECYCLE_BY_MESSAGE_ID, message_id)
+    }
+
     fn store_interchain_gas_payment_data_by_gas_payment_key(
         &self,
         key: &GasPaymentKey,
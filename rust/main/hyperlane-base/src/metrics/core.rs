@@ -47,6 +47,8 @@ pub struct CoreMetrics {
     merkle_tree_ingest_message_ids_count: IntCounterVec,
 
     submitter_queue_length: IntGaugeVec,
+    submitter_held_operations: IntGaugeVec,
+    submitter_window_spend: GaugeVec,
 
     operations_processed_count: IntCounterVec,
     messages_processed_count: IntCounterVec,
@@ -195,6 +197,26 @@ impl CoreMetrics {
             registry
         )?;
 
+        let submitter_held_operations = register_int_gauge_vec_with_registry!(
+            opts!(
+                namespaced!("submitter_held_operations"),
+                "Number of operations held back from submission by a submission limit",
+                const_labels_ref
+            ),
+            &["remote", "reason"],
+            registry
+        )?;
+
+        let submitter_window_spend = register_gauge_vec_with_registry!(
+            opts!(
+                namespaced!("submitter_window_spend"),
+                "Native tokens spent on submissions during the current spend budget window",
+                const_labels_ref
+            ),
+            &["remote"],
+            registry
+        )?;
+
         let latest_checkpoint = register_int_gauge_vec_with_registry!(
             opts!(
                 namespaced!("latest_checkpoint"),
@@ -243,6 +265,8 @@ impl CoreMetrics {
             merkle_tree_ingest_message_ids_count,
 
             submitter_queue_length,
+            submitter_held_operations,
+            submitter_window_spend,
 
             operations_processed_count,
             messages_processed_count,
@@ -436,6 +460,25 @@ impl CoreMetrics {
         self.submitter_queue_length.clone()
     }
 
+    /// Number of operations a Submitter holds back because a submission limit
+    /// for its destination was reached.
+    ///
+    /// Labels:
+    /// - `remote`: Remote chain the operations are for.
+    /// - `reason`: Which limit was reached.
+    pub fn submitter_held_operations(&self) -> IntGaugeVec {
+        self.submitter_held_operations.clone()
+    }
+
+    /// Native tokens (in the smallest unit) a Submitter spent on transactions
+    /// during its current spend budget window.
+    ///
+    /// Labels:
+    /// - `remote`: Remote chain the transactions were submitted to.
+    pub fn submitter_window_spend(&self) -> GaugeVec {
+        self.submitter_window_spend.clone()
+    }
+
     /// The number of operations successfully submitted by this process during
     /// its lifetime.
     ///
@@ -173,6 +173,20 @@ impl ChainConnectionConf {
             Self::Ethereum(conf) => Some(&conf.operation_batch),
             Self::Cosmos(conf) => Some(&conf.operation_batch),
             Self::Sealevel(conf) => Some(&conf.operation_batch),
+            Self::Ton(conf) => Some(&conf.operation_batch),
+            _ => None,
+        }
+    }
+
+    /// Get the number of decimals of this chain's native token, if known.
+    pub fn native_token_decimals(&self) -> Option<u32> {
+        match self {
+            // Gas is paid in wei
+            Self::Ethereum(_) => Some(18),
+            Self::Cosmos(conf) => Some(conf.get_native_token().decimals),
+            Self::Sealevel(conf) => Some(conf.native_token.decimals),
+            // Gas is paid in nanotons
+            Self::Ton(_) => Some(9),
             _ => None,
         }
     }
@@ -234,7 +248,10 @@ impl ChainConf {
                 h_cosmos::application::CosmosApplicationOperationVerifier::new(),
             )
                 as Box<dyn ApplicationOperationVerifier>),
-            ChainConnectionConf::Ton(_) => todo!(),
+            ChainConnectionConf::Ton(_conf) => Ok(Box::new(
+                h_ton::application::TonApplicationOperationVerifier::new(),
+            )
+                as Box<dyn ApplicationOperationVerifier>),
         };
 
         result.context(ctx)
@@ -262,11 +279,10 @@ impl ChainConf {
                 let provider = build_cosmos_provider(self, conf, metrics, &locator, None)?;
                 Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
             }
-            ChainConnectionConf::Ton(conf) => Ok(Box::new(TonProvider::new(
-                reqwest::Client::new(),
-                conf.clone(),
-                locator.domain.clone(),
-            )) as Box<dyn HyperlaneProvider>),
+            ChainConnectionConf::Ton(conf) => {
+                let provider = build_ton_provider(self, conf, metrics, &locator);
+                Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
+            }
         }
         .context(ctx)
     }
@@ -314,14 +330,20 @@ impl ChainConf {
                     .map_err(Into::into)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
+                let workchain = conf.workchains.mailbox;
                 let mailbox_address =
-                    ConversionUtils::h256_to_ton_address(&self.addresses.mailbox, 0);
+                    ConversionUtils::h256_to_ton_address(&self.addresses.mailbox, workchain);
 
                 let signer = self.ton_signer().await.context(ctx)?;
 
-                let mailbox = h_ton::TonMailbox::new(mailbox_address, provider, 0, signer.unwrap());
+                let mailbox = h_ton::TonMailbox::new(
+                    mailbox_address,
+                    provider,
+                    workchain,
+                    conf.workchains.recipient,
+                    signer.unwrap(),
+                );
 
                 Ok(Box::new(mailbox) as Box<dyn Mailbox>)
             }
@@ -361,11 +383,12 @@ impl ChainConf {
                 Ok(Box::new(hook) as Box<dyn MerkleTreeHook>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
 
-                let address =
-                    ConversionUtils::h256_to_ton_address(&self.addresses.merkle_tree_hook, 0);
+                let address = ConversionUtils::h256_to_ton_address(
+                    &self.addresses.merkle_tree_hook,
+                    conf.workchains.merkle_tree_hook,
+                );
                 let hook = TonMerkleTreeHook::new(provider, address)?;
                 Ok(Box::new(hook) as Box<dyn MerkleTreeHook>)
             }
@@ -434,14 +457,20 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
                 let signer = self.ton_signer().await.context(ctx)?;
 
+                let workchain = conf.workchains.mailbox;
                 let mailbox_address =
-                    ConversionUtils::h256_to_ton_address(&self.addresses.mailbox, 0);
+                    ConversionUtils::h256_to_ton_address(&self.addresses.mailbox, workchain);
 
-                let mailbox = h_ton::TonMailbox::new(mailbox_address, provider, 0, signer.unwrap());
+                let mailbox = h_ton::TonMailbox::new(
+                    mailbox_address,
+                    provider,
+                    workchain,
+                    conf.workchains.recipient,
+                    signer.unwrap(),
+                );
 
                 let indexer = Box::new(h_ton::TonMailboxIndexer { mailbox });
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
@@ -502,14 +531,20 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
                 let signer = self.ton_signer().await.context(ctx)?;
 
+                let workchain = conf.workchains.mailbox;
                 let mailbox_address =
-                    ConversionUtils::h256_to_ton_address(&self.addresses.mailbox, 0);
+                    ConversionUtils::h256_to_ton_address(&self.addresses.mailbox, workchain);
 
-                let mailbox = h_ton::TonMailbox::new(mailbox_address, provider, 0, signer.unwrap());
+                let mailbox = h_ton::TonMailbox::new(
+                    mailbox_address,
+                    provider,
+                    workchain,
+                    conf.workchains.recipient,
+                    signer.unwrap(),
+                );
 
                 let indexer = Box::new(h_ton::TonMailboxIndexer { mailbox });
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
@@ -557,23 +592,21 @@ impl ChainConf {
                 Ok(paymaster as Box<dyn InterchainGasPaymaster>)
             }
             ChainConnectionConf::Ton(conf) => {
-                use tonlib_core::TonAddress;
-
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
 
                 let signer = self.ton_signer().await.context(ctx)?;
 
-                let igp_address = TonAddress::from_base64_url(
-                    &self.addresses.interchain_gas_paymaster.to_string(),
-                )
-                .expect("Failed to convert IGP address");
+                let workchain = conf.workchains.interchain_gas_paymaster;
+                let igp_address = ConversionUtils::h256_to_ton_address(
+                    &self.addresses.interchain_gas_paymaster,
+                    workchain,
+                );
 
                 let paymaster = h_ton::TonInterchainGasPaymaster {
                     igp_address,
                     provider,
                     signer: signer.unwrap(),
-                    workchain: 0,
+                    workchain,
                 };
 
                 Ok(Box::new(paymaster) as Box<dyn InterchainGasPaymaster>)
@@ -638,11 +671,10 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
                 let igp_address = ConversionUtils::h256_to_ton_address(
                     &self.addresses.interchain_gas_paymaster,
-                    0,
+                    conf.workchains.interchain_gas_paymaster,
                 );
 
                 let indexer = Box::new(h_ton::TonInterchainGasPaymasterIndexer::new(
@@ -716,11 +748,12 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
 
-                let address =
-                    ConversionUtils::h256_to_ton_address(&self.addresses.merkle_tree_hook, 0);
+                let address = ConversionUtils::h256_to_ton_address(
+                    &self.addresses.merkle_tree_hook,
+                    conf.workchains.merkle_tree_hook,
+                );
                 let indexer = Box::new(TonMerkleTreeHookIndexer::new(address, provider)?);
 
                 Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
@@ -762,10 +795,11 @@ impl ChainConf {
                 Ok(va as Box<dyn ValidatorAnnounce>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
-                let validator_announce_address =
-                    ConversionUtils::h256_to_ton_address(&self.addresses.validator_announce, 0);
+                let provider = build_ton_provider(self, conf, metrics, &locator);
+                let validator_announce_address = ConversionUtils::h256_to_ton_address(
+                    &self.addresses.validator_announce,
+                    conf.workchains.validator_announce,
+                );
 
                 let signer = self.ton_signer().await.context(ctx)?;
 
@@ -824,9 +858,10 @@ impl ChainConf {
             }
             ChainConnectionConf::Ton(conf) => {
                 let signer = self.ton_signer().await.context(ctx)?;
+                let provider = build_ton_provider(self, conf, metrics, &locator);
                 let ism = Box::new(h_ton::TonInterchainSecurityModule::new(
                     locator,
-                    conf.clone(),
+                    provider,
                     signer.unwrap(),
                 ));
                 Ok(ism as Box<dyn InterchainSecurityModule>)
@@ -870,10 +905,10 @@ impl ChainConf {
                 Ok(ism as Box<dyn MultisigIsm>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
 
-                let multisig_address = ConversionUtils::h256_to_ton_address(&address, 0);
+                let multisig_address =
+                    ConversionUtils::h256_to_ton_address(&address, conf.workchains.ism);
 
                 let ism = h_ton::TonMultisigIsm::new(provider, multisig_address);
 
@@ -912,10 +947,9 @@ impl ChainConf {
                 Ok(ism as Box<dyn RoutingIsm>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
 
-                let address = ConversionUtils::h256_to_ton_address(&address, 0);
+                let address = ConversionUtils::h256_to_ton_address(&address, conf.workchains.ism);
                 let ism = Box::new(TonRoutingIsm::new(provider, address)?);
 
                 Ok(ism as Box<dyn RoutingIsm>)
@@ -956,10 +990,9 @@ impl ChainConf {
                 Ok(ism as Box<dyn AggregationIsm>)
             }
             ChainConnectionConf::Ton(conf) => {
-                let provider =
-                    TonProvider::new(reqwest::Client::new(), conf.clone(), locator.domain.clone());
+                let provider = build_ton_provider(self, conf, metrics, &locator);
 
-                let address = ConversionUtils::h256_to_ton_address(&address, 0);
+                let address = ConversionUtils::h256_to_ton_address(&address, conf.workchains.ism);
                 let ism = Box::new(TonAggregationIsm::new(provider, address)?);
 
                 Ok(ism as Box<dyn AggregationIsm>)
@@ -1174,6 +1207,24 @@ fn build_sealevel_tx_submitter(
     )
 }
 
+/// Helper to build a TON provider with per-endpoint metrics
+fn build_ton_provider(
+    chain_conf: &ChainConf,
+    connection_conf: &h_ton::TonConnectionConf,
+    metrics: &CoreMetrics,
+    locator: &ContractLocator,
+) -> TonProvider {
+    let middleware_metrics = chain_conf.metrics_conf();
+    let client_metrics = metrics.client_metrics();
+    TonProvider::with_metrics(
+        reqwest::Client::new(),
+        connection_conf.clone(),
+        locator.domain.clone(),
+        client_metrics,
+        middleware_metrics.chain.clone(),
+    )
+}
+
 fn build_cosmos_provider(
     chain_conf: &ChainConf,
     connection_conf: &h_cosmos::ConnectionConf,
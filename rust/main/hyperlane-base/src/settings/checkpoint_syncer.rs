@@ -1,14 +1,19 @@
 use crate::{
-    CheckpointSyncer, GcsStorageClientBuilder, LocalStorage, S3Storage, GCS_SERVICE_ACCOUNT_KEY,
-    GCS_USER_SECRET,
+    validate_azure_account, AzureBlobStorage, CheckpointSyncer, GcsStorageClientBuilder,
+    HttpStorage, LocalStorage, S3Storage, GCS_SERVICE_ACCOUNT_KEY, GCS_USER_SECRET,
 };
 use core::str::FromStr;
 use eyre::{eyre, Context, Report, Result};
 use hyperlane_core::{ChainCommunicationError, ReorgEvent};
 use prometheus::IntGauge;
 use rusoto_core::Region;
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    net::{Ipv4Addr, Ipv6Addr},
+    path::PathBuf,
+};
 use tracing::error;
+use url::{Host, Url};
 use ya_gcp::{AuthFlow, ServiceAccountAuth};
 
 /// Checkpoint Syncer types
@@ -40,6 +45,22 @@ pub enum CheckpointSyncerConf {
         /// `gcloud auth application-default login`
         user_secrets: Option<String>,
     },
+    /// A checkpoint syncer on Azure Blob Storage
+    Azure {
+        /// Storage account name
+        account: String,
+        /// Container name
+        container: String,
+        /// Folder name inside container - defaults to the root of the container
+        folder: Option<String>,
+        /// SAS token authorizing writes, and reads from private containers
+        sas_token: Option<String>,
+    },
+    /// A read-only checkpoint syncer fetching from an HTTP(S) url
+    Http {
+        /// Url the checkpoints are published under
+        url: Url,
+    },
 }
 
 /// Checkpoint Syncer errors
@@ -110,12 +131,53 @@ impl FromStr for CheckpointSyncerConf {
                     }),
                 }
             }
+            // Locations parsed from strings may have been announced by anyone, so they
+            // never get the SAS token of the agent's own syncer
+            "azure" => {
+                let url_components = suffix.split('/').collect::<Vec<&str>>();
+                let (account, container, folder): (&str, &str, Option<String>) = match url_components.len() {
+                    2 => Ok((url_components[0], url_components[1], None)),
+                    3 .. => Ok((url_components[0], url_components[1], Some(url_components[2..].join("/")))),
+                    _ => Err(eyre!("Error parsing storage location; could not split account, container and folder ({suffix})"))
+                }?;
+                validate_azure_account(account)?;
+                Ok(CheckpointSyncerConf::Azure {
+                    account: account.into(),
+                    container: container.into(),
+                    folder,
+                    sas_token: None,
+                })
+            }
+            "http" | "https" => Ok(CheckpointSyncerConf::Http {
+                url: s
+                    .parse()
+                    .context("Invalid url when parsing storage location")?,
+            }),
             _ => Err(eyre!("Unknown storage location prefix `{prefix}`")),
         }
     }
 }
 
 impl CheckpointSyncerConf {
+    /// Whether the syncer reads from the local machine or network.
+    ///
+    /// Only the literal host of HTTP locations is checked. Domain names are not
+    /// resolved, so a public name pointing at a local address is not detected.
+    pub fn is_local(&self) -> bool {
+        match self {
+            CheckpointSyncerConf::LocalStorage { .. } => true,
+            CheckpointSyncerConf::Http { url } => match url.host() {
+                Some(Host::Domain(domain)) => {
+                    domain == "localhost" || domain.ends_with(".localhost")
+                }
+                Some(Host::Ipv4(ip)) => is_local_ipv4(&ip),
+                Some(Host::Ipv6(ip)) => is_local_ipv6(&ip),
+                None => true,
+            },
+            _ => false,
+        }
+    }
+
     /// Turn conf info a Checkpoint Syncer
     ///
     /// # Panics
@@ -183,14 +245,101 @@ impl CheckpointSyncerConf {
                         .await?,
                 )
             }
+            CheckpointSyncerConf::Azure {
+                account,
+                container,
+                folder,
+                sas_token,
+            } => Box::new(AzureBlobStorage::new(
+                account.clone(),
+                container.clone(),
+                folder.clone(),
+                sas_token.clone(),
+                latest_index_gauge,
+            )?),
+            CheckpointSyncerConf::Http { url } => {
+                Box::new(HttpStorage::new(url.clone(), latest_index_gauge)?)
+            }
         })
     }
 }
 
+fn is_local_ipv4(ip: &Ipv4Addr) -> bool {
+    ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified()
+}
+
+fn is_local_ipv6(ip: &Ipv6Addr) -> bool {
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return is_local_ipv4(&ip);
+    }
+    let first_segment = ip.segments()[0];
+    ip.is_loopback()
+        || ip.is_unspecified()
+        // unique local addresses, fc00::/7
+        || first_segment & 0xfe00 == 0xfc00
+        // link-local addresses, fe80::/10
+        || first_segment & 0xffc0 == 0xfe80
+}
+
 #[cfg(test)]
 mod test {
     use hyperlane_core::{ReorgPeriod, H256};
 
+    #[test]
+    fn test_parse_azure_and_http_locations() {
+        use super::*;
+
+        let conf = CheckpointSyncerConf::from_str("azure://account/container/some/folder").unwrap();
+        assert!(matches!(
+            conf,
+            CheckpointSyncerConf::Azure { account, container, folder, .. }
+                if account == "account"
+                    && container == "container"
+                    && folder.as_deref() == Some("some/folder")
+        ));
+        assert!(CheckpointSyncerConf::from_str("azure://account").is_err());
+        // Account names end up in the endpoint host
+        for account in ["ab", "Account", "evil.example#", "a".repeat(25).as_str()] {
+            assert!(
+                CheckpointSyncerConf::from_str(&format!("azure://{account}/container")).is_err()
+            );
+        }
+
+        let conf = CheckpointSyncerConf::from_str("https://gateway.example/ipfs/cid").unwrap();
+        assert!(matches!(
+            conf,
+            CheckpointSyncerConf::Http { url } if url.as_str() == "https://gateway.example/ipfs/cid"
+        ));
+    }
+
+    #[test]
+    fn test_is_local() {
+        use super::*;
+
+        for location in [
+            "file:///tmp/checkpoints",
+            "http://localhost:8080/checkpoints",
+            "https://127.0.0.1/checkpoints",
+            "https://10.0.0.5/checkpoints",
+            "https://169.254.169.254/latest",
+            "https://[::1]/checkpoints",
+            "https://[fd00::1]/checkpoints",
+            "https://[::ffff:192.168.0.1]/checkpoints",
+        ] {
+            let conf = CheckpointSyncerConf::from_str(location).unwrap();
+            assert!(conf.is_local(), "{location} should be local");
+        }
+        for location in [
+            "https://gateway.example/ipfs/cid",
+            "https://8.8.8.8/checkpoints",
+            "s3://bucket/us-east-1",
+            "azure://account/container",
+        ] {
+            let conf = CheckpointSyncerConf::from_str(location).unwrap();
+            assert!(!conf.is_local(), "{location} should not be local");
+        }
+    }
+
     #[tokio::test]
     async fn test_build_and_validate() {
         use super::*;
@@ -6,6 +6,9 @@ use itertools::Itertools;
 
 /// A source for loading configuration from command line arguments.
 ///
+/// Arguments preceding the first key select a subcommand of the agent and are
+/// skipped.
+///
 /// * `--key=value`
 /// * `--key="value"`
 /// * `--key='value'`
@@ -67,6 +70,7 @@ impl Source for CommandLineArguments {
         } else {
             ArgumentParser::from_env()
         };
+        args.skip_subcommand();
 
         while let Some((key, value)) = args
             .next()
@@ -117,6 +121,12 @@ impl ArgumentParser {
         ArgumentParser(args)
     }
 
+    /// Removes the arguments preceding the first key, which select a subcommand.
+    fn skip_subcommand(&mut self) {
+        let subcommand_len = self.index_of_next_key().unwrap_or(self.0.len());
+        self.0.drain(..subcommand_len);
+    }
+
     /// Returns a list of remaining arguments.
     ///
     /// It's up to the caller what to do with them.
@@ -314,6 +324,18 @@ mod test {
         assert!(config.is_empty());
     }
 
+    #[test]
+    fn skips_subcommand() {
+        let mut config = CommandLineArguments::default()
+            .source(["audit", "report", "--key-a", "value-a"])
+            .collect()
+            .unwrap();
+
+        assert_arg!(config, "key.a", "value-a");
+
+        assert!(config.is_empty());
+    }
+
     #[test]
     fn ignore_empty() {
         let mut config = CommandLineArguments::default()
@@ -356,45 +356,266 @@ fn build_ton_connection_conf(
     rpcs: &[Url],
     chain: &ValueParser,
     err: &mut ConfigParsingError,
+    operation_batch: OperationBatchConfig,
 ) -> Option<ChainConnectionConf> {
-    let url = rpcs
-        .get(0)
-        .cloned()
-        .ok_or_else(|| {
+    if rpcs.is_empty() {
+        err.push(
+            chain.cwp.clone().join("url"),
+            eyre!("Missing URL for TonConnectionConf"),
+        );
+        return None;
+    }
+
+    let api_backend = parse_ton_api_backend(chain, err)?;
+
+    // A self-hosted tonlib endpoint does not necessarily require a key
+    let api_key = match api_backend {
+        hyperlane_ton::TonApiBackend::TonCenter => chain
+            .chain(err)
+            .get_key("api_key")
+            .parse_string()
+            .end()
+            .or_else(|| {
+                err.push(
+                    chain.cwp.clone().join("api_key"),
+                    eyre!("Missing API key for TonConnectionConf"),
+                );
+                None
+            })?,
+        hyperlane_ton::TonApiBackend::Tonlib { .. } => chain
+            .chain(err)
+            .get_opt_key("api_key")
+            .parse_string()
+            .unwrap_or_default(),
+    };
+
+    let endpoints = parse_ton_endpoints(rpcs, &api_key, chain, err)?;
+
+    let max_attempts = chain
+        .chain(err)
+        .get_opt_key("maxAttempts")
+        .parse_u16()
+        .end()
+        .unwrap_or(3);
+
+    let fee_schedule = parse_ton_fee_schedule(chain, err);
+    let workchains = parse_ton_contract_workchains(chain, err);
+
+    Some(ChainConnectionConf::Ton(hyperlane_ton::TonConnectionConf {
+        fee_schedule,
+        workchains,
+        api_backend,
+        operation_batch,
+        ..hyperlane_ton::TonConnectionConf::with_endpoints(
+            endpoints,
+            max_attempts.try_into().unwrap(),
+        )
+    }))
+}
+
+/// Endpoints in the order of `rpcUrls`. Endpoints share `api_key` unless
+/// `api_keys` lists a key for every url.
+fn parse_ton_endpoints(
+    rpcs: &[Url],
+    api_key: &str,
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<Vec<hyperlane_ton::TonEndpoint>> {
+    let api_keys = chain
+        .chain(err)
+        .get_opt_key("api_keys")
+        .into_array_iter()
+        .map(|keys| {
+            keys.filter_map(|key| key.chain(err).parse_string().end().map(str::to_string))
+                .collect::<Vec<_>>()
+        });
+
+    let api_keys = match api_keys {
+        Some(api_keys) if api_keys.len() != rpcs.len() => {
             err.push(
-                chain.cwp.clone().join("url"),
-                eyre!("Missing URL for TonConnectionConf"),
+                chain.cwp.clone().join("api_keys"),
+                eyre!(
+                    "Expected {} API keys, one for each rpc url, got {}",
+                    rpcs.len(),
+                    api_keys.len()
+                ),
             );
-        })
-        .ok()?;
+            return None;
+        }
+        Some(api_keys) => api_keys,
+        None => vec![api_key.to_string(); rpcs.len()],
+    };
 
-    let api_key = chain
+    Some(
+        rpcs.iter()
+            .zip(api_keys)
+            .map(|(url, api_key)| hyperlane_ton::TonEndpoint {
+                url: url.clone(),
+                api_key,
+            })
+            .collect(),
+    )
+}
+
+fn parse_ton_api_backend(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<hyperlane_ton::TonApiBackend> {
+    let api_backend = chain
         .chain(err)
-        .get_key("api_key")
+        .get_opt_key("apiBackend")
         .parse_string()
-        .end()
-        .or_else(|| {
+        .unwrap_or("toncenter");
+
+    match api_backend.to_lowercase().as_str() {
+        "toncenter" => Some(hyperlane_ton::TonApiBackend::TonCenter),
+        "tonlib" => {
+            let indexer_url = chain
+                .chain(err)
+                .get_opt_key("indexerUrl")
+                .parse_from_str("Invalid indexerUrl")
+                .end();
+            Some(hyperlane_ton::TonApiBackend::Tonlib { indexer_url })
+        }
+        _ => {
             err.push(
-                chain.cwp.clone().join("api_key"),
-                eyre!("Missing API key for TonConnectionConf"),
+                chain.cwp.clone().join("apiBackend"),
+                eyre!("Unknown TON API backend `{api_backend}`, expected `toncenter` or `tonlib`"),
             );
             None
-        })?;
+        }
+    }
+}
 
-    let max_attempts = chain
+fn parse_ton_fee_schedule(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> hyperlane_ton::TonFeeSchedule {
+    let mut fee_schedule = hyperlane_ton::TonFeeSchedule::default();
+
+    if let Some(default_gas) = chain
         .chain(err)
-        .get_opt_key("maxAttempts")
-        .parse_u16()
+        .get_opt_key("feeSchedule")
+        .get_opt_key("defaultGas")
+        .parse_u64()
         .end()
-        .unwrap_or(3);
+    {
+        fee_schedule.default_gas = default_gas;
+    }
 
-    Some(ChainConnectionConf::Ton(
-        hyperlane_ton::TonConnectionConf::new(
-            url,
-            api_key.to_string(),
-            max_attempts.try_into().unwrap(),
+    let opcode_gas = chain
+        .chain(err)
+        .get_opt_key("feeSchedule")
+        .get_opt_key("opcodeGas")
+        .into_obj_iter()
+        .into_iter()
+        .flatten();
+    for (opcode, gas) in opcode_gas {
+        let Some(opcode) = parse_ton_opcode(&opcode) else {
+            err.push(
+                gas.cwp.clone(),
+                eyre!("Invalid opcode `{opcode}` in TON fee schedule, expected 0x-prefixed hex or decimal"),
+            );
+            continue;
+        };
+        if let Some(gas) = gas.chain(err).parse_u64().end() {
+            fee_schedule.opcode_gas.insert(opcode, gas);
+        }
+    }
+
+    fee_schedule
+}
+
+/// Parses an opcode given as `0x`-prefixed hex or as decimal
+fn parse_ton_opcode(opcode: &str) -> Option<u32> {
+    match opcode
+        .strip_prefix("0x")
+        .or_else(|| opcode.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => opcode.parse().ok(),
+    }
+}
+
+fn parse_ton_contract_workchains(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> hyperlane_ton::TonContractWorkchains {
+    let mut workchains = hyperlane_ton::TonContractWorkchains::default();
+
+    for (key, workchain) in [
+        ("mailbox", &mut workchains.mailbox),
+        (
+            "interchainGasPaymaster",
+            &mut workchains.interchain_gas_paymaster,
         ),
-    ))
+        ("merkleTreeHook", &mut workchains.merkle_tree_hook),
+        ("validatorAnnounce", &mut workchains.validator_announce),
+        ("ism", &mut workchains.ism),
+        ("recipient", &mut workchains.recipient),
+    ] {
+        if let Some(parsed) = parse_ton_contract_workchain(chain, key, err) {
+            *workchain = parsed;
+        }
+    }
+
+    workchains
+}
+
+/// Parses `workchains.<key>`, given either as a workchain id or as the full raw
+/// `workchain:hex` address of the contract. A raw address must match the
+/// contract address configured under `<key>`.
+fn parse_ton_contract_workchain(
+    chain: &ValueParser,
+    key: &str,
+    err: &mut ConfigParsingError,
+) -> Option<i32> {
+    use hyperlane_ton::constants::{WORKCHAIN_BASECHAIN, WORKCHAIN_MASTERCHAIN};
+
+    let value = chain
+        .chain(err)
+        .get_opt_key("workchains")
+        .get_opt_key(key)
+        .end()?;
+
+    let is_raw_address = value.val.as_str().is_some_and(|s| s.contains(':'));
+    let workchain = if is_raw_address {
+        let raw_address = value.chain(err).parse_string().end()?;
+        let (workchain, account_id) =
+            match hyperlane_ton::ConversionUtils::parse_raw_address(raw_address) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    err.push(
+                        value.cwp.clone(),
+                        eyre!("Invalid raw TON address `{raw_address}`: {e}"),
+                    );
+                    return None;
+                }
+            };
+        let configured = chain.chain(err).get_opt_key(key).parse_address_hash().end();
+        if let Some(configured) = configured {
+            if configured != account_id {
+                err.push(
+                    value.cwp.clone(),
+                    eyre!("Raw TON address `{raw_address}` does not match `{key}` address {configured:?}"),
+                );
+                return None;
+            }
+        }
+        workchain
+    } else {
+        value.chain(err).parse_i32().end()?
+    };
+
+    if workchain != WORKCHAIN_BASECHAIN && workchain != WORKCHAIN_MASTERCHAIN {
+        err.push(
+            value.cwp.clone(),
+            eyre!("Unsupported TON workchain {workchain} for `{key}`, expected 0 or -1"),
+        );
+        return None;
+    }
+
+    Some(workchain)
 }
 
 pub fn build_connection_conf(
@@ -424,6 +645,148 @@ pub fn build_connection_conf(
         HyperlaneDomainProtocol::Cosmos => {
             build_cosmos_connection_conf(rpcs, chain, err, operation_batch)
         }
-        HyperlaneDomainProtocol::Ton => build_ton_connection_conf(rpcs, chain, err),
+        HyperlaneDomainProtocol::Ton => {
+            build_ton_connection_conf(rpcs, chain, err, operation_batch)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    const MAILBOX: &str = "0xafb01eb40c4941c07a473a3dba2bd35fc46260cdeb3fe69cf0578f101b11daaf";
+    const RAW_MAILBOX: &str = "-1:AFB01EB40C4941C07A473A3DBA2BD35FC46260CDEB3FE69CF0578F101B11DAAF";
+
+    #[test]
+    fn test_parse_ton_contract_workchains() {
+        let value = json!({
+            "mailbox": MAILBOX,
+            "workchains": {
+                "mailbox": RAW_MAILBOX,
+                "merkleTreeHook": -1,
+                "ism": "-1"
+            }
+        });
+        let chain = ValueParser::new(Default::default(), &value);
+        let mut err = ConfigParsingError::default();
+
+        let workchains = parse_ton_contract_workchains(&chain, &mut err);
+
+        assert!(err.is_ok());
+        assert_eq!(
+            workchains,
+            hyperlane_ton::TonContractWorkchains {
+                mailbox: -1,
+                interchain_gas_paymaster: 0,
+                merkle_tree_hook: -1,
+                validator_announce: 0,
+                ism: -1,
+                recipient: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_ton_contract_workchains_address_mismatch() {
+        let value = json!({
+            "mailbox": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "workchains": { "mailbox": RAW_MAILBOX }
+        });
+        let chain = ValueParser::new(Default::default(), &value);
+        let mut err = ConfigParsingError::default();
+
+        parse_ton_contract_workchains(&chain, &mut err);
+
+        assert!(!err.is_ok());
+    }
+
+    #[test]
+    fn test_parse_ton_contract_workchains_unsupported_workchain() {
+        let value = json!({ "workchains": { "validatorAnnounce": 1 } });
+        let chain = ValueParser::new(Default::default(), &value);
+        let mut err = ConfigParsingError::default();
+
+        parse_ton_contract_workchains(&chain, &mut err);
+
+        assert!(!err.is_ok());
+    }
+
+    #[test]
+    fn test_parse_ton_fee_schedule_opcodes() {
+        let value = json!({
+            "feeSchedule": {
+                "opcodeGas": { "0x10": 100, "32": 300 }
+            }
+        });
+        let chain = ValueParser::new(Default::default(), &value);
+        let mut err = ConfigParsingError::default();
+
+        let fee_schedule = parse_ton_fee_schedule(&chain, &mut err);
+
+        assert!(err.is_ok());
+        assert_eq!(
+            fee_schedule.opcode_gas,
+            HashMap::from([(0x10, 100), (32, 300)])
+        );
+    }
+
+    #[test]
+    fn test_parse_ton_opcode() {
+        assert_eq!(parse_ton_opcode("0x1f"), Some(31));
+        assert_eq!(parse_ton_opcode("0X1F"), Some(31));
+        assert_eq!(parse_ton_opcode("31"), Some(31));
+        assert_eq!(parse_ton_opcode("1f"), None);
+        assert_eq!(parse_ton_opcode("0x"), None);
+    }
+
+    fn ton_rpcs() -> Vec<Url> {
+        vec![
+            Url::parse("https://toncenter.com/api/").unwrap(),
+            Url::parse("https://ton.backup.example/api/").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_parse_ton_endpoints_with_shared_api_key() {
+        let value = json!({});
+        let chain = ValueParser::new(Default::default(), &value);
+        let mut err = ConfigParsingError::default();
+
+        let endpoints = parse_ton_endpoints(&ton_rpcs(), "key", &chain, &mut err).unwrap();
+
+        assert!(err.is_ok());
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[1].url.as_str(), "https://ton.backup.example/api/");
+        assert!(endpoints.iter().all(|e| e.api_key == "key"));
+    }
+
+    #[test]
+    fn test_parse_ton_endpoints_with_api_key_per_url() {
+        let value = json!({ "api_keys": ["first", "second"] });
+        let chain = ValueParser::new(Default::default(), &value);
+        let mut err = ConfigParsingError::default();
+
+        let endpoints = parse_ton_endpoints(&ton_rpcs(), "key", &chain, &mut err).unwrap();
+
+        assert!(err.is_ok());
+        assert_eq!(endpoints[0].api_key, "first");
+        assert_eq!(endpoints[1].api_key, "second");
+    }
+
+    #[test]
+    fn test_parse_ton_endpoints_api_key_count_mismatch() {
+        let value = json!({ "api_keys": ["first"] });
+        let chain = ValueParser::new(Default::default(), &value);
+        let mut err = ConfigParsingError::default();
+
+        let endpoints = parse_ton_endpoints(&ton_rpcs(), "key", &chain, &mut err);
+
+        assert!(endpoints.is_none());
+        assert!(!err.is_ok());
     }
 }
@@ -0,0 +1,384 @@
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use eyre::{bail, eyre, Result};
+use hyperlane_core::{ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId};
+use prometheus::IntGauge;
+use reqwest::{header, Client, StatusCode, Url};
+use tracing::instrument;
+
+use crate::types::utils;
+use crate::{AgentMetadata, CheckpointSyncer};
+
+/// Env var holding a SAS token to authorize Azure Blob Storage requests with
+pub const AZURE_STORAGE_SAS_TOKEN: &str = "AZURE_STORAGE_SAS_TOKEN";
+
+/// Timeout for Azure Blob Storage requests
+const AZURE_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Blob service REST API version requests are made against
+const AZURE_STORAGE_API_VERSION: &str = "2021-08-06";
+
+/// Checks that `account` is a valid storage account name. Names are
+/// interpolated into the endpoint host, so anything else could point requests
+/// at another host.
+pub fn validate_azure_account(account: &str) -> Result<()> {
+    let valid = (3..=24).contains(&account.len())
+        && account
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit());
+    if !valid {
+        bail!("Invalid Azure storage account name `{account}`; expected 3 to 24 lowercase letters and digits");
+    }
+    Ok(())
+}
+
+/// Type for reading/writing to an Azure Blob Storage container
+#[derive(Clone)]
+pub struct AzureBlobStorage {
+    /// The storage account name.
+    account: String,
+    /// The name of the container.
+    container: String,
+    /// A specific folder inside the above container - defaults to the root of the container
+    folder: Option<String>,
+    /// SAS token appended to requests. Without one, only public containers can be read.
+    sas_token: Option<String>,
+    /// The blob service endpoint of the account.
+    endpoint: Url,
+    client: Client,
+    /// The latest seen signed checkpoint index.
+    latest_index: Option<IntGauge>,
+}
+
+impl fmt::Debug for AzureBlobStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AzureBlobStorage")
+            .field("account", &self.account)
+            .field("container", &self.container)
+            .field("folder", &self.folder)
+            .field("endpoint", &self.endpoint.as_str())
+            .finish()
+    }
+}
+
+impl AzureBlobStorage {
+    /// Creates a client for `container` in the public Azure cloud
+    pub fn new(
+        account: String,
+        container: String,
+        folder: Option<String>,
+        sas_token: Option<String>,
+        latest_index: Option<IntGauge>,
+    ) -> Result<Self> {
+        validate_azure_account(&account)?;
+        let endpoint = Url::parse(&format!("https://{account}.blob.core.windows.net"))?;
+        Ok(Self {
+            account,
+            container,
+            folder,
+            sas_token: sas_token.map(|token| token.trim_start_matches('?').to_owned()),
+            endpoint,
+            client: Client::builder().timeout(AZURE_REQUEST_TIMEOUT).build()?,
+            latest_index,
+        })
+    }
+
+    /// Sends requests to `endpoint` instead of the public Azure cloud, e.g. to
+    /// an emulator like Azurite.
+    pub fn with_endpoint(self, endpoint: Url) -> Self {
+        Self { endpoint, ..self }
+    }
+
+    fn blob_url(&self, key: &str) -> Result<Url> {
+        let mut url = self.endpoint.clone();
+        url.path_segments_mut()
+            .map_err(|_| eyre!("Invalid Azure Blob Storage endpoint {}", self.endpoint))?
+            .pop_if_empty()
+            .push(&self.container)
+            .extend(
+                self.folder
+                    .iter()
+                    .flat_map(|folder| folder.split('/'))
+                    .filter(|segment| !segment.is_empty()),
+            )
+            .push(key);
+        url.set_query(self.sas_token.as_deref());
+        Ok(url)
+    }
+
+    async fn write_blob(&self, key: &str, body: String) -> Result<()> {
+        let response = self
+            .client
+            .put(self.blob_url(key)?)
+            .header("x-ms-version", AZURE_STORAGE_API_VERSION)
+            .header("x-ms-blob-type", "BlockBlob")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            // Errors include the url, which holds the SAS token
+            .map_err(reqwest::Error::without_url)?;
+        if !response.status().is_success() {
+            bail!(
+                "Failed to write blob {key} to Azure container {}: {}",
+                self.container,
+                response.status()
+            );
+        }
+        Ok(())
+    }
+
+    async fn read_blob(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let response = self
+            .client
+            .get(self.blob_url(key)?)
+            .header("x-ms-version", AZURE_STORAGE_API_VERSION)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(
+                response
+                    .bytes()
+                    .await
+                    .map_err(reqwest::Error::without_url)?
+                    .to_vec(),
+            )),
+            status => bail!(
+                "Failed to read blob {key} from Azure container {}: {status}",
+                self.container
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckpointSyncer for AzureBlobStorage {
+    #[instrument(skip(self))]
+    async fn latest_index(&self) -> Result<Option<u32>> {
+        let ret = self
+            .read_blob(utils::LATEST_INDEX_KEY)
+            .await?
+            .map(|data| serde_json::from_slice(&data))
+            .transpose()
+            .map_err(Into::into);
+
+        if let Ok(Some(latest_index)) = ret {
+            if let Some(gauge) = &self.latest_index {
+                gauge.set(latest_index as i64);
+            }
+        }
+
+        ret
+    }
+
+    #[instrument(skip(self))]
+    async fn write_latest_index(&self, index: u32) -> Result<()> {
+        let serialized_index = serde_json::to_string(&index)?;
+        self.write_blob(utils::LATEST_INDEX_KEY, serialized_index)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>> {
+        self.read_blob(&utils::checkpoint_key(index))
+            .await?
+            .map(|data| serde_json::from_slice(&data))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    #[instrument(skip(self, signed_checkpoint))]
+    async fn write_checkpoint(
+        &self,
+        signed_checkpoint: &SignedCheckpointWithMessageId,
+    ) -> Result<()> {
+        let serialized_checkpoint = serde_json::to_string_pretty(signed_checkpoint)?;
+        self.write_blob(
+            &utils::checkpoint_key(signed_checkpoint.value.index),
+            serialized_checkpoint,
+        )
+        .await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn write_metadata(&self, metadata: &AgentMetadata) -> Result<()> {
+        let serialized_metadata = serde_json::to_string_pretty(metadata)?;
+        self.write_blob(utils::METADATA_KEY, serialized_metadata)
+            .await
+    }
+
+    #[instrument(skip(self, signed_announcement))]
+    async fn write_announcement(&self, signed_announcement: &SignedAnnouncement) -> Result<()> {
+        let serialized_announcement = serde_json::to_string_pretty(signed_announcement)?;
+        self.write_blob(utils::ANNOUNCEMENT_KEY, serialized_announcement)
+            .await
+    }
+
+    fn announcement_location(&self) -> String {
+        match self.folder.as_deref() {
+            None | Some("") => format!("azure://{}/{}", self.account, self.container),
+            Some(folder) => format!("azure://{}/{}/{}", self.account, self.container, folder),
+        }
+    }
+
+    #[instrument(skip(self, reorg_event))]
+    async fn write_reorg_status(&self, reorg_event: &ReorgEvent) -> Result<()> {
+        let serialized_reorg = serde_json::to_string(reorg_event)?;
+        self.write_blob(utils::REORG_FLAG_KEY, serialized_reorg)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn reorg_status(&self) -> Result<Option<ReorgEvent>> {
+        self.read_blob(utils::REORG_FLAG_KEY)
+            .await?
+            .map(|data| serde_json::from_slice(&data))
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        body::Bytes,
+        extract::{Path, RawQuery, State},
+        http::{HeaderMap, StatusCode},
+        routing, Router,
+    };
+    use hyperlane_core::{Checkpoint, CheckpointWithMessageId, Signature, H256};
+
+    use super::*;
+
+    const SAS_TOKEN: &str = "sv=2021-08-06&sig=secret";
+
+    type Blobs = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// Stand-in for the blob service that stores blobs in memory. Writes must
+    /// carry the SAS token and be block blobs.
+    fn setup_blob_service(blobs: Blobs) -> SocketAddr {
+        async fn get_blob(
+            State(blobs): State<Blobs>,
+            Path(path): Path<String>,
+        ) -> Result<Vec<u8>, StatusCode> {
+            blobs
+                .lock()
+                .unwrap()
+                .get(&path)
+                .cloned()
+                .ok_or(StatusCode::NOT_FOUND)
+        }
+
+        async fn put_blob(
+            State(blobs): State<Blobs>,
+            Path(path): Path<String>,
+            RawQuery(query): RawQuery,
+            headers: HeaderMap,
+            body: Bytes,
+        ) -> StatusCode {
+            if query.as_deref() != Some(SAS_TOKEN) {
+                return StatusCode::FORBIDDEN;
+            }
+            if headers.get("x-ms-blob-type").map(|v| v.as_bytes()) != Some(b"BlockBlob") {
+                return StatusCode::BAD_REQUEST;
+            }
+            blobs.lock().unwrap().insert(path, body.to_vec());
+            StatusCode::CREATED
+        }
+
+        let app = Router::new()
+            .route("/*path", routing::get(get_blob).put(put_blob))
+            .with_state(blobs);
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    fn storage(addr: SocketAddr, sas_token: Option<&str>) -> AzureBlobStorage {
+        AzureBlobStorage::new(
+            "account".to_owned(),
+            "container".to_owned(),
+            Some("validator/".to_owned()),
+            sas_token.map(str::to_owned),
+            None,
+        )
+        .unwrap()
+        .with_endpoint(Url::parse(&format!("http://{addr}/account")).unwrap())
+    }
+
+    fn signed_checkpoint(index: u32) -> SignedCheckpointWithMessageId {
+        SignedCheckpointWithMessageId {
+            value: CheckpointWithMessageId {
+                checkpoint: Checkpoint {
+                    merkle_tree_hook_address: H256::random(),
+                    mailbox_domain: 1,
+                    root: H256::random(),
+                    index,
+                },
+                message_id: H256::random(),
+            },
+            signature: Signature {
+                r: 1.into(),
+                s: 2.into(),
+                v: 27,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writes_and_reads_blobs() {
+        let blobs = Blobs::default();
+        let addr = setup_blob_service(blobs.clone());
+        let storage = storage(addr, Some(&format!("?{SAS_TOKEN}")));
+
+        assert_eq!(storage.latest_index().await.unwrap(), None);
+        assert_eq!(storage.fetch_checkpoint(5).await.unwrap(), None);
+
+        let checkpoint = signed_checkpoint(5);
+        storage.write_checkpoint(&checkpoint).await.unwrap();
+        storage.update_latest_index(5).await.unwrap();
+
+        assert_eq!(storage.latest_index().await.unwrap(), Some(5));
+        assert_eq!(storage.fetch_checkpoint(5).await.unwrap(), Some(checkpoint));
+        assert!(blobs
+            .lock()
+            .unwrap()
+            .keys()
+            .any(|path| path.ends_with("account/container/validator/checkpoint_5_with_id.json")));
+    }
+
+    #[tokio::test]
+    async fn test_writes_fail_without_sas_token() {
+        let addr = setup_blob_service(Blobs::default());
+        let storage = storage(addr, None);
+
+        assert!(storage.write_latest_index(1).await.is_err());
+    }
+
+    #[test]
+    fn test_announcement_location() {
+        let storage = AzureBlobStorage::new(
+            "account".to_owned(),
+            "container".to_owned(),
+            Some("validator".to_owned()),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            storage.announcement_location(),
+            "azure://account/container/validator"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use eyre::{bail, eyre, Result};
+use hyperlane_core::{ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId};
+use prometheus::IntGauge;
+use reqwest::{Client, StatusCode, Url};
+use serde::de::DeserializeOwned;
+
+use crate::types::utils;
+use crate::{AgentMetadata, CheckpointSyncer};
+
+/// Timeout for HTTP storage requests
+const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Read-only access to checkpoints published over HTTP(S), e.g. by a static
+/// file host or an IPFS gateway. Objects are expected under `url` with the
+/// same keys as in S3 buckets.
+#[derive(Clone)]
+pub struct HttpStorage {
+    /// Base url the objects are found under
+    url: Url,
+    client: Client,
+    /// The latest seen signed checkpoint index.
+    latest_index: Option<IntGauge>,
+}
+
+impl fmt::Debug for HttpStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpStorage")
+            .field("url", &self.url.as_str())
+            .finish()
+    }
+}
+
+impl HttpStorage {
+    /// Creates a client reading the objects under `url`
+    pub fn new(url: Url, latest_index: Option<IntGauge>) -> Result<Self> {
+        if url.cannot_be_a_base() {
+            bail!("Invalid HTTP storage location {url}");
+        }
+        Ok(Self {
+            url,
+            client: Client::builder().timeout(HTTP_REQUEST_TIMEOUT).build()?,
+            latest_index,
+        })
+    }
+
+    fn object_url(&self, key: &str) -> Result<Url> {
+        let mut url = self.url.clone();
+        url.path_segments_mut()
+            .map_err(|_| eyre!("Invalid HTTP storage location {}", self.url))?
+            .pop_if_empty()
+            .push(key);
+        Ok(url)
+    }
+
+    async fn read_object<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let response = self.client.get(self.object_url(key)?).send().await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => {
+                Ok(Some(serde_json::from_slice(&response.bytes().await?)?))
+            }
+            status => bail!("Failed to read {key} from {}: {status}", self.url),
+        }
+    }
+
+    fn read_only_error(&self) -> eyre::Report {
+        eyre!("HTTP storage at {} is read-only", self.url)
+    }
+}
+
+#[async_trait]
+impl CheckpointSyncer for HttpStorage {
+    async fn latest_index(&self) -> Result<Option<u32>> {
+        let latest_index = self.read_object(utils::LATEST_INDEX_KEY).await?;
+        if let (Some(latest_index), Some(gauge)) = (latest_index, &self.latest_index) {
+            gauge.set(latest_index as i64);
+        }
+        Ok(latest_index)
+    }
+
+    async fn write_latest_index(&self, _index: u32) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>> {
+        self.read_object(&utils::checkpoint_key(index)).await
+    }
+
+    async fn write_checkpoint(
+        &self,
+        _signed_checkpoint: &SignedCheckpointWithMessageId,
+    ) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    async fn write_metadata(&self, _metadata: &AgentMetadata) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    async fn write_announcement(&self, _signed_announcement: &SignedAnnouncement) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    fn announcement_location(&self) -> String {
+        self.url.to_string()
+    }
+
+    async fn write_reorg_status(&self, _reorg_event: &ReorgEvent) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    async fn reorg_status(&self) -> Result<Option<ReorgEvent>> {
+        self.read_object(utils::REORG_FLAG_KEY).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use axum::{http::StatusCode, routing, Json, Router};
+    use hyperlane_core::{Checkpoint, CheckpointWithMessageId, Signature, H256};
+
+    use super::*;
+
+    /// Stand-in for a static file host serving a validator's checkpoints under
+    /// `/ipfs/validator`
+    fn setup_file_host(checkpoint: SignedCheckpointWithMessageId) -> SocketAddr {
+        let app = Router::new()
+            .route(
+                "/ipfs/validator/checkpoint_latest_index.json",
+                routing::get(|| async { Json(3) }),
+            )
+            .route(
+                "/ipfs/validator/checkpoint_3_with_id.json",
+                routing::get(move || async move { Json(checkpoint) }),
+            )
+            .route(
+                "/ipfs/validator/reorg_flag.json",
+                routing::get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+            );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_reads_published_checkpoints() {
+        let checkpoint = SignedCheckpointWithMessageId {
+            value: CheckpointWithMessageId {
+                checkpoint: Checkpoint {
+                    merkle_tree_hook_address: H256::random(),
+                    mailbox_domain: 1,
+                    root: H256::random(),
+                    index: 3,
+                },
+                message_id: H256::random(),
+            },
+            signature: Signature {
+                r: 1.into(),
+                s: 2.into(),
+                v: 27,
+            },
+        };
+        let addr = setup_file_host(checkpoint.clone());
+        let url = Url::parse(&format!("http://{addr}/ipfs/validator/")).unwrap();
+        let storage = HttpStorage::new(url, None).unwrap();
+
+        assert_eq!(storage.latest_index().await.unwrap(), Some(3));
+        assert_eq!(storage.fetch_checkpoint(3).await.unwrap(), Some(checkpoint));
+        assert_eq!(storage.fetch_checkpoint(4).await.unwrap(), None);
+        assert!(storage.reorg_status().await.is_err());
+        assert!(storage.write_latest_index(4).await.is_err());
+        assert_eq!(
+            storage.announcement_location(),
+            format!("http://{addr}/ipfs/validator/")
+        );
+    }
+}
@@ -0,0 +1,574 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use eyre::{bail, Result};
+use futures_util::future::{join_all, BoxFuture};
+use hyperlane_core::{ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId};
+use tracing::{debug, info, instrument, warn};
+
+use crate::{AgentMetadata, CheckpointSyncer};
+
+/// Maximum number of checkpoints copied to a single lagging mirror per
+/// reconciliation pass
+const MAX_RECONCILE_BATCH_SIZE: u32 = 1000;
+
+/// Writes to several checkpoint syncers at once, succeeding as long as a
+/// quorum of them accepted the write.
+///
+/// A mirror only has its latest index advanced past checkpoints it holds, so
+/// a mirror that missed writes appears to lag behind the others and can be
+/// backfilled with [`MirroredCheckpointSyncer::reconcile`].
+///
+/// Every mirror is announced, but relayers only read from the most recently
+/// announced location they can build a syncer for, and do not fail over to
+/// another one when reads from it fail. The primary is announced last so that
+/// relayers prefer it; mirrors announced after the primary, e.g. when added to
+/// an existing validator, are preferred over it instead.
+#[derive(Debug)]
+pub struct MirroredCheckpointSyncer {
+    syncers: Vec<Arc<dyn CheckpointSyncer>>,
+    quorum: usize,
+    /// Per mirror, the indices of checkpoints that failed to be written to it
+    /// and have not been written since.
+    missing: Vec<Mutex<BTreeSet<u32>>>,
+}
+
+impl MirroredCheckpointSyncer {
+    /// Mirrors writes to `syncers`, requiring `quorum` of them to succeed.
+    /// The first syncer is the primary one reads are served from first.
+    pub fn new(syncers: Vec<Arc<dyn CheckpointSyncer>>, quorum: usize) -> Result<Self> {
+        if syncers.is_empty() {
+            bail!("At least one checkpoint syncer is required");
+        }
+        if quorum == 0 || quorum > syncers.len() {
+            bail!(
+                "Checkpoint syncer quorum must be between 1 and {}, got {quorum}",
+                syncers.len()
+            );
+        }
+        let missing = syncers.iter().map(|_| Default::default()).collect();
+        Ok(Self {
+            syncers,
+            quorum,
+            missing,
+        })
+    }
+
+    /// The mirrored syncers
+    pub fn syncers(&self) -> &[Arc<dyn CheckpointSyncer>] {
+        &self.syncers
+    }
+
+    /// The number of syncers a write must succeed on
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+
+    /// The announcement locations of all mirrors
+    pub fn announcement_locations(&self) -> Vec<String> {
+        self.syncers
+            .iter()
+            .map(|syncer| syncer.announcement_location())
+            .collect()
+    }
+
+    /// Runs `write` against every mirror concurrently and returns the
+    /// per-mirror results, logging failures.
+    async fn write_all<'a, F>(&'a self, operation: &str, write: F) -> Vec<Result<()>>
+    where
+        F: Fn(usize, &'a dyn CheckpointSyncer) -> BoxFuture<'a, Result<()>> + Send,
+    {
+        let results = join_all(
+            self.syncers
+                .iter()
+                .enumerate()
+                .map(|(i, syncer)| write(i, syncer.as_ref())),
+        )
+        .await;
+        for (syncer, result) in self.syncers.iter().zip(&results) {
+            if let Err(err) = result {
+                warn!(
+                    location = %syncer.announcement_location(),
+                    ?err,
+                    operation,
+                    "Failed to write to checkpoint syncer mirror"
+                );
+            }
+        }
+        results
+    }
+
+    fn check_quorum(&self, operation: &str, results: &[Result<()>]) -> Result<()> {
+        let successes = results.iter().filter(|result| result.is_ok()).count();
+        if successes < self.quorum {
+            bail!(
+                "Failed to {operation}: only {successes} of {} checkpoint syncers succeeded, quorum is {}",
+                self.syncers.len(),
+                self.quorum
+            );
+        }
+        Ok(())
+    }
+
+    /// The highest index mirror `i` can have its latest index set to, given
+    /// the checkpoints known to be missing from it.
+    fn highest_contiguous_index(&self, i: usize, index: u32) -> Option<u32> {
+        match self.missing[i].lock().unwrap().first() {
+            Some(&first_missing) if first_missing <= index => first_missing.checked_sub(1),
+            _ => Some(index),
+        }
+    }
+
+    /// Reads from every mirror concurrently, returning the successful reads.
+    /// Fails only if no mirror could be read from.
+    async fn read_all<'a, T, F>(&'a self, read: F) -> Result<Vec<T>>
+    where
+        F: Fn(&'a dyn CheckpointSyncer) -> BoxFuture<'a, Result<T>> + Send,
+        T: Send,
+    {
+        let results = join_all(self.syncers.iter().map(|syncer| read(syncer.as_ref()))).await;
+        let mut values = Vec::with_capacity(results.len());
+        let mut last_err = None;
+        for result in results {
+            match result {
+                Ok(value) => values.push(value),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        match last_err {
+            Some(err) if values.is_empty() => Err(err),
+            _ => Ok(values),
+        }
+    }
+
+    /// Writes or updates the latest index of every mirror, capped below the
+    /// first checkpoint known to be missing from it. A capped mirror counts as
+    /// a failed write.
+    async fn set_latest_index(&self, operation: &str, index: u32, update: bool) -> Result<()> {
+        let results = self
+            .write_all(operation, |i, syncer| {
+                let contiguous = self.highest_contiguous_index(i, index);
+                Box::pin(async move {
+                    let Some(contiguous) = contiguous else {
+                        bail!("Checkpoint syncer is missing checkpoint 0");
+                    };
+                    if update {
+                        syncer.update_latest_index(contiguous).await?;
+                    } else {
+                        syncer.write_latest_index(contiguous).await?;
+                    }
+                    if contiguous < index {
+                        bail!("Checkpoint syncer is missing checkpoint {}", contiguous + 1);
+                    }
+                    Ok(())
+                })
+            })
+            .await;
+        self.check_quorum(operation, &results)
+    }
+
+    /// Backfills mirrors that lag behind the most advanced one by copying
+    /// checkpoints from the mirrors that have them, then advancing their
+    /// latest index.
+    #[instrument(skip(self))]
+    pub async fn reconcile(&self) -> Result<()> {
+        if self.syncers.len() < 2 {
+            return Ok(());
+        }
+        let latest_indices = join_all(self.syncers.iter().map(|syncer| syncer.latest_index()))
+            .await
+            .into_iter()
+            .map(|result| result.ok())
+            .collect::<Vec<_>>();
+        let Some(target) = latest_indices.iter().flatten().flatten().copied().max() else {
+            return Ok(());
+        };
+
+        for (i, syncer) in self.syncers.iter().enumerate() {
+            let Some(latest_index) = latest_indices[i] else {
+                // Unreachable mirrors are retried on the next pass
+                continue;
+            };
+            let missing = self.missing[i].lock().unwrap().clone();
+            let start = latest_index.map_or(0, |index| index + 1);
+            if start > target && missing.is_empty() {
+                continue;
+            }
+            let location = syncer.announcement_location();
+            info!(
+                %location,
+                ?latest_index,
+                target,
+                missing = missing.len(),
+                "Reconciling lagging checkpoint syncer mirror"
+            );
+
+            // Retry checkpoints that failed to be written first, they may be
+            // above the mirror's latest index
+            for index in missing.into_iter().take(MAX_RECONCILE_BATCH_SIZE as usize) {
+                match self.copy_checkpoint(i, index).await {
+                    Ok(true) => {
+                        self.missing[i].lock().unwrap().remove(&index);
+                    }
+                    Ok(false) => {}
+                    Err(err) => warn!(
+                        %location,
+                        index,
+                        ?err,
+                        "Failed to backfill checkpoint mirror"
+                    ),
+                }
+            }
+
+            let end = target.min(start.saturating_add(MAX_RECONCILE_BATCH_SIZE - 1));
+            let mut reconciled_to = latest_index;
+            for index in start..=end {
+                match self.copy_checkpoint(i, index).await {
+                    Ok(true) => reconciled_to = Some(index),
+                    Ok(false) => break,
+                    Err(err) => {
+                        warn!(
+                            %location,
+                            index,
+                            ?err,
+                            "Failed to backfill checkpoint mirror"
+                        );
+                        break;
+                    }
+                }
+            }
+            let Some(reconciled_to) = reconciled_to
+                .and_then(|index| self.highest_contiguous_index(i, index))
+                .filter(|&index| Some(index) > latest_index)
+            else {
+                continue;
+            };
+            match syncer.write_latest_index(reconciled_to).await {
+                Ok(()) => debug!(%location, reconciled_to, "Backfilled checkpoint mirror"),
+                Err(err) => warn!(%location, ?err, "Failed to update checkpoint mirror index"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes sure mirror `i` holds the checkpoint at `index`, copying it from
+    /// another mirror if needed. Returns whether the mirror holds it afterwards.
+    async fn copy_checkpoint(&self, i: usize, index: u32) -> Result<bool> {
+        let syncer = &self.syncers[i];
+        if syncer.fetch_checkpoint(index).await?.is_some() {
+            return Ok(true);
+        }
+        for (j, source) in self.syncers.iter().enumerate() {
+            if j == i {
+                continue;
+            }
+            if let Ok(Some(checkpoint)) = source.fetch_checkpoint(index).await {
+                syncer.write_checkpoint(&checkpoint).await?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[async_trait]
+impl CheckpointSyncer for MirroredCheckpointSyncer {
+    async fn latest_index(&self) -> Result<Option<u32>> {
+        let indices = self.read_all(|syncer| syncer.latest_index()).await?;
+        Ok(indices.into_iter().flatten().max())
+    }
+
+    async fn write_latest_index(&self, index: u32) -> Result<()> {
+        self.set_latest_index("write latest index", index, false)
+            .await
+    }
+
+    async fn update_latest_index(&self, index: u32) -> Result<()> {
+        self.set_latest_index("update latest index", index, true)
+            .await
+    }
+
+    /// Only returns a checkpoint held by a quorum of mirrors, so that a
+    /// checkpoint written to too few of them gets written again.
+    async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>> {
+        let checkpoints = self
+            .read_all(|syncer| syncer.fetch_checkpoint(index))
+            .await?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        if checkpoints.len() < self.quorum {
+            return Ok(None);
+        }
+        Ok(checkpoints.into_iter().next())
+    }
+
+    async fn write_checkpoint(
+        &self,
+        signed_checkpoint: &SignedCheckpointWithMessageId,
+    ) -> Result<()> {
+        let index = signed_checkpoint.value.index;
+        let results = self
+            .write_all("write checkpoint", |_, syncer| {
+                syncer.write_checkpoint(signed_checkpoint)
+            })
+            .await;
+        for (missing, result) in self.missing.iter().zip(&results) {
+            let mut missing = missing.lock().unwrap();
+            if result.is_ok() {
+                missing.remove(&index);
+            } else {
+                missing.insert(index);
+            }
+        }
+        self.check_quorum("write checkpoint", &results)
+    }
+
+    async fn write_metadata(&self, metadata: &AgentMetadata) -> Result<()> {
+        let results = self
+            .write_all("write metadata", |_, syncer| {
+                syncer.write_metadata(metadata)
+            })
+            .await;
+        self.check_quorum("write metadata", &results)
+    }
+
+    async fn write_announcement(&self, signed_announcement: &SignedAnnouncement) -> Result<()> {
+        let results = self
+            .write_all("write announcement", |_, syncer| {
+                syncer.write_announcement(signed_announcement)
+            })
+            .await;
+        self.check_quorum("write announcement", &results)
+    }
+
+    /// The location of the primary mirror. See
+    /// [`MirroredCheckpointSyncer::announcement_locations`] for all of them.
+    fn announcement_location(&self) -> String {
+        self.syncers[0].announcement_location()
+    }
+
+    async fn write_reorg_status(&self, reorg_event: &ReorgEvent) -> Result<()> {
+        let results = self
+            .write_all("write reorg status", |_, syncer| {
+                syncer.write_reorg_status(reorg_event)
+            })
+            .await;
+        self.check_quorum("write reorg status", &results)
+    }
+
+    /// Reports a reorg if any reachable mirror flagged one
+    async fn reorg_status(&self) -> Result<Option<ReorgEvent>> {
+        let statuses = self.read_all(|syncer| syncer.reorg_status()).await?;
+        Ok(statuses.into_iter().flatten().next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use hyperlane_core::{Checkpoint, CheckpointWithMessageId, Signature, H256};
+
+    use super::*;
+    use crate::LocalStorage;
+
+    /// Local storage that fails every request while `down` is set
+    #[derive(Debug)]
+    struct FlakyStorage {
+        inner: LocalStorage,
+        down: AtomicBool,
+    }
+
+    impl FlakyStorage {
+        fn new(dir: &tempfile::TempDir) -> Arc<Self> {
+            Arc::new(Self {
+                inner: LocalStorage::new(dir.path().to_owned(), None).unwrap(),
+                down: AtomicBool::new(false),
+            })
+        }
+
+        fn set_down(&self, down: bool) {
+            self.down.store(down, Ordering::SeqCst);
+        }
+
+        fn check(&self) -> Result<()> {
+            if self.down.load(Ordering::SeqCst) {
+                bail!("storage is down");
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl CheckpointSyncer for FlakyStorage {
+        async fn latest_index(&self) -> Result<Option<u32>> {
+            self.check()?;
+            self.inner.latest_index().await
+        }
+        async fn write_latest_index(&self, index: u32) -> Result<()> {
+            self.check()?;
+            self.inner.write_latest_index(index).await
+        }
+        async fn fetch_checkpoint(
+            &self,
+            index: u32,
+        ) -> Result<Option<SignedCheckpointWithMessageId>> {
+            self.check()?;
+            self.inner.fetch_checkpoint(index).await
+        }
+        async fn write_checkpoint(
+            &self,
+            signed_checkpoint: &SignedCheckpointWithMessageId,
+        ) -> Result<()> {
+            self.check()?;
+            self.inner.write_checkpoint(signed_checkpoint).await
+        }
+        async fn write_metadata(&self, metadata: &AgentMetadata) -> Result<()> {
+            self.check()?;
+            self.inner.write_metadata(metadata).await
+        }
+        async fn write_announcement(&self, signed_announcement: &SignedAnnouncement) -> Result<()> {
+            self.check()?;
+            self.inner.write_announcement(signed_announcement).await
+        }
+        fn announcement_location(&self) -> String {
+            self.inner.announcement_location()
+        }
+        async fn write_reorg_status(&self, reorg_event: &ReorgEvent) -> Result<()> {
+            self.check()?;
+            self.inner.write_reorg_status(reorg_event).await
+        }
+        async fn reorg_status(&self) -> Result<Option<ReorgEvent>> {
+            self.check()?;
+            self.inner.reorg_status().await
+        }
+    }
+
+    fn signed_checkpoint(index: u32) -> SignedCheckpointWithMessageId {
+        SignedCheckpointWithMessageId {
+            value: CheckpointWithMessageId {
+                checkpoint: Checkpoint {
+                    merkle_tree_hook_address: H256::zero(),
+                    mailbox_domain: 1,
+                    root: H256::zero(),
+                    index,
+                },
+                message_id: H256::zero(),
+            },
+            signature: Signature {
+                r: 1.into(),
+                s: 2.into(),
+                v: 27,
+            },
+        }
+    }
+
+    fn setup(
+        quorum: usize,
+    ) -> (
+        Vec<tempfile::TempDir>,
+        Vec<Arc<FlakyStorage>>,
+        MirroredCheckpointSyncer,
+    ) {
+        let dirs = (0..3)
+            .map(|_| tempfile::tempdir().unwrap())
+            .collect::<Vec<_>>();
+        let mirrors = dirs.iter().map(FlakyStorage::new).collect::<Vec<_>>();
+        let syncer = MirroredCheckpointSyncer::new(
+            mirrors
+                .iter()
+                .map(|mirror| mirror.clone() as Arc<dyn CheckpointSyncer>)
+                .collect(),
+            quorum,
+        )
+        .unwrap();
+        (dirs, mirrors, syncer)
+    }
+
+    #[test]
+    fn test_rejects_invalid_quorum() {
+        assert!(MirroredCheckpointSyncer::new(vec![], 1).is_err());
+        let (_dirs, _mirrors, syncer) = setup(1);
+        assert!(MirroredCheckpointSyncer::new(syncer.syncers().to_vec(), 0).is_err());
+        assert!(MirroredCheckpointSyncer::new(syncer.syncers().to_vec(), 4).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_writes_succeed_with_quorum() {
+        let (_dirs, mirrors, syncer) = setup(2);
+
+        mirrors[2].set_down(true);
+        syncer
+            .write_checkpoint(&signed_checkpoint(0))
+            .await
+            .unwrap();
+        syncer.update_latest_index(0).await.unwrap();
+        assert_eq!(syncer.latest_index().await.unwrap(), Some(0));
+        assert!(syncer.fetch_checkpoint(0).await.unwrap().is_some());
+
+        mirrors[1].set_down(true);
+        assert!(syncer
+            .write_checkpoint(&signed_checkpoint(1))
+            .await
+            .is_err());
+        // Only written to a single mirror, so it must be written again
+        assert_eq!(syncer.fetch_checkpoint(1).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_backfills_lagging_mirror() {
+        let (_dirs, mirrors, syncer) = setup(2);
+
+        mirrors[2].set_down(true);
+        for index in 0..5 {
+            syncer
+                .write_checkpoint(&signed_checkpoint(index))
+                .await
+                .unwrap();
+        }
+        syncer.update_latest_index(4).await.unwrap();
+        mirrors[2].set_down(false);
+
+        // The mirror that was down has no checkpoints, so its index was never
+        // advanced
+        assert_eq!(mirrors[2].latest_index().await.unwrap(), None);
+
+        syncer.reconcile().await.unwrap();
+        assert_eq!(mirrors[2].latest_index().await.unwrap(), Some(4));
+        for index in 0..5 {
+            assert!(mirrors[2].fetch_checkpoint(index).await.unwrap().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missed_checkpoint_caps_mirror_index() {
+        let (_dirs, mirrors, syncer) = setup(2);
+
+        syncer
+            .write_checkpoint(&signed_checkpoint(0))
+            .await
+            .unwrap();
+        mirrors[2].set_down(true);
+        syncer
+            .write_checkpoint(&signed_checkpoint(1))
+            .await
+            .unwrap();
+        mirrors[2].set_down(false);
+        syncer
+            .write_checkpoint(&signed_checkpoint(2))
+            .await
+            .unwrap();
+        syncer.update_latest_index(2).await.unwrap();
+
+        assert_eq!(mirrors[0].latest_index().await.unwrap(), Some(2));
+        assert_ne!(mirrors[2].latest_index().await.unwrap(), Some(2));
+
+        syncer.reconcile().await.unwrap();
+        assert_eq!(mirrors[2].latest_index().await.unwrap(), Some(2));
+        assert!(mirrors[2].fetch_checkpoint(1).await.unwrap().is_some());
+    }
+}
@@ -1,12 +1,18 @@
+mod azure_storage;
 mod gcs_storage;
+mod http_storage;
 mod local_storage;
+mod mirrored_storage;
 mod multisig;
 mod s3_storage;
 
 /// Reusable logic for working with storage backends.
 pub mod utils;
 
+pub use azure_storage::*;
 pub use gcs_storage::*;
+pub use http_storage::*;
 pub use local_storage::*;
+pub use mirrored_storage::*;
 pub use multisig::*;
 pub use s3_storage::*;
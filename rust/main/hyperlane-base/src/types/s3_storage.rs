@@ -129,33 +129,13 @@ impl S3Storage {
             Some(folder_str) => format!("{}/{}", folder_str, key),
         }
     }
-
-    fn checkpoint_key(index: u32) -> String {
-        format!("checkpoint_{index}_with_id.json")
-    }
-
-    fn latest_index_key() -> String {
-        "checkpoint_latest_index.json".to_owned()
-    }
-
-    fn metadata_key() -> String {
-        "metadata_latest.json".to_owned()
-    }
-
-    fn announcement_key() -> String {
-        "announcement.json".to_owned()
-    }
-
-    fn reorg_flag_key() -> String {
-        "reorg_flag.json".to_owned()
-    }
 }
 
 #[async_trait]
 impl CheckpointSyncer for S3Storage {
     async fn latest_index(&self) -> Result<Option<u32>> {
         let ret = self
-            .anonymously_read_from_bucket(S3Storage::latest_index_key())
+            .anonymously_read_from_bucket(utils::LATEST_INDEX_KEY.to_owned())
             .await?
             .map(|data| serde_json::from_slice(&data))
             .transpose()
@@ -172,13 +152,13 @@ impl CheckpointSyncer for S3Storage {
 
     async fn write_latest_index(&self, index: u32) -> Result<()> {
         let serialized_index = serde_json::to_string(&index)?;
-        self.write_to_bucket(S3Storage::latest_index_key(), &serialized_index)
+        self.write_to_bucket(utils::LATEST_INDEX_KEY.to_owned(), &serialized_index)
             .await?;
         Ok(())
     }
 
     async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>> {
-        self.anonymously_read_from_bucket(S3Storage::checkpoint_key(index))
+        self.anonymously_read_from_bucket(utils::checkpoint_key(index))
             .await?
             .map(|data| serde_json::from_slice(&data))
             .transpose()
@@ -191,7 +171,7 @@ impl CheckpointSyncer for S3Storage {
     ) -> Result<()> {
         let serialized_checkpoint = serde_json::to_string_pretty(signed_checkpoint)?;
         self.write_to_bucket(
-            S3Storage::checkpoint_key(signed_checkpoint.value.index),
+            utils::checkpoint_key(signed_checkpoint.value.index),
             &serialized_checkpoint,
         )
         .await?;
@@ -200,14 +180,14 @@ impl CheckpointSyncer for S3Storage {
 
     async fn write_metadata(&self, metadata: &AgentMetadata) -> Result<()> {
         let serialized_metadata = serde_json::to_string_pretty(metadata)?;
-        self.write_to_bucket(S3Storage::metadata_key(), &serialized_metadata)
+        self.write_to_bucket(utils::METADATA_KEY.to_owned(), &serialized_metadata)
             .await?;
         Ok(())
     }
 
     async fn write_announcement(&self, signed_announcement: &SignedAnnouncement) -> Result<()> {
         let serialized_announcement = serde_json::to_string_pretty(signed_announcement)?;
-        self.write_to_bucket(S3Storage::announcement_key(), &serialized_announcement)
+        self.write_to_bucket(utils::ANNOUNCEMENT_KEY.to_owned(), &serialized_announcement)
             .await?;
         Ok(())
     }
@@ -223,13 +203,13 @@ impl CheckpointSyncer for S3Storage {
 
     async fn write_reorg_status(&self, reorged_event: &ReorgEvent) -> Result<()> {
         let serialized_reorg = serde_json::to_string(reorged_event)?;
-        self.write_to_bucket(S3Storage::reorg_flag_key(), &serialized_reorg)
+        self.write_to_bucket(utils::REORG_FLAG_KEY.to_owned(), &serialized_reorg)
             .await?;
         Ok(())
     }
 
     async fn reorg_status(&self) -> Result<Option<ReorgEvent>> {
-        self.anonymously_read_from_bucket(S3Storage::reorg_flag_key())
+        self.anonymously_read_from_bucket(utils::REORG_FLAG_KEY.to_owned())
             .await?
             .map(|data| serde_json::from_slice(&data))
             .transpose()
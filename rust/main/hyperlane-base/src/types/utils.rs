@@ -13,3 +13,17 @@ pub fn http_client_with_timeout() -> Result<HttpClient> {
     config.pool_idle_timeout(HYPER_POOL_IDLE_TIMEOUT);
     Ok(HttpClient::new_with_config(config)?)
 }
+
+/// Object key of the latest signed checkpoint index in bucket based storage
+pub const LATEST_INDEX_KEY: &str = "checkpoint_latest_index.json";
+/// Object key of the agent metadata in bucket based storage
+pub const METADATA_KEY: &str = "metadata_latest.json";
+/// Object key of the signed announcement in bucket based storage
+pub const ANNOUNCEMENT_KEY: &str = "announcement.json";
+/// Object key of the reorg flag in bucket based storage
+pub const REORG_FLAG_KEY: &str = "reorg_flag.json";
+
+/// Object key of the signed checkpoint at `index` in bucket based storage
+pub fn checkpoint_key(index: u32) -> String {
+    format!("checkpoint_{index}_with_id.json")
+}
@@ -5,6 +5,10 @@ use std::path::PathBuf;
 use crate::accumulator::merkle::Proof;
 use crate::{HyperlaneDomain, H256};
 
+pub use mock_pending_operation::MockPendingOperation;
+
+mod mock_pending_operation;
+
 /// Struct representing a single merkle test case
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,307 @@
+use std::{
+    collections::VecDeque,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use prometheus::IntGauge;
+use serde::Serialize;
+
+use crate::{
+    HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneDomainTechnicalStack, HyperlaneDomainType,
+    HyperlaneMessage, PendingOperation, PendingOperationResult, PendingOperationStatus, TryBatchAs,
+    TxOutcome, H256, U256,
+};
+
+/// In-memory `PendingOperation` for exercising queues and submitters in tests.
+///
+/// The results of `prepare`, `submit` and `confirm` can be scripted and are
+/// returned in order; once a script runs out, the step succeeds.
+#[derive(Debug, Clone, Serialize)]
+pub struct MockPendingOperation {
+    id: H256,
+    sender_address: H256,
+    origin_domain_id: u32,
+    destination_domain_id: u32,
+    recipient_address: H256,
+    seconds_to_next_attempt: u64,
+    destination_domain: HyperlaneDomain,
+    #[serde(skip)]
+    body: Vec<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    app_context: Option<String>,
+    #[serde(skip)]
+    status: PendingOperationStatus,
+    #[serde(skip)]
+    metric: Option<Arc<IntGauge>>,
+    #[serde(skip)]
+    priority: u32,
+    #[serde(skip)]
+    retries: u32,
+    #[serde(skip)]
+    prepare_results: VecDeque<PendingOperationResult>,
+    #[serde(skip)]
+    submit_results: VecDeque<PendingOperationResult>,
+    #[serde(skip)]
+    confirm_results: VecDeque<PendingOperationResult>,
+    #[serde(skip)]
+    submission_outcome: Option<TxOutcome>,
+    #[serde(skip)]
+    tx_cost_estimate: Option<U256>,
+}
+
+impl MockPendingOperation {
+    /// Creates an operation with random addresses, due in `seconds_to_next_attempt`
+    pub fn new(seconds_to_next_attempt: u64, destination_domain: HyperlaneDomain) -> Self {
+        Self {
+            id: H256::random(),
+            sender_address: H256::random(),
+            origin_domain_id: 0,
+            destination_domain_id: destination_domain.id(),
+            recipient_address: H256::random(),
+            seconds_to_next_attempt,
+            destination_domain,
+            body: vec![],
+            app_context: None,
+            status: PendingOperationStatus::FirstPrepareAttempt,
+            metric: None,
+            priority: 0,
+            retries: 0,
+            prepare_results: VecDeque::new(),
+            submit_results: VecDeque::new(),
+            confirm_results: VecDeque::new(),
+            submission_outcome: None,
+            tx_cost_estimate: None,
+        }
+    }
+
+    /// Creates an operation carrying the fields of `message`, due immediately
+    pub fn with_message_data(message: HyperlaneMessage) -> Self {
+        let destination_domain = HyperlaneDomain::Unknown {
+            domain_id: message.destination,
+            domain_name: "test".to_string(),
+            domain_type: HyperlaneDomainType::Unknown,
+            domain_protocol: HyperlaneDomainProtocol::Ethereum,
+            domain_technical_stack: HyperlaneDomainTechnicalStack::Other,
+        };
+        Self {
+            id: message.id(),
+            sender_address: message.sender,
+            recipient_address: message.recipient,
+            origin_domain_id: message.origin,
+            body: message.body,
+            ..Self::new(0, destination_domain)
+        }
+    }
+
+    /// Sets the operation id from its hex representation
+    pub fn with_id(self, id: &str) -> Self {
+        Self {
+            id: H256::from_str(id).unwrap(),
+            ..self
+        }
+    }
+
+    /// Sets the sender address from its hex representation
+    pub fn with_sender_address(self, sender_address: &str) -> Self {
+        Self {
+            sender_address: H256::from_str(sender_address).unwrap(),
+            ..self
+        }
+    }
+
+    /// Sets the recipient address from its hex representation
+    pub fn with_recipient_address(self, recipient_address: &str) -> Self {
+        Self {
+            recipient_address: H256::from_str(recipient_address).unwrap(),
+            ..self
+        }
+    }
+
+    /// Sets the origin domain
+    pub fn with_origin_domain(self, domain: HyperlaneDomain) -> Self {
+        Self {
+            origin_domain_id: domain.id(),
+            ..self
+        }
+    }
+
+    /// Sets the app context
+    pub fn with_app_context(self, app_context: &str) -> Self {
+        Self {
+            app_context: Some(app_context.to_owned()),
+            ..self
+        }
+    }
+
+    /// Sets the priority used to order operations from the same origin
+    pub fn with_priority(self, priority: u32) -> Self {
+        Self { priority, ..self }
+    }
+
+    /// Scripts the results of successive `prepare` calls
+    pub fn with_prepare_results(
+        self,
+        results: impl IntoIterator<Item = PendingOperationResult>,
+    ) -> Self {
+        Self {
+            prepare_results: results.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Scripts the results of successive `submit` calls
+    pub fn with_submit_results(
+        self,
+        results: impl IntoIterator<Item = PendingOperationResult>,
+    ) -> Self {
+        Self {
+            submit_results: results.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Scripts the results of successive `confirm` calls
+    pub fn with_confirm_results(
+        self,
+        results: impl IntoIterator<Item = PendingOperationResult>,
+    ) -> Self {
+        Self {
+            confirm_results: results.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Sets the outcome and estimated cost reported after submission
+    pub fn with_operation_outcome(self, outcome: TxOutcome, tx_cost_estimate: U256) -> Self {
+        Self {
+            submission_outcome: Some(outcome),
+            tx_cost_estimate: Some(tx_cost_estimate),
+            ..self
+        }
+    }
+
+    /// Number of retries set through `set_retries`
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+}
+
+impl TryBatchAs<HyperlaneMessage> for MockPendingOperation {}
+
+#[async_trait]
+#[typetag::serialize]
+impl PendingOperation for MockPendingOperation {
+    fn id(&self) -> H256 {
+        self.id
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    fn origin_domain_id(&self) -> u32 {
+        self.origin_domain_id
+    }
+
+    fn retrieve_status_from_db(&self) -> Option<PendingOperationStatus> {
+        None
+    }
+
+    fn destination_domain(&self) -> &HyperlaneDomain {
+        &self.destination_domain
+    }
+
+    fn sender_address(&self) -> &H256 {
+        &self.sender_address
+    }
+
+    fn recipient_address(&self) -> &H256 {
+        &self.recipient_address
+    }
+
+    fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    fn app_context(&self) -> Option<String> {
+        self.app_context.clone()
+    }
+
+    fn get_metric(&self) -> Option<Arc<IntGauge>> {
+        self.metric.clone()
+    }
+
+    fn set_metric(&mut self, metric: Arc<IntGauge>) {
+        self.metric = Some(metric);
+    }
+
+    fn status(&self) -> PendingOperationStatus {
+        self.status.clone()
+    }
+
+    fn set_status(&mut self, status: PendingOperationStatus) {
+        self.status = status;
+    }
+
+    async fn prepare(&mut self) -> PendingOperationResult {
+        self.prepare_results
+            .pop_front()
+            .unwrap_or(PendingOperationResult::Success)
+    }
+
+    async fn submit(&mut self) -> PendingOperationResult {
+        self.submit_results
+            .pop_front()
+            .unwrap_or(PendingOperationResult::Success)
+    }
+
+    fn set_submission_outcome(&mut self, outcome: TxOutcome) {
+        self.submission_outcome = Some(outcome);
+    }
+
+    fn get_submission_outcome(&self) -> Option<TxOutcome> {
+        self.submission_outcome.clone()
+    }
+
+    fn get_tx_cost_estimate(&self) -> Option<U256> {
+        self.tx_cost_estimate
+    }
+
+    async fn confirm(&mut self) -> PendingOperationResult {
+        self.confirm_results
+            .pop_front()
+            .unwrap_or(PendingOperationResult::Success)
+    }
+
+    fn set_operation_outcome(
+        &mut self,
+        submission_outcome: TxOutcome,
+        submission_estimated_cost: U256,
+    ) {
+        self.submission_outcome = Some(submission_outcome);
+        self.tx_cost_estimate = Some(submission_estimated_cost);
+    }
+
+    fn next_attempt_after(&self) -> Option<Instant> {
+        Some(
+            Instant::now()
+                .checked_add(Duration::from_secs(self.seconds_to_next_attempt))
+                .unwrap(),
+        )
+    }
+
+    fn set_next_attempt_after(&mut self, delay: Duration) {
+        self.seconds_to_next_attempt = delay.as_secs();
+    }
+
+    fn reset_attempts(&mut self) {
+        self.seconds_to_next_attempt = 0;
+    }
+
+    fn set_retries(&mut self, retries: u32) {
+        self.retries = retries;
+    }
+}
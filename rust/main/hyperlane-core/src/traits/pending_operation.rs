@@ -3,7 +3,7 @@ use std::{
     fmt::{Debug, Display},
     io::Write,
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
@@ -17,7 +17,7 @@ use hyperlane_application::ApplicationReport;
 
 use crate::{
     ChainResult, Decode, Encode, FixedPointNumber, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProtocolError, Mailbox, TryBatchAs, TxOutcome, H256, U256,
+    HyperlaneProtocolError, Mailbox, TryBatchAs, TxOutcome, H256, H512, U256,
 };
 
 /// Boxed operation that can be stored in an operation queue
@@ -70,6 +70,9 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
     /// The recipient address of this operation.
     fn recipient_address(&self) -> &H256;
 
+    /// The body of the message this operation delivers.
+    fn body(&self) -> &[u8];
+
     /// Label to use for metrics granularity.
     fn app_context(&self) -> Option<String>;
 
@@ -100,6 +103,13 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
         new_metric: Arc<IntGauge>,
     ) {
         if let Some(status) = status {
+            let previous = self.status();
+            if previous != status {
+                self.record_lifecycle_event(OperationLifecycleEvent::StatusChanged {
+                    from: previous,
+                    to: status.clone(),
+                });
+            }
             self.set_status(status);
         }
         if let Some(old_metric) = self.get_metric() {
@@ -109,6 +119,9 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
         self.set_metric(new_metric);
     }
 
+    /// Record an event in the lifecycle log of this operation, if it keeps one.
+    fn record_lifecycle_event(&self, _event: OperationLifecycleEvent) {}
+
     /// Get tuple of labels for metrics.
     fn get_operation_labels(&self) -> (String, String) {
         let app_context = self.app_context().unwrap_or("Unknown".to_string());
@@ -127,7 +140,10 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
     /// Set the outcome of the `submit` call
     fn set_submission_outcome(&mut self, outcome: TxOutcome);
 
-    /// Get the estimated the cost of the `submit` call
+    /// Get the outcome of the `submit` call, if this operation submitted a transaction
+    fn get_submission_outcome(&self) -> Option<TxOutcome>;
+
+    /// Get the estimated gas units the `submit` call will use
     fn get_tx_cost_estimate(&self) -> Option<U256>;
 
     /// This will be called after the operation has been submitted and is
@@ -180,6 +196,9 @@ pub enum PendingOperationStatus {
     /// The operation has been submitted and is awaiting confirmation
     #[strum(to_string = "Confirm({0})")]
     Confirm(ConfirmReason),
+    /// The operation is ready to be submitted, but submission is held back by a limit
+    #[strum(to_string = "Held({0})")]
+    Held(HoldReason),
 }
 
 impl Encode for PendingOperationStatus {
@@ -259,6 +278,9 @@ pub enum ReprepareReason {
     #[strum(to_string = "ApplicationReport({0})")]
     /// Application report
     ApplicationReport(ApplicationReport),
+    #[strum(to_string = "Moved to the prepare queue by an operator")]
+    /// Moved to the prepare queue by an operator
+    ManuallyMoved,
 }
 
 #[derive(Display, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -276,6 +298,134 @@ pub enum ConfirmReason {
     ErrorConfirmingDelivery,
     /// Error storing delivery outcome
     ErrorRecordingProcessSuccess,
+    #[strum(to_string = "Moved to the confirm queue by an operator")]
+    /// Moved to the confirm queue by an operator
+    ManuallyMoved,
+}
+
+#[derive(Display, Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+/// Reasons for holding back the submission of an operation
+/// WARNING: This enum is serialized to JSON and stored in the database, so to keep backwards compatibility, we shouldn't remove or rename any variants.
+/// Adding new variants is fine.
+pub enum HoldReason {
+    #[strum(to_string = "Destination spend budget exhausted")]
+    /// The spend budget for the destination's current window is used up
+    SpendBudgetExhausted,
+    #[strum(to_string = "Destination transaction rate limit reached")]
+    /// The destination's transactions-per-minute limit is reached
+    TxRateLimited,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+/// Something that happened to a pending operation, kept to explain how it was processed
+/// WARNING: This enum is serialized to JSON and stored in the database, so to keep backwards compatibility, we shouldn't remove or rename any variants.
+/// Adding new variants is fine.
+pub enum OperationLifecycleEvent {
+    /// The status of the operation changed
+    StatusChanged {
+        /// Status before the change
+        from: PendingOperationStatus,
+        /// Status after the change
+        to: PendingOperationStatus,
+    },
+    /// Metadata was built for the operation, or failed to be
+    MetadataBuildAttempt {
+        /// Why building the metadata failed, if it did
+        error: Option<String>,
+    },
+    /// The gas payment policy was checked for the operation
+    GasPolicyDecision {
+        /// The gas limit allowed by the policy, if it was met
+        gas_limit: Option<U256>,
+        /// Why the policy was not met, if it wasn't
+        rejection: Option<ReprepareReason>,
+    },
+    /// A transaction delivering the operation was sent
+    TransactionSubmitted {
+        /// Hash of the transaction
+        transaction_id: H512,
+        /// Whether the transaction executed successfully
+        executed: bool,
+        /// Gas used by the operation
+        gas_used: U256,
+    },
+    /// Sending a transaction delivering the operation failed
+    SubmissionFailed {
+        /// The error returned by the chain
+        error: String,
+    },
+    /// The delivery of the operation was checked on the destination
+    ConfirmAttempt {
+        /// Whether the operation was found to be delivered
+        delivered: bool,
+        /// Why checking the delivery failed, if it did
+        error: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// An `OperationLifecycleEvent` and when it happened
+pub struct OperationLifecycleEntry {
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// What happened
+    #[serde(flatten)]
+    pub event: OperationLifecycleEvent,
+}
+
+impl OperationLifecycleEntry {
+    /// Timestamps `event` with the current time
+    pub fn new(event: OperationLifecycleEvent) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            event,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+/// The most recent lifecycle events of an operation, oldest first
+pub struct OperationLifecycleLog(pub Vec<OperationLifecycleEntry>);
+
+impl OperationLifecycleLog {
+    /// Appends `entry`, dropping the oldest entries beyond `max_len`
+    pub fn push_bounded(&mut self, entry: OperationLifecycleEntry, max_len: usize) {
+        self.0.push(entry);
+        let excess = self.0.len().saturating_sub(max_len);
+        self.0.drain(..excess);
+    }
+}
+
+impl Encode for OperationLifecycleLog {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        // Serialized as JSON, like `PendingOperationStatus`
+        let serialized = serde_json::to_vec(self)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Failed to serialize"))?;
+        writer.write(&serialized)
+    }
+}
+
+impl Decode for OperationLifecycleLog {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        serde_json::from_reader(reader).map_err(|err| {
+            HyperlaneProtocolError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to deserialize. Error: {}", err),
+            ))
+        })
+    }
 }
 
 /// Utility fn to calculate the total estimated cost of an operation batch
@@ -357,7 +507,7 @@ impl Ord for QueueOperation {
 }
 
 /// Possible outcomes of performing an action on a pending operation (such as `prepare`, `submit` or `confirm`).
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PendingOperationResult {
     /// Promote to the next step
     Success,
@@ -382,4 +532,29 @@ mod test {
         let decoded = PendingOperationStatus::read_from(&mut &encoded[..]).unwrap();
         assert_eq!(status, decoded);
     }
+
+    #[test]
+    fn test_lifecycle_log_is_bounded_and_encodable() {
+        let mut log = OperationLifecycleLog::default();
+        for i in 0..5 {
+            log.push_bounded(
+                OperationLifecycleEntry {
+                    timestamp: i,
+                    event: OperationLifecycleEvent::ConfirmAttempt {
+                        delivered: false,
+                        error: None,
+                    },
+                },
+                3,
+            );
+        }
+        assert_eq!(
+            log.0.iter().map(|e| e.timestamp).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+
+        let encoded = log.to_vec();
+        let decoded = OperationLifecycleLog::read_from(&mut &encoded[..]).unwrap();
+        assert_eq!(log, decoded);
+    }
 }
@@ -317,8 +317,38 @@ export enum GasPaymentEnforcementPolicyType {
   None = 'none',
   Minimum = 'minimum',
   OnChainFeeQuoting = 'onChainFeeQuoting',
+  UsdValue = 'usdValue',
 }
 
+const ZNonNegativeDecimal = z.union([
+  z.number().nonnegative(),
+  z.string().regex(/^\d+(\.\d+)?$/),
+]);
+
+const TokenPriceOracleSchema = z.discriminatedUnion('type', [
+  z.object({
+    type: z.literal('static'),
+    prices: z
+      .record(ZNonNegativeDecimal)
+      .describe('The USD price of one native token, by chain name.'),
+  }),
+  z.object({
+    type: z.literal('http'),
+    url: z
+      .string()
+      .url()
+      .describe(
+        'An endpoint returning a JSON object of chain names to native token USD prices.',
+      ),
+    cacheTtl: ZNzUint.optional().describe(
+      'How long in seconds fetched prices are reused. Defaults to 60.',
+    ),
+    maxStaleness: ZNzUint.optional().describe(
+      'How old in seconds the last fetched prices may get while fetching fresh ones fails, after which lookups error. Must be at least cacheTtl. Defaults to 900.',
+    ),
+  }),
+]);
+
 const GasPaymentEnforcementBaseSchema = z.object({
   matchingList: MatchingListSchema.optional().describe(
     'An optional matching list, any message that matches will use this policy. By default all messages will match.',
@@ -339,6 +369,21 @@ const GasPaymentEnforcementSchema = z.union([
       .regex(/^\d+ ?\/ ?[1-9]\d*$/)
       .optional(),
   }),
+  GasPaymentEnforcementBaseSchema.extend({
+    type: z.literal(GasPaymentEnforcementPolicyType.UsdValue),
+    oracle: TokenPriceOracleSchema.describe(
+      'The source of native token USD prices.',
+    ),
+    margin: ZNonNegativeDecimal.optional().describe(
+      'Fraction of the delivery cost required on top of it, e.g. 0.1 requires payments worth 110% of the cost. Defaults to 0.',
+    ),
+    tokenDecimals: z
+      .record(ZUint)
+      .optional()
+      .describe(
+        "Decimals of native tokens, by chain name. Defaults to the chain's native token decimals, and is required for chains where they are unknown.",
+      ),
+  }),
 ]);
 export type GasPaymentEnforcement = z.infer<typeof GasPaymentEnforcementSchema>;
 
@@ -347,6 +392,28 @@ const MetricAppContextSchema = z.object({
   matchingList: MatchingListSchema.describe(
     'A matching list, any message that matches will be classified as this app context.',
   ),
+  weight: ZNzUint.optional().describe(
+    'Share of the relayer op queues given to this app context relative to others. Setting this on any app context enables fair scheduling; unlisted app contexts default to 1.',
+  ),
+  maxInFlight: ZNzUint.optional().describe(
+    'Max operations of this app context to have past the prepare queue at once, per destination. Unlimited if unset.',
+  ),
+});
+
+const SubmissionLimitsSchema = z.object({
+  destination: z
+    .string()
+    .min(1)
+    .describe('The name of the destination chain the limits apply to.'),
+  spendBudget: ZUWei.optional().describe(
+    'Max native tokens (in the smallest unit) to spend on transactions during any spend window. Unlimited if unset.',
+  ),
+  spendWindow: ZNzUint.optional().describe(
+    'The rolling window in seconds the spend budget applies to. Defaults to 3600.',
+  ),
+  maxTxPerMinute: ZNzUint.optional().describe(
+    'Max transactions to submit per minute. Unlimited if unset.',
+  ),
 });
 
 export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
@@ -379,7 +446,19 @@ export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
   addressBlacklist: z
     .string()
     .optional()
-    .describe('Comma separated list of addresses to blacklist.'),
+    .describe(
+      'Comma separated list of addresses to blacklist. Entries may be typed by protocol, e.g. `evm:0x...`, `sealevel:<base58>`, `cosmos:<bech32>` or `ton:<address>`; untyped hex entries are matched as raw bytes.',
+    ),
+  addressBlacklistSource: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'A file path or http(s) URL to periodically reload additional blacklisted addresses from, one or more comma separated entries per line.',
+    ),
+  addressBlacklistRefreshInterval: ZNzUint.optional().describe(
+    'How often to reload `addressBlacklistSource`, in seconds. Defaults to 300.',
+  ),
   transactionGasLimit: ZUWei.optional().describe(
     'This is optional. If not specified, any amount of gas will be valid, otherwise this is the max allowed gas in wei to relay a transaction.',
   ),
@@ -390,7 +469,7 @@ export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
     .boolean()
     .optional()
     .describe(
-      'If true, allows local storage based checkpoint syncers. Not intended for production use.',
+      'If true, allows checkpoint syncers reading from the local machine or network, e.g. local storage or private HTTP hosts, and HTTP locations not served over https. Not intended for production use.',
     ),
   metricAppContexts: z
     .union([z.array(MetricAppContextSchema), z.string().min(1)])
@@ -398,6 +477,22 @@ export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
     .describe(
       'A list of app contexts and their matching lists to use for metrics. A message will be classified as the first matching app context.',
     ),
+  adminApiKey: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'Bearer token for the operation admin API (pause, resume, drop and move). The API is disabled if unset.',
+    ),
+  submissionLimits: z
+    .union([z.array(SubmissionLimitsSchema), z.string().min(1)])
+    .optional()
+    .describe(
+      'Per-destination limits on transaction spend and rate. Operations are held in the submit queue while a limit is reached.',
+    ),
+  gasAccountingInterval: ZNzUint.optional().describe(
+    'How often in seconds to rebuild the gas payment and expenditure report served at /gas_accounting. Defaults to 300.',
+  ),
 });
 
 export type RelayerConfig = z.infer<typeof RelayerAgentConfigSchema>;
@@ -411,60 +506,116 @@ export const ScraperAgentConfigSchema = AgentConfigSchema.extend({
 
 export type ScraperConfig = z.infer<typeof ScraperAgentConfigSchema>;
 
+const CheckpointSyncerSchema = z.discriminatedUnion('type', [
+  z
+    .object({
+      type: z.literal('localStorage'),
+      path: z.string().min(1).describe('Path to the local storage location'),
+    })
+    .describe('A local checkpoint syncer'),
+  z
+    .object({
+      type: z.literal('s3'),
+      bucket: z.string().min(1),
+      region: z.string().min(1),
+      folder: z
+        .string()
+        .min(1)
+        .optional()
+        .describe(
+          'The folder/key-prefix to use, defaults to the root of the bucket',
+        ),
+    })
+    .describe('A checkpoint syncer that uses S3'),
+  z
+    .object({
+      type: z.literal('gcs'),
+      bucket: z.string().min(1),
+      folder: z
+        .string()
+        .min(1)
+        .optional()
+        .describe('The folder to use, defaults to the root of the bucket'),
+      service_account_key: z
+        .string()
+        .min(1)
+        .optional()
+        .describe('The path to GCS service account key file'),
+      user_secrets: z
+        .string()
+        .min(1)
+        .optional()
+        .describe('The path to GCS user secret file'),
+    })
+    .describe('A checkpoint syncer that uses Google Cloud Storage'),
+  z
+    .object({
+      type: z.literal('azure'),
+      account: z.string().min(1).describe('The storage account name'),
+      container: z.string().min(1),
+      folder: z
+        .string()
+        .min(1)
+        .optional()
+        .describe('The folder to use, defaults to the root of the container'),
+      sasToken: z
+        .string()
+        .min(1)
+        .optional()
+        .describe(
+          'A SAS token authorizing writes to the container, defaults to the AZURE_STORAGE_SAS_TOKEN env var',
+        ),
+    })
+    .describe('A checkpoint syncer that uses Azure Blob Storage'),
+]);
+
 export const ValidatorAgentConfigSchema = AgentConfigSchema.extend({
   db: z
     .string()
     .min(1)
     .optional()
-    .describe('The path to the validator database.'),
+    .describe(
+      'The path to the validator database. When validating several chains, it is shared by the chains that do not set their own db in originChains. Defaults to validator_db_<chain> for each chain.',
+    ),
   originChainName: z
     .string()
     .min(1)
-    .describe('Name of the chain to validate messages on'),
+    .describe(
+      'Name of the chain to validate messages on, or a comma separated list of chain names to validate messages on from one process',
+    ),
   validator: AgentSignerSchema.describe('The validator attestation signer'),
-  checkpointSyncer: z.discriminatedUnion('type', [
-    z
-      .object({
-        type: z.literal('localStorage'),
-        path: z.string().min(1).describe('Path to the local storage location'),
-      })
-      .describe('A local checkpoint syncer'),
-    z
-      .object({
-        type: z.literal('s3'),
-        bucket: z.string().min(1),
-        region: z.string().min(1),
-        folder: z
-          .string()
-          .min(1)
-          .optional()
-          .describe(
-            'The folder/key-prefix to use, defaults to the root of the bucket',
-          ),
-      })
-      .describe('A checkpoint syncer that uses S3'),
-    z
-      .object({
-        type: z.literal('gcs'),
-        bucket: z.string().min(1),
-        folder: z
-          .string()
-          .min(1)
-          .optional()
-          .describe('The folder to use, defaults to the root of the bucket'),
-        service_account_key: z
-          .string()
-          .min(1)
-          .optional()
-          .describe('The path to GCS service account key file'),
-        user_secrets: z
+  checkpointSyncer: CheckpointSyncerSchema.optional().describe(
+    'The primary checkpoint syncer. Either this or checkpointSyncers must be set.',
+  ),
+  checkpointSyncers: z
+    .array(CheckpointSyncerSchema)
+    .optional()
+    .describe(
+      'Checkpoint syncers to mirror checkpoints to, in addition to checkpointSyncer',
+    ),
+  checkpointSyncerQuorum: ZNzUint.optional().describe(
+    'How many checkpoint syncers a write must succeed on, defaults to all of them',
+  ),
+  originChains: z
+    .record(
+      z.object({
+        checkpointSyncer: CheckpointSyncerSchema.optional(),
+        checkpointSyncers: z.array(CheckpointSyncerSchema).optional(),
+        checkpointSyncerQuorum: ZNzUint.optional(),
+        db: z
           .string()
           .min(1)
           .optional()
-          .describe('The path to GCS user secret file'),
-      })
-      .describe('A checkpoint syncer that uses Google Cloud Storage'),
-  ]),
+          .describe('The path to the database of this chain'),
+      }),
+    )
+    .optional()
+    .describe(
+      'Checkpoint syncers and database per origin chain name, overriding the top level ones. Checkpoint syncers are required for each chain when validating multiple chains.',
+    ),
+  checkpointSyncerReconcileInterval: ZUint.optional().describe(
+    'How often to backfill lagging checkpoint syncers in seconds, defaults to 60.',
+  ),
   interval: ZUint.optional().describe(
     'How long to wait between checking for new checkpoints in seconds.',
   ),
@@ -4,7 +4,7 @@
  */
 import { z } from 'zod';
 
-import { ZHash, ZNzUint } from './customZodTypes.js';
+import { ZHash, ZNzUint, ZUWei } from './customZodTypes.js';
 
 const DomainSchema = z.union([
   z.literal('*'),
@@ -14,12 +14,26 @@ const DomainSchema = z.union([
 
 const AddressSchema = z.union([z.literal('*'), ZHash, z.array(ZHash)]);
 
+const HexBytes = z.string().regex(/^(0x)?([0-9a-fA-F]{2})*$/);
+
+const BodyPrefixSchema = z.union([
+  z.literal('*'),
+  HexBytes,
+  z.array(HexBytes),
+]);
+
+const AmountSchema = z.union([ZUWei, z.string().regex(/^0x[0-9a-fA-F]+$/)]);
+
 const MatchingListElementSchema = z.object({
   messageId: AddressSchema.optional(),
   originDomain: DomainSchema.optional(),
   senderAddress: AddressSchema.optional(),
   destinationDomain: DomainSchema.optional(),
   recipientAddress: AddressSchema.optional(),
+  bodyPrefix: BodyPrefixSchema.optional(),
+  tokenRecipient: AddressSchema.optional(),
+  minAmount: AmountSchema.optional(),
+  maxAmount: AmountSchema.optional(),
 });
 
 export const MatchingListSchema = z.array(MatchingListElementSchema);